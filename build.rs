@@ -0,0 +1,34 @@
+// 构建脚本：把 locales/ 目录下的所有消息目录（*.ftl）嵌入二进制
+// 新增语言只需添加一个目录文件，例如 locales/de.ftl
+
+use std::path::Path;
+
+fn main() {
+    let locales = Path::new(env!("CARGO_MANIFEST_DIR")).join("locales");
+    println!("cargo:rerun-if-changed={}", locales.display());
+
+    let mut catalogs: Vec<(String, String)> = std::fs::read_dir(&locales)
+        .expect("read locales directory")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ftl"))
+        .map(|path| {
+            let lang = path.file_stem().unwrap().to_string_lossy().into_owned();
+            (lang, path.display().to_string())
+        })
+        .collect();
+    catalogs.sort();
+
+    let entries: String = catalogs
+        .iter()
+        .map(|(lang, path)| format!("    ({:?}, include_str!({:?})),\n", lang, path))
+        .collect();
+    let out = Path::new(&std::env::var("OUT_DIR").unwrap()).join("catalogs.rs");
+    std::fs::write(
+        out,
+        format!(
+            "/// 内置的消息目录：(语言标签, Fluent 源文本)\npub static CATALOGS: &[(&str, &str)] = &[\n{}];\n",
+            entries
+        ),
+    )
+    .expect("write catalogs.rs");
+}
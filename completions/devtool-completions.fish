@@ -1,48 +1,213 @@
 # Print an optspec for argparse to handle cmd's options that are independent of any subcommand.
 function __fish_devtool_global_optspecs
-	string join \n h/help V/version
+    string join \n lang= no-redact print-locale h/help V/version
 end
 
 function __fish_devtool_needs_command
-	# Figure out if the current invocation already has a command.
-	set -l cmd (commandline -opc)
-	set -e cmd[1]
-	argparse -s (__fish_devtool_global_optspecs) -- $cmd 2>/dev/null
-	or return
-	if set -q argv[1]
-		# Also print the command, so this can be used to figure out what it is.
-		echo $argv[1]
-		return 1
-	end
-	return 0
+    # Figure out if the current invocation already has a command.
+    set -l cmd (commandline -opc)
+    set -e cmd[1]
+    argparse -s (__fish_devtool_global_optspecs) -- $cmd 2>/dev/null
+    or return
+    if set -q argv[1]
+        # Also print the command, so this can be used to figure out what it is.
+        echo $argv[1]
+        return 1
+    end
+    return 0
 end
 
 function __fish_devtool_using_subcommand
-	set -l cmd (__fish_devtool_needs_command)
-	test -z "$cmd"
-	and return 1
-	contains -- $cmd[1] $argv
+    set -l cmd (__fish_devtool_needs_command)
+    test -z "$cmd"
+    and return 1
+    contains -- $cmd[1] $argv
 end
 
-complete -c devtool -n "__fish_devtool_needs_command" -s h -l help -d 'Print help'
+complete -c devtool -n "__fish_devtool_needs_command" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_needs_command" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_needs_command" -l print-locale -d '显示界面语言的协商过程后退出'
+complete -c devtool -n "__fish_devtool_needs_command" -s h -l help -d 'Print help (see more with \'--help\')'
 complete -c devtool -n "__fish_devtool_needs_command" -s V -l version -d 'Print version'
 complete -c devtool -n "__fish_devtool_needs_command" -f -a "update" -d '更新开发工具（默认命令）'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "status" -d '显示正在运行的更新和更新策略状态（维护窗口、冻结期和下一个允许的时间）'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "logs" -d '列出保存的运行日志，在 $PAGER 中打开步骤日志，或跟随正在运行的步骤'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "serve" -d '启动本地 HTTP API，供仪表盘和机器人查询状态、触发更新'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "schedule" -d '管理定时更新（systemd 用户定时器，或 crontab）'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "sync" -d '按团队清单安装、升级或降级工具'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "check" -d '检查已安装版本是否符合团队清单，存在偏差时以非零状态退出'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "snapshot" -d '导出当前环境快照（已安装的工具、版本、tap、工具链组件和目标）'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "diff" -d '对比两个环境快照，或将本机与快照对比'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "bootstrap" -d '根据环境状态文件初始化新机器，安装缺失的工具（可中断后继续）'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "doctor" -d '诊断开发环境中常见的更新失败原因'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "conflicts" -d '列出被多个工具同时提供的命令及 PATH 中实际生效的副本'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "which" -d '显示命令由哪个工具提供、当前版本及升级方式'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "upgrade" -d '只升级提供指定命令的项目'
 complete -c devtool -n "__fish_devtool_needs_command" -f -a "completion" -d '生成 shell 补全脚本'
-complete -c devtool -n "__fish_devtool_needs_command" -f -a "progress-status" -d '显示进度状态'
+complete -c devtool -n "__fish_devtool_needs_command" -f -a "feedback" -d '收集用户反馈'
 complete -c devtool -n "__fish_devtool_needs_command" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
-complete -c devtool -n "__fish_devtool_using_subcommand update" -l jobs -d '并行任务数量限制' -r
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l jobs -d '并行任务数量限制，至少为 1' -r
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l min-age -d '新版本隔离期，首次发现未满该时长的版本暂缓升级（如 7d、36h，覆盖配置文件）' -r
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l save-plan -d '将交互式选择保存为升级计划，不执行升级' -r -F
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l plan -d '执行保存的升级计划' -r -F
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
 complete -c devtool -n "__fish_devtool_using_subcommand update" -s n -l dry-run -d '模拟执行，不实际运行命令'
 complete -c devtool -n "__fish_devtool_using_subcommand update" -s v -l verbose -d '详细输出模式'
 complete -c devtool -n "__fish_devtool_using_subcommand update" -l no-color -d '禁用彩色输出'
-complete -c devtool -n "__fish_devtool_using_subcommand update" -l keep-logs -d '保留日志文件到 ~/.cache/devtool/'
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l keep-logs -d '成功的运行也保留日志到 ~/.cache/devtool/runs/（失败的运行总是保留）'
 complete -c devtool -n "__fish_devtool_using_subcommand update" -l parallel -d '并行执行更新步骤 (默认启用)'
 complete -c devtool -n "__fish_devtool_using_subcommand update" -l sequential -d '顺序执行更新步骤 (覆盖并行模式)'
 complete -c devtool -n "__fish_devtool_using_subcommand update" -l no-banner -d '不显示启动横幅'
 complete -c devtool -n "__fish_devtool_using_subcommand update" -l compact -d '使用紧凑输出格式（适用于非交互环境）'
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l accessible -d '屏幕阅读器友好的输出：不显示进度条和图标，每次状态变化输出一个完整的句子'
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l force -d '忽略维护窗口和冻结期限制，强制执行更新'
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l scheduled -d '以计划任务模式运行：不在允许的时间内时静默跳过'
+complete -c devtool -n "__fish_devtool_using_subcommand update" -s i -l interactive -d '在终端界面中选择要升级的项目'
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l tui -d '使用全屏仪表盘显示每个工具的实时日志（非终端环境下回退为进度条）'
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l wait -d '另一个 devtool 运行正在进行时等待其结束，而不是立即退出'
+complete -c devtool -n "__fish_devtool_using_subcommand update" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
 complete -c devtool -n "__fish_devtool_using_subcommand update" -s h -l help -d 'Print help'
+complete -c devtool -n "__fish_devtool_using_subcommand status" -l format -d '输出格式' -r -f -a "text\t'人类可读文本'
+json\t'JSON（适用于脚本和 CI）'"
+complete -c devtool -n "__fish_devtool_using_subcommand status" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand status" -s w -l watch -d '持续显示正在运行的更新状态，每次变化输出一行'
+complete -c devtool -n "__fish_devtool_using_subcommand status" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand status" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and not __fish_seen_subcommand_from grep help" -l run -d '选择第 N 次运行（1 为最近一次，也可以是运行目录的时间戳）' -r
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and not __fish_seen_subcommand_from grep help" -l step -d '在 $PAGER 中打开该步骤的日志（如 upgrade、homebrew/upgrade，或完整文件名）' -r
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and not __fish_seen_subcommand_from grep help" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and not __fish_seen_subcommand_from grep help" -s f -l follow -d '持续输出正在运行的步骤日志，直到更新结束'
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and not __fish_seen_subcommand_from grep help" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and not __fish_seen_subcommand_from grep help" -s h -l help -d 'Print help'
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and not __fish_seen_subcommand_from grep help" -a "grep" -d '在保存的日志中搜索（正则表达式），可按工具、运行和步骤限定范围'
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and not __fish_seen_subcommand_from grep help" -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and __fish_seen_subcommand_from grep" -l run -d '只搜索第 N 次运行（1 为最近一次，也可以是运行目录的时间戳）' -r
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and __fish_seen_subcommand_from grep" -l step -d '只搜索该步骤的日志（如 upgrade、homebrew/upgrade，或完整文件名）' -r
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and __fish_seen_subcommand_from grep" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and __fish_seen_subcommand_from grep" -s i -l ignore-case -d '忽略大小写'
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and __fish_seen_subcommand_from grep" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and __fish_seen_subcommand_from grep" -s h -l help -d 'Print help'
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and __fish_seen_subcommand_from help" -f -a "grep" -d '在保存的日志中搜索（正则表达式），可按工具、运行和步骤限定范围'
+complete -c devtool -n "__fish_devtool_using_subcommand logs; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c devtool -n "__fish_devtool_using_subcommand serve" -l listen -d '监听地址（只允许本机回环地址）' -r
+complete -c devtool -n "__fish_devtool_using_subcommand serve" -l socket -d '改为监听 Unix 套接字' -r -F
+complete -c devtool -n "__fish_devtool_using_subcommand serve" -l token -d '访问令牌（默认读取 DEVTOOL_SERVE_TOKEN，未设置时随机生成）' -r
+complete -c devtool -n "__fish_devtool_using_subcommand serve" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand serve" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand serve" -s h -l help -d 'Print help'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and not __fish_seen_subcommand_from install status remove help" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and not __fish_seen_subcommand_from install status remove help" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and not __fish_seen_subcommand_from install status remove help" -s h -l help -d 'Print help'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and not __fish_seen_subcommand_from install status remove help" -f -a "install" -d '安装并启用定时更新'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and not __fish_seen_subcommand_from install status remove help" -f -a "status" -d '显示已安装的定时更新和最近一次定时运行'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and not __fish_seen_subcommand_from install status remove help" -f -a "remove" -d '移除定时更新'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and not __fish_seen_subcommand_from install status remove help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from install" -l daily -d '每天运行的时间（HH:MM，本地时间）' -r
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from install" -l days -d '只在指定的星期运行（如 Mon-Fri、weekends）' -r
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from install" -l backend -d '实现方式（默认优先 systemd，不可用时使用 cron）' -r -f -a "systemd\t'systemd 用户服务和定时器'
+cron\t'crontab 条目'"
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from install" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from install" -s n -l dry-run -d '只显示将要生成的内容，不安装'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from install" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from install" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from status" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from status" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from status" -s h -l help -d 'Print help'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from remove" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from remove" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from remove" -s h -l help -d 'Print help'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from help" -f -a "install" -d '安装并启用定时更新'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from help" -f -a "status" -d '显示已安装的定时更新和最近一次定时运行'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from help" -f -a "remove" -d '移除定时更新'
+complete -c devtool -n "__fish_devtool_using_subcommand schedule; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c devtool -n "__fish_devtool_using_subcommand sync" -l manifest -d '团队清单文件（默认在当前目录及上级目录查找 devtool.team.toml）' -r -F
+complete -c devtool -n "__fish_devtool_using_subcommand sync" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand sync" -s n -l dry-run -d '只显示将要执行的操作'
+complete -c devtool -n "__fish_devtool_using_subcommand sync" -s v -l verbose -d '详细输出模式'
+complete -c devtool -n "__fish_devtool_using_subcommand sync" -l wait -d '另一个 devtool 运行正在进行时等待其结束，而不是立即退出'
+complete -c devtool -n "__fish_devtool_using_subcommand sync" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand sync" -s h -l help -d 'Print help'
+complete -c devtool -n "__fish_devtool_using_subcommand check" -l manifest -d '团队清单文件（默认在当前目录及上级目录查找 devtool.team.toml）' -r -F
+complete -c devtool -n "__fish_devtool_using_subcommand check" -l format -d '输出格式' -r -f -a "text\t'人类可读文本'
+json\t'JSON（适用于脚本和 CI）'"
+complete -c devtool -n "__fish_devtool_using_subcommand check" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand check" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand check" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c devtool -n "__fish_devtool_using_subcommand snapshot" -s o -l output -d '输出文件（默认输出到标准输出）' -r -F
+complete -c devtool -n "__fish_devtool_using_subcommand snapshot" -l format -d '导出格式（默认根据输出文件名推断，否则为 JSON）' -r -f -a "json\t'完整的 JSON 快照'
+brewfile\t'Homebrew 的 Brewfile'
+rust-toolchain\t'默认工具链的 rust-toolchain.toml'
+mise-config\t'mise 的 config.toml'"
+complete -c devtool -n "__fish_devtool_using_subcommand snapshot" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand snapshot" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand snapshot" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c devtool -n "__fish_devtool_using_subcommand diff" -l against -d '以该快照为基准，对比本机当前状态' -r -F
+complete -c devtool -n "__fish_devtool_using_subcommand diff" -l format -d '输出格式' -r -f -a "text\t'人类可读文本'
+json\t'JSON（适用于脚本和 CI）'"
+complete -c devtool -n "__fish_devtool_using_subcommand diff" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand diff" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand diff" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c devtool -n "__fish_devtool_using_subcommand bootstrap" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand bootstrap" -s n -l dry-run -d '只显示安装计划，不实际执行'
+complete -c devtool -n "__fish_devtool_using_subcommand bootstrap" -s v -l verbose -d '详细输出模式'
+complete -c devtool -n "__fish_devtool_using_subcommand bootstrap" -l wait -d '另一个 devtool 运行正在进行时等待其结束，而不是立即退出'
+complete -c devtool -n "__fish_devtool_using_subcommand bootstrap" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand bootstrap" -s h -l help -d 'Print help'
+complete -c devtool -n "__fish_devtool_using_subcommand doctor" -l format -d '输出格式' -r -f -a "text\t'人类可读文本'
+json\t'JSON（适用于脚本和 CI）'"
+complete -c devtool -n "__fish_devtool_using_subcommand doctor" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand doctor" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand doctor" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c devtool -n "__fish_devtool_using_subcommand conflicts" -l format -d '输出格式' -r -f -a "text\t'人类可读文本'
+json\t'JSON（适用于脚本和 CI）'"
+complete -c devtool -n "__fish_devtool_using_subcommand conflicts" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand conflicts" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand conflicts" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c devtool -n "__fish_devtool_using_subcommand which" -l format -d '输出格式' -r -f -a "text\t'人类可读文本'
+json\t'JSON（适用于脚本和 CI）'"
+complete -c devtool -n "__fish_devtool_using_subcommand which" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand which" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand which" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c devtool -n "__fish_devtool_using_subcommand upgrade" -l min-age -d '新版本隔离期，首次发现未满该时长的版本暂缓升级（如 7d、36h，覆盖配置文件）' -r
+complete -c devtool -n "__fish_devtool_using_subcommand upgrade" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand upgrade" -s n -l dry-run -d '只显示升级命令，不实际执行'
+complete -c devtool -n "__fish_devtool_using_subcommand upgrade" -s v -l verbose -d '详细输出模式'
+complete -c devtool -n "__fish_devtool_using_subcommand upgrade" -l wait -d '另一个 devtool 运行正在进行时等待其结束，而不是立即退出'
+complete -c devtool -n "__fish_devtool_using_subcommand upgrade" -l force -d '忽略维护窗口和冻结期限制，强制执行升级'
+complete -c devtool -n "__fish_devtool_using_subcommand upgrade" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand upgrade" -s h -l help -d 'Print help'
+complete -c devtool -n "__fish_devtool_using_subcommand completion" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand completion" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
 complete -c devtool -n "__fish_devtool_using_subcommand completion" -s h -l help -d 'Print help (see more with \'--help\')'
-complete -c devtool -n "__fish_devtool_using_subcommand progress-status" -s h -l help -d 'Print help'
-complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update completion progress-status help" -f -a "update" -d '更新开发工具（默认命令）'
-complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update completion progress-status help" -f -a "completion" -d '生成 shell 补全脚本'
-complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update completion progress-status help" -f -a "progress-status" -d '显示进度状态'
-complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update completion progress-status help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c devtool -n "__fish_devtool_using_subcommand feedback" -s t -l type -d '反馈类型' -r -f -a "bug\t'Bug 报告'
+feature\t'功能请求'
+ux\t'用户体验问题'
+performance\t'性能问题'
+documentation\t'文档问题'
+other\t'其他'"
+complete -c devtool -n "__fish_devtool_using_subcommand feedback" -s m -l message -d '反馈内容' -r
+complete -c devtool -n "__fish_devtool_using_subcommand feedback" -l lang -d '界面语言（如 en、zh），优先于配置文件和系统环境' -r
+complete -c devtool -n "__fish_devtool_using_subcommand feedback" -s v -l verbose -d '详细模式'
+complete -c devtool -n "__fish_devtool_using_subcommand feedback" -l no-redact -d '不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏'
+complete -c devtool -n "__fish_devtool_using_subcommand feedback" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "update" -d '更新开发工具（默认命令）'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "status" -d '显示正在运行的更新和更新策略状态（维护窗口、冻结期和下一个允许的时间）'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "logs" -d '列出保存的运行日志，在 $PAGER 中打开步骤日志，或跟随正在运行的步骤'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "serve" -d '启动本地 HTTP API，供仪表盘和机器人查询状态、触发更新'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "schedule" -d '管理定时更新（systemd 用户定时器，或 crontab）'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "sync" -d '按团队清单安装、升级或降级工具'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "check" -d '检查已安装版本是否符合团队清单，存在偏差时以非零状态退出'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "snapshot" -d '导出当前环境快照（已安装的工具、版本、tap、工具链组件和目标）'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "diff" -d '对比两个环境快照，或将本机与快照对比'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "bootstrap" -d '根据环境状态文件初始化新机器，安装缺失的工具（可中断后继续）'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "doctor" -d '诊断开发环境中常见的更新失败原因'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "conflicts" -d '列出被多个工具同时提供的命令及 PATH 中实际生效的副本'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "which" -d '显示命令由哪个工具提供、当前版本及升级方式'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "upgrade" -d '只升级提供指定命令的项目'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "completion" -d '生成 shell 补全脚本'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "feedback" -d '收集用户反馈'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and not __fish_seen_subcommand_from update status logs serve schedule sync check snapshot diff bootstrap doctor conflicts which upgrade completion feedback help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and __fish_seen_subcommand_from logs" -f -a "grep" -d '在保存的日志中搜索（正则表达式），可按工具、运行和步骤限定范围'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and __fish_seen_subcommand_from schedule" -f -a "install" -d '安装并启用定时更新'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and __fish_seen_subcommand_from schedule" -f -a "status" -d '显示已安装的定时更新和最近一次定时运行'
+complete -c devtool -n "__fish_devtool_using_subcommand help; and __fish_seen_subcommand_from schedule" -f -a "remove" -d '移除定时更新'
@@ -0,0 +1,376 @@
+# devtool English message catalog
+#
+# Every user-facing string lives here. Other catalogs (zh.ftl, ...) must
+# define the same message IDs; missing messages fall back to English.
+
+## Durations
+
+duration-hms = { $hours }h { $minutes }m { $seconds }s
+duration-ms = { $minutes }m { $seconds }s
+duration-s = { $seconds }s
+duration-days = { $count ->
+    [one] { $count } day
+   *[other] { $count } days
+}
+duration-hours = { $count ->
+    [one] { $count } hour
+   *[other] { $count } hours
+}
+duration-minutes = { $count ->
+    [one] { $count } minute
+   *[other] { $count } minutes
+}
+duration-seconds = { $count ->
+    [one] { $count } second
+   *[other] { $count } seconds
+}
+duration-separator = {", "}
+
+## Progress bars
+
+progress-preparing = { $tool } preparing...
+progress-executing = { $tool } running...
+progress-done = { $icon } { $tool } done
+progress-failed = { $icon } { $tool } failed
+progress-interrupted = { $icon } { $tool } interrupted
+
+## Compact output (--compact)
+
+compact-started = { $tool }: started
+compact-done = { $tool }: done ({ $elapsed })
+compact-failed = { $tool }: failed ({ $elapsed })
+
+## Accessible output (--accessible)
+
+accessible-steps = Updating { $count ->
+    [one] 1 tool
+   *[other] { $count } tools
+}: { $tools }.
+accessible-started = { $tool } upgrade started.
+accessible-finished = { $tool } upgrade finished, { $count ->
+    [0] nothing to upgrade
+    [one] 1 package upgraded
+   *[other] { $count } packages upgraded
+}.
+accessible-failed = { $tool } upgrade failed.
+accessible-interrupted = { $tool } upgrade was interrupted.
+accessible-complete = Update finished in { $duration }.
+accessible-tool-result = { $tool }: { $count ->
+    [0] already up to date
+    [one] 1 package upgraded
+   *[other] { $count } packages upgraded
+}.
+accessible-tool-failed = { $tool }: failed.
+accessible-upgrade = { $tool } upgraded { $detail }.
+accessible-version-arrow = {" to "}
+accessible-deferred = { $tool } postponed { $name } version { $version } because it is still in quarantine, { $remaining } remaining.
+accessible-unchecked = { $tool } was skipped because new versions could not be listed while the quarantine is enabled.
+accessible-shadowed = { $command } was upgraded with { $via }, but the copy at { $path }, managed by { $owner }, runs first.
+
+## Result tables
+
+summary-header-tool = TOOL
+summary-header-status = STATUS
+summary-header-upgrades = UPGRADES
+summary-status-ok = ok
+summary-status-failed = failed
+summary-upgrades-heading = { $tool } upgrades:
+
+## CI integration
+
+ci-deferred = { $name } { $version } held back by quarantine ({ $remaining } remaining)
+ci-summary-title = devtool update
+ci-summary-tool = Tool
+ci-summary-status = Status
+ci-summary-upgrades = Upgrades
+ci-summary-ok = ✅ ok
+ci-summary-failed = ❌ failed
+ci-summary-details = { $tool } upgrades
+ci-summary-deferred = Held back
+
+## Dashboard (--tui)
+
+dashboard-pending = pending
+dashboard-running = running
+dashboard-done = done
+dashboard-failed = failed
+dashboard-queue = Queue
+dashboard-queue-empty = nothing queued
+dashboard-blocked-by = blocked by { $step }
+dashboard-help = ←/→ select · Enter expand · c cancel step · v verbose · Ctrl-C cancel all
+
+## Interactive upgrade selection
+
+select-title = Pending upgrades
+select-header-tool = Tool
+select-header-name = Name
+select-header-version = Version
+select-header-class = Class
+select-filter = Filter
+select-help-filter = type to filter · Enter done · Esc clear
+select-help = ↑/↓ move · Space toggle · a all of tool · / filter · Enter upgrade · q quit
+
+## Notifications
+
+notify-title-success = devtool update finished on { $host }
+notify-title-failure = devtool update failed on { $host }
+notify-tool-line = { $tool }: { $status }
+notify-tool-failed = failed
+notify-tool-upgraded = { $count ->
+    [0] up to date
+   *[other] { $count } upgraded
+}
+notify-took = Took { $duration }
+
+## Lists
+
+# Separator between items of an inline list
+list-separator = {", "}
+
+## doctor
+
+doctor-git-ok = git found at { $path }
+doctor-git-missing = git is not installed; Homebrew and mise plugins need it
+doctor-git-missing-fix = Install git: xcode-select --install (macOS) or your system package manager
+doctor-tools-missing = None of brew, rustup or mise was found in PATH
+doctor-tools-missing-fix = Install at least one of them, or add its bin directory to PATH
+doctor-tools-ok = Found { $tools }
+doctor-rustc-ok = Only one rustc on PATH
+doctor-rustc-multiple = Multiple rustc on PATH: { $list }
+doctor-rustc-fix-brew = brew uninstall rust  # keep the rustup-managed toolchain
+doctor-rustc-fix = Remove the extra rustc or put ~/.cargo/bin first in PATH
+doctor-mise-ok = mise-managed tools resolve to mise
+doctor-mise-shadowed = Shadowed mise tools: { $list }
+doctor-mise-fix = Activate mise in your shell (eval "$(mise activate zsh)") so its shims come first
+doctor-mise-fix-brew = brew uninstall { $names }  # or activate mise after Homebrew in your shell profile
+doctor-prefix-ok = Homebrew directories under { $prefix } are writable
+doctor-prefix-readonly = Homebrew directories under { $prefix } are not writable: { $dirs }
+doctor-lock-stale = Stale lock { $path } ({ $minutes } min old) blocks brew update
+doctor-lock-active = { $path } exists; a brew update may be running
+doctor-lock-active-fix = Wait for the running brew command to finish
+doctor-lock-ok = No stale brew update lock
+doctor-rustup-default-ok = Default toolchain: { $toolchain }
+doctor-rustup-default-missing = rustup has no default toolchain
+doctor-cache-ok = Cache directory { $path } is writable
+doctor-cache-error = Cannot create cache directory: { $error }
+doctor-cache-error-fix = Check permissions of ~/.cache (or $XDG_CACHE_HOME)
+
+## Single-instance lock
+
+lock-held = Another devtool run is in progress (pid { $pid }, started { $started }): { $command }
+lock-held-unknown = Another devtool run is in progress
+lock-waiting = Waiting for it to finish...
+lock-wait-hint = Use --wait to run once it finishes (lock file: { $path })
+
+## Update policy
+
+policy-allowed = Updates are allowed now
+policy-frozen = Updates are frozen from { $from } to { $to }{ $reason }
+policy-outside-window = Outside maintenance windows: { $windows }
+policy-next-allowed = Next allowed window: { $time }
+policy-no-window = No allowed window within the next year
+
+## status
+
+status-running = Update running (pid { $pid }, started { $started }, { $elapsed } elapsed)
+status-idle = No update is running
+policy-unrestricted = No maintenance windows or freezes configured; updates are always allowed
+policy-windows-heading = Maintenance windows:
+policy-freezes-heading = Freezes:
+
+## Run results
+
+notify-send-failed = Failed to send { $sink } notification: { $error }
+ci-summary-write-failed = Warning: failed to write job summary: { $error }
+compact-finished = update finished in { $duration }
+compact-deferred = { $tool } deferred (in quarantine): { $name } { $version } ({ $remaining } remaining)
+update-shadowed = { $command } was upgraded via { $via }, but { $path } ({ $owner }) wins in PATH
+
+## serve
+
+serve-listening = HTTP API listening on { $address }
+serve-token = Token: { $token }
+serve-stop-hint = Press Ctrl-C to stop
+
+## schedule
+
+schedule-installed = Scheduled update installed ({ $backend }): { $schedule }
+schedule-log = Log: { $path }
+schedule-enabled = enabled
+schedule-disabled = disabled
+schedule-status = Scheduled update ({ $backend }, { $state }): { $schedule }
+schedule-next-run = Next run: { $time }
+schedule-not-installed = No scheduled update installed
+schedule-run-succeeded = succeeded
+schedule-run-failed = failed
+schedule-last-run = Last scheduled run: { $time } ({ $result })
+schedule-removed = Scheduled update removed ({ $backend })
+
+## check / sync
+
+check-requires = requires
+check-installed = installed
+check-missing = missing
+check-mismatch = mismatch
+check-clean = All tools match the team manifest
+check-drift = { $count ->
+    [one] 1 item drifts from the team manifest; run devtool sync --manifest to fix
+   *[other] { $count } items drift from the team manifest; run devtool sync --manifest to fix
+}
+sync-brew-downgrade = Homebrew cannot downgrade { $name } to { $version }; install a versioned formula (e.g. { $name }@{ $hint }) or pin it manually
+sync-brew-downgrade-range = { $name } { $installed } is newer than { $req } allows and Homebrew cannot downgrade it; install a versioned formula or pin it manually
+sync-brew-pinned = { $name } is pinned to { $version } but { $installed } is installed; brew upgrade would go past the pinned version, so install { $version } manually (e.g. a versioned formula) or pin it
+sync-manual = Cannot pick a version of { $name } matching { $req } automatically; install a matching version manually
+
+## bootstrap
+
+bootstrap-nothing-to-do = Everything in the state file is already installed
+bootstrap-plan = { $count ->
+    [one] Bootstrap plan (1 step):
+   *[other] Bootstrap plan ({ $count } steps):
+}
+bootstrap-step-done = (done)
+bootstrap-resuming = { $count ->
+    [one] Resuming interrupted bootstrap, 1 step remaining
+   *[other] Resuming interrupted bootstrap, { $count } steps remaining
+}
+bootstrap-complete = Bootstrap complete
+bootstrap-failed = Some steps failed; fix them and re-run devtool bootstrap { $state } to resume
+doctor-summary = { $ok } ok, { $warnings ->
+    [one] 1 warning
+   *[other] { $warnings } warnings
+}, { $failures ->
+    [one] 1 failure
+   *[other] { $failures } failures
+}
+
+## Selective updates (--interactive / --plan)
+
+select-requires-terminal = --interactive requires a terminal
+select-checking = Checking for available upgrades...
+select-up-to-date = Everything is up to date
+select-nothing-selected = Nothing selected
+select-plan-saved = Plan saved to { $path }; run it with devtool update --plan { $path }
+select-plan = { $count ->
+    [one] Upgrade plan (1 item):
+   *[other] Upgrade plan ({ $count } items):
+}
+select-all-upgraded = All selected items upgraded
+
+## conflicts / which / upgrade
+
+conflicts-none = No command is provided by more than one tool
+conflicts-winner = wins: { $path } ({ $owner })
+conflicts-shadowed = shadowed: { $path } ({ $owner })
+conflicts-summary = { $count ->
+    [one] 1 conflicting command; the copy earlier in PATH wins
+   *[other] { $count } conflicting commands; the copy earlier in PATH wins
+}
+which-not-found = command not found in PATH: { $command }
+which-path = path
+which-resolved = resolved
+which-provider = provider
+which-toolchain = toolchain
+which-component = component
+which-version = version
+which-upgrade = upgrade
+which-unknown = unknown
+which-unmanaged = not managed by a tool devtool supports
+upgrade-unmanaged = { $path } ({ $owner }) is not managed by a tool devtool supports
+upgrade-done = { $command } upgraded (now { $version })
+
+## diff / snapshot
+
+diff-identical = Environments are identical
+diff-summary = { $missing } missing, { $extra } extra, { $changed } changed
+snapshot-tool-missing = { $tool } not detected; cannot export this format
+snapshot-saved = Snapshot saved to { $path }
+
+## update
+
+policy-forced = (--force: proceeding anyway)
+policy-dry-run = (dry run: ignoring policy)
+policy-force-hint = Use --force to update anyway
+
+tool-homebrew = Homebrew update & upgrade & cleanup
+tool-rustup = Rustup all toolchains update
+tool-mise = Mise tools update
+update-banner = Starting devtool update: { $time }
+update-no-tools = No executable steps detected. Skipped: { $skipped }
+steps-count = { $count ->
+    [one] Will execute 1 step:
+   *[other] Will execute { $count } steps:
+}
+compact-steps = devtool update: { $count ->
+    [one] 1 step
+   *[other] { $count } steps
+} ({ $tools })
+update-parallel-mode = Parallel mode (max concurrency: { $jobs })
+update-sequential-mode = Sequential mode
+update-complete = Update completed: { $time } (Time taken: { $duration })
+update-updated = Updated: { $tools }
+update-no-updates = No updates applied.
+update-actions = Actions executed: { $actions }
+update-unchanged = Already latest: { $tools }
+update-details-heading = { $tool } upgrades:
+update-detail-new = { $name }: new installation → { $version }
+update-detail-downgrade = { $name }: { $old } → { $new } (downgrade)
+update-toolchains-stable = Stable toolchains:
+update-toolchains-beta = Beta toolchains:
+update-toolchains-nightly = Nightly toolchains:
+update-toolchains-other = Other toolchains:
+update-deferred-heading = { $tool } deferred (in quarantine):
+update-deferred-item = { $name } { $version } ({ $remaining } remaining)
+update-unchecked = { $tool } skipped: new versions could not be listed, so the quarantine could not be checked
+update-failed = Failed: { $tools }
+
+## feedback
+
+feedback-title = devtool User Feedback Collection
+feedback-select-type = Please select feedback type:
+feedback-type-bug = Bug Report
+feedback-type-feature = Feature Request
+feedback-type-ux = User Experience Issue
+feedback-type-performance = Performance Issue
+feedback-type-documentation = Documentation Issue
+feedback-type-other = Other
+feedback-choice-prompt = Please enter your choice (1-6):
+feedback-describe = Please describe your feedback:
+feedback-empty = Feedback content cannot be empty!
+feedback-saved = Feedback saved to: { $path }
+feedback-summary = Feedback Summary:
+feedback-summary-type = Type: { $kind }
+feedback-summary-content = Content: { $content }
+feedback-system-info = System Information:
+feedback-channels = You can also submit feedback through:
+update-tool-error = Error executing { $tool }: { $error }
+
+## Logs
+
+log-save-failed = Warning: Failed to save the run logs: { $error }
+logs-empty = No saved run logs yet (failed runs are kept automatically; use `devtool update --keep-logs` to keep successful ones)
+logs-empty-tool = No saved logs for { $tool }
+logs-hint = Open a step with `devtool logs [tool] [--run N] --step <step>`, or search with `devtool logs grep <pattern>`
+logs-run-not-found = No run { $run } ({ $count ->
+    [one] 1 saved run
+   *[other] { $count } saved runs
+})
+logs-step-not-found = No step { $step } in run #{ $run } (available: { $steps })
+logs-grep-no-match = No matches for { $pattern }
+logs-follow-step = ==> { $tool } { $step } ({ $path }) <==
+logs-status-ok = ok
+logs-status-failed = failed
+logs-status-incomplete = incomplete
+logs-run-kept = Logs of this run: { $path } (`devtool logs --run 1`)
+logs-prune-failed = Warning: Failed to remove old run logs: { $error }
+logs-retention-invalid = Warning: Invalid [logs] settings, old run logs are not cleaned up: { $error }
+
+## Themes
+
+theme-invalid = warning: invalid [theme] settings, using the default theme: { $error }
+env-deprecated = warning: { $name } is deprecated and will be removed in a future release, use { $replacement } instead
+redact-invalid = warning: invalid [redact] settings, only the built-in redaction rules apply: { $error }
+
+## Language selection
+
+lang-unsupported = Warning: no message catalog for language "{ $lang }", using English (available: { $available })
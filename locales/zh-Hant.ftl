@@ -0,0 +1,327 @@
+# devtool 繁體中文訊息目錄
+#
+# 訊息 ID 與 en.ftl 一一對應；缺少的訊息回退到英文。
+
+## 時長
+
+duration-hms = { $hours }小時{ $minutes }分{ $seconds }秒
+duration-ms = { $minutes }分{ $seconds }秒
+duration-s = { $seconds }秒
+duration-days = { $count }天
+duration-hours = { $count }小時
+duration-minutes = { $count }分鐘
+duration-seconds = { $count }秒
+duration-separator = {""}
+
+## 進度條
+
+progress-preparing = { $tool } 準備中...
+progress-executing = { $tool } 執行中...
+progress-done = { $icon } { $tool } 完成
+progress-failed = { $icon } { $tool } 失敗
+progress-interrupted = { $icon } { $tool } 中斷
+
+## 緊湊輸出（--compact）
+
+compact-started = { $tool }: 開始
+compact-done = { $tool }: 完成 ({ $elapsed })
+compact-failed = { $tool }: 失敗 ({ $elapsed })
+
+## 無障礙輸出（--accessible）
+
+accessible-steps = 將更新 { $count } 個工具：{ $tools }。
+accessible-started = { $tool } 開始升級。
+accessible-finished = { $tool } 升級完成，{ $count ->
+    [0] 沒有需要升級的套件
+   *[other] 升級了 { $count } 個套件
+}。
+accessible-failed = { $tool } 升級失敗。
+accessible-interrupted = { $tool } 升級被中斷。
+accessible-complete = 更新完成，耗時 { $duration }。
+accessible-tool-result = { $tool }：{ $count ->
+    [0] 已是最新
+   *[other] 升級了 { $count } 個套件
+}。
+accessible-tool-failed = { $tool }：失敗。
+accessible-upgrade = { $tool } 升級了 { $detail }。
+accessible-version-arrow = {" 到 "}
+accessible-deferred = { $tool } 的 { $name } { $version } 仍在隔離期內，已暫緩升級，還需 { $remaining }。
+accessible-unchecked = { $tool } 無法列出新版本，無法確認是否已滿隔離期，已跳過升級。
+accessible-shadowed = { $command } 已透過 { $via } 升級，但優先執行的是 { $owner } 管理的 { $path }。
+
+## 結果表
+
+summary-header-tool = 工具
+summary-header-status = 狀態
+summary-header-upgrades = 升級
+summary-status-ok = 成功
+summary-status-failed = 失敗
+summary-upgrades-heading = { $tool } 升級詳情：
+
+## CI 整合
+
+ci-deferred = { $name } { $version } 在隔離期內暫緩升級（剩餘 { $remaining }）
+ci-summary-title = devtool 更新
+ci-summary-tool = 工具
+ci-summary-status = 狀態
+ci-summary-upgrades = 升級
+ci-summary-ok = ✅ 成功
+ci-summary-failed = ❌ 失敗
+ci-summary-details = { $tool } 升級詳情
+ci-summary-deferred = 暫緩的升級
+
+## 儀表板（--tui）
+
+dashboard-pending = 等待中
+dashboard-running = 執行中
+dashboard-done = 完成
+dashboard-failed = 失敗
+dashboard-queue = 佇列
+dashboard-queue-empty = 沒有等待中的步驟
+dashboard-blocked-by = 等待 { $step }
+dashboard-help = ←/→ 選擇 · Enter 展開 · c 取消步驟 · v 詳細 · Ctrl-C 全部取消
+
+## 互動式升級選擇
+
+select-title = 待升級項目
+select-header-tool = 工具
+select-header-name = 名稱
+select-header-version = 版本
+select-header-class = 級別
+select-filter = 過濾
+select-help-filter = 輸入過濾 · Enter 完成 · Esc 清除
+select-help = ↑/↓ 移動 · 空白鍵 勾選 · a 全選該工具 · / 過濾 · Enter 升級 · q 退出
+
+## 通知
+
+notify-title-success = devtool 更新完成（{ $host }）
+notify-title-failure = devtool 更新失敗（{ $host }）
+notify-tool-line = { $tool }：{ $status }
+notify-tool-failed = 失敗
+notify-tool-upgraded = { $count ->
+    [0] 已是最新
+   *[other] 升級 { $count } 項
+}
+notify-took = 耗時 { $duration }
+
+## 列表
+
+list-separator = 、
+
+## doctor
+
+doctor-git-ok = 已找到 git：{ $path }
+doctor-git-missing = 未安裝 git，Homebrew 和 mise 外掛依賴 git
+doctor-git-missing-fix = 安裝 git：xcode-select --install（macOS）或使用系統套件管理器
+doctor-tools-missing = PATH 中未找到 brew、rustup 或 mise
+doctor-tools-missing-fix = 至少安裝其中一個，或將其 bin 目錄加入 PATH
+doctor-tools-ok = 已找到 { $tools }
+doctor-rustc-ok = PATH 中只有一個 rustc
+doctor-rustc-multiple = PATH 中有多個 rustc：{ $list }
+doctor-rustc-fix-brew = brew uninstall rust  # 保留 rustup 管理的工具鏈
+doctor-rustc-fix = 刪除多餘的 rustc，或將 ~/.cargo/bin 放在 PATH 最前面
+doctor-mise-ok = mise 管理的工具均指向 mise
+doctor-mise-shadowed = 以下 mise 工具被遮蔽：{ $list }
+doctor-mise-fix = 在 shell 中啟用 mise（eval "$(mise activate zsh)"），使其優先於其他路徑
+doctor-mise-fix-brew = brew uninstall { $names }  # 或在 shell 設定中於 Homebrew 之後啟用 mise
+doctor-prefix-ok = Homebrew 前綴 { $prefix } 下的受管目錄可寫
+doctor-prefix-readonly = Homebrew 前綴 { $prefix } 下的目錄不可寫：{ $dirs }
+doctor-lock-stale = 殘留的鎖檔案 { $path }（{ $minutes } 分鐘前）會阻止 brew update
+doctor-lock-active = { $path } 存在，可能有 brew update 正在執行
+doctor-lock-active-fix = 等待正在執行的 brew 命令完成
+doctor-lock-ok = 沒有殘留的 brew update 鎖
+doctor-rustup-default-ok = 預設工具鏈：{ $toolchain }
+doctor-rustup-default-missing = rustup 未設定預設工具鏈
+doctor-cache-ok = 快取目錄 { $path } 可寫
+doctor-cache-error = 無法建立快取目錄：{ $error }
+doctor-cache-error-fix = 檢查 ~/.cache（或 $XDG_CACHE_HOME）的權限
+
+## 單實例鎖
+
+lock-held = 另一個 devtool 執行正在進行（PID { $pid }，開始於 { $started }）：{ $command }
+lock-held-unknown = 另一個 devtool 執行正在進行
+lock-waiting = 等待其結束...
+lock-wait-hint = 使用 --wait 可等待其結束後再執行（鎖檔案：{ $path }）
+
+## 更新策略
+
+policy-allowed = 目前允許更新
+policy-frozen = 目前處於更新凍結期：{ $from } 至 { $to }{ $reason }
+policy-outside-window = 目前不在維護時段內：{ $windows }
+policy-next-allowed = 下一個允許的時間：{ $time }
+policy-no-window = 一年內沒有允許更新的時間
+
+## status
+
+status-running = 正在更新（pid { $pid }，{ $started } 開始，已用 { $elapsed }）
+status-idle = 目前沒有正在執行的更新
+policy-unrestricted = 未設定維護時段或凍結期，隨時允許更新
+policy-windows-heading = 維護時段：
+policy-freezes-heading = 凍結期：
+
+## 執行結果
+
+notify-send-failed = 發送 { $sink } 通知失敗：{ $error }
+ci-summary-write-failed = 警告：寫入任務摘要失敗：{ $error }
+compact-finished = 更新完成，耗時 { $duration }
+compact-deferred = { $tool } 暫緩升級（隔離期內）：{ $name } { $version }（剩餘 { $remaining }）
+update-shadowed = { $command } 已透過 { $via } 升級，但 PATH 中生效的是 { $path }（{ $owner }）
+
+## serve
+
+serve-listening = HTTP API 監聽於 { $address }
+serve-token = 存取權杖：{ $token }
+serve-stop-hint = 按 Ctrl-C 停止
+
+## schedule
+
+schedule-installed = 已安裝定時更新（{ $backend }）：{ $schedule }
+schedule-log = 日誌：{ $path }
+schedule-enabled = 已啟用
+schedule-disabled = 未啟用
+schedule-status = 定時更新（{ $backend }，{ $state }）：{ $schedule }
+schedule-next-run = 下次執行：{ $time }
+schedule-not-installed = 未安裝定時更新
+schedule-run-succeeded = 成功
+schedule-run-failed = 失敗
+schedule-last-run = 上次定時執行：{ $time }（{ $result }）
+schedule-removed = 已移除定時更新（{ $backend }）
+
+## check / sync
+
+check-requires = 要求
+check-installed = 已安裝
+check-missing = 未安裝
+check-mismatch = 版本不符
+check-clean = 所有工具均符合團隊清單
+check-drift = { $count } 項與團隊清單不符，執行 devtool sync --manifest 進行同步
+sync-brew-downgrade = Homebrew 無法將 { $name } 降級到 { $version }；請安裝帶版本號的 formula（例如 { $name }@{ $hint }）或手動固定版本
+sync-brew-downgrade-range = { $name } { $installed } 高於 { $req } 允許的版本，Homebrew 無法降級；請安裝帶版本號的 formula 或手動固定版本
+sync-brew-pinned = { $name } 固定為 { $version }，目前安裝的是 { $installed }；brew upgrade 會越過固定版本升級到最新版，請手動安裝 { $version }（例如帶版本號的 formula）或固定版本
+sync-manual = 無法自動選擇符合 { $req } 的 { $name } 版本，請手動安裝符合要求的版本
+
+## bootstrap
+
+bootstrap-nothing-to-do = 本機已包含狀態檔案中的所有工具
+bootstrap-plan = 安裝計劃（{ $count } 步）：
+bootstrap-step-done = （已完成）
+bootstrap-resuming = 繼續上次中斷的初始化，剩餘 { $count } 步
+bootstrap-complete = 初始化完成
+bootstrap-failed = 部分步驟失敗，修復後重新執行 devtool bootstrap { $state } 將從中斷處繼續
+doctor-summary = 正常 { $ok } 項，警告 { $warnings } 項，失敗 { $failures } 項
+
+## 選擇性更新（--interactive / --plan）
+
+select-requires-terminal = --interactive 需要在終端中執行
+select-checking = 正在檢查可用的升級...
+select-up-to-date = 所有工具均已是最新版本
+select-nothing-selected = 未選擇任何項目
+select-plan-saved = 升級計劃已儲存到 { $path }，使用 devtool update --plan { $path } 執行
+select-plan = 升級計劃（{ $count } 項）：
+select-all-upgraded = 選定的項目均已升級
+
+## conflicts / which / upgrade
+
+conflicts-none = 沒有被多個工具同時提供的命令
+conflicts-winner = 生效：{ $path }（{ $owner }）
+conflicts-shadowed = 被遮蔽：{ $path }（{ $owner }）
+conflicts-summary = 共 { $count } 個命令存在衝突，PATH 中靠前的副本生效
+which-not-found = PATH 中找不到命令：{ $command }
+which-path = 路徑
+which-resolved = 實際檔案
+which-provider = 提供者
+which-toolchain = 工具鏈
+which-component = 元件
+which-version = 版本
+which-upgrade = 升級
+which-unknown = 未知
+which-unmanaged = 不受 devtool 支援的工具管理
+upgrade-unmanaged = { $path }（{ $owner }）不受 devtool 支援的工具管理，無法升級
+upgrade-done = { $command } 已升級（目前版本 { $version }）
+
+## diff / snapshot
+
+diff-identical = 兩個環境一致
+diff-summary = 缺失 { $missing } 項，多餘 { $extra } 項，版本不同 { $changed } 項
+snapshot-tool-missing = 未偵測到 { $tool }，無法匯出該格式
+snapshot-saved = 快照已儲存到 { $path }
+
+## update
+
+policy-forced = （--force：忽略限制繼續執行）
+policy-dry-run = （試執行：忽略限制）
+policy-force-hint = 使用 --force 可忽略限制強制更新
+
+tool-homebrew = Homebrew 更新、升級與清理
+tool-rustup = Rustup 更新所有工具鏈
+tool-mise = Mise 更新託管工具
+update-banner = 開始 devtool 更新：{ $time }
+update-no-tools = 未偵測到可執行步驟。跳過：{ $skipped }
+steps-count = 將執行 { $count } 個步驟：
+compact-steps = devtool 更新：{ $count } 個步驟（{ $tools }）
+update-parallel-mode = 並行執行模式（最大並行數：{ $jobs }）
+update-sequential-mode = 順序執行模式
+update-complete = 更新完成：{ $time }（耗時：{ $duration }）
+update-updated = 已更新：{ $tools }
+update-no-updates = 無更新應用。
+update-actions = 已執行動作：{ $actions }
+update-unchanged = 已是最新：{ $tools }
+update-details-heading = { $tool } 升級詳情：
+update-detail-new = { $name }: 新安裝 → { $version }
+update-detail-downgrade = { $name }: { $old } → { $new }（降級）
+update-toolchains-stable = Stable 工具鏈：
+update-toolchains-beta = Beta 工具鏈：
+update-toolchains-nightly = Nightly 工具鏈：
+update-toolchains-other = 其他工具鏈：
+update-deferred-heading = { $tool } 暫緩升級（隔離期內）：
+update-deferred-item = { $name } { $version }（剩餘 { $remaining }）
+update-unchecked = { $tool } 已跳過：無法列出新版本，無法確認是否已滿隔離期
+update-failed = 失敗：{ $tools }
+
+## feedback
+
+feedback-title = devtool 使用者意見回饋
+feedback-select-type = 請選擇意見回饋類型：
+feedback-type-bug = 問題報告
+feedback-type-feature = 功能請求
+feedback-type-ux = 使用者體驗問題
+feedback-type-performance = 效能問題
+feedback-type-documentation = 文件問題
+feedback-type-other = 其他
+feedback-choice-prompt = 請輸入選項（1-6）：
+feedback-describe = 請描述你的意見回饋：
+feedback-empty = 意見回饋內容不能為空！
+feedback-saved = 意見回饋已儲存到：{ $path }
+feedback-summary = 意見回饋摘要：
+feedback-summary-type = 類型：{ $kind }
+feedback-summary-content = 內容：{ $content }
+feedback-system-info = 系統資訊：
+feedback-channels = 也可以透過以下管道提交意見回饋：
+update-tool-error = 執行 { $tool } 時出錯：{ $error }
+
+## 日誌
+
+log-save-failed = 警告：儲存執行日誌失敗：{ $error }
+logs-empty = 還沒有儲存的執行日誌（失敗的執行會自動保留；使用 `devtool update --keep-logs` 保留成功的執行）
+logs-empty-tool = 沒有 { $tool } 的日誌
+logs-hint = 使用 `devtool logs [工具] [--run N] --step <步驟>` 開啟步驟日誌，或使用 `devtool logs grep <模式>` 搜尋
+logs-run-not-found = 沒有第 { $run } 次執行（共儲存了 { $count } 次）
+logs-step-not-found = 第 { $run } 次執行中沒有步驟 { $step }（可用：{ $steps }）
+logs-grep-no-match = 沒有符合 { $pattern } 的內容
+logs-follow-step = ==> { $tool } { $step }（{ $path }）<==
+logs-status-ok = 成功
+logs-status-failed = 失敗
+logs-status-incomplete = 未完成
+logs-run-kept = 本次執行的日誌：{ $path }（`devtool logs --run 1`）
+logs-prune-failed = 警告：清理舊的執行日誌失敗：{ $error }
+logs-retention-invalid = 警告：[logs] 設定有誤，不清理舊的執行日誌：{ $error }
+
+## 主題
+
+theme-invalid = 警告：[theme] 設定無效，使用預設主題：{ $error }
+env-deprecated = 警告：{ $name } 已棄用，將在日後的版本中移除，請改用 { $replacement }
+redact-invalid = 警告：[redact] 設定無效，只套用內建的脫敏規則：{ $error }
+
+## 語言選擇
+
+lang-unsupported = 警告：沒有語言 "{ $lang }" 的訊息目錄，使用英語（可用：{ $available }）
@@ -0,0 +1,327 @@
+# devtool 中文消息目录
+#
+# 消息 ID 与 en.ftl 一一对应；缺少的消息回退到英文。
+
+## 时长
+
+duration-hms = { $hours }小时{ $minutes }分{ $seconds }秒
+duration-ms = { $minutes }分{ $seconds }秒
+duration-s = { $seconds }秒
+duration-days = { $count }天
+duration-hours = { $count }小时
+duration-minutes = { $count }分钟
+duration-seconds = { $count }秒
+duration-separator = {""}
+
+## 进度条
+
+progress-preparing = { $tool } 准备中...
+progress-executing = { $tool } 执行中...
+progress-done = { $icon } { $tool } 完成
+progress-failed = { $icon } { $tool } 失败
+progress-interrupted = { $icon } { $tool } 中断
+
+## 紧凑输出（--compact）
+
+compact-started = { $tool }: 开始
+compact-done = { $tool }: 完成 ({ $elapsed })
+compact-failed = { $tool }: 失败 ({ $elapsed })
+
+## 无障碍输出（--accessible）
+
+accessible-steps = 将更新 { $count } 个工具：{ $tools }。
+accessible-started = { $tool } 开始升级。
+accessible-finished = { $tool } 升级完成，{ $count ->
+    [0] 没有需要升级的软件包
+   *[other] 升级了 { $count } 个软件包
+}。
+accessible-failed = { $tool } 升级失败。
+accessible-interrupted = { $tool } 升级被中断。
+accessible-complete = 更新完成，耗时 { $duration }。
+accessible-tool-result = { $tool }：{ $count ->
+    [0] 已是最新
+   *[other] 升级了 { $count } 个软件包
+}。
+accessible-tool-failed = { $tool }：失败。
+accessible-upgrade = { $tool } 升级了 { $detail }。
+accessible-version-arrow = {" 到 "}
+accessible-deferred = { $tool } 的 { $name } { $version } 仍在隔离期内，已暂缓升级，还需 { $remaining }。
+accessible-unchecked = { $tool } 无法列出新版本，无法确认是否已满隔离期，已跳过升级。
+accessible-shadowed = { $command } 已通过 { $via } 升级，但优先执行的是 { $owner } 管理的 { $path }。
+
+## 结果表
+
+summary-header-tool = 工具
+summary-header-status = 状态
+summary-header-upgrades = 升级
+summary-status-ok = 成功
+summary-status-failed = 失败
+summary-upgrades-heading = { $tool } 升级详情：
+
+## CI 集成
+
+ci-deferred = { $name } { $version } 在隔离期内暂缓升级（剩余 { $remaining }）
+ci-summary-title = devtool 更新
+ci-summary-tool = 工具
+ci-summary-status = 状态
+ci-summary-upgrades = 升级
+ci-summary-ok = ✅ 成功
+ci-summary-failed = ❌ 失败
+ci-summary-details = { $tool } 升级详情
+ci-summary-deferred = 暂缓的升级
+
+## 仪表盘（--tui）
+
+dashboard-pending = 等待中
+dashboard-running = 执行中
+dashboard-done = 完成
+dashboard-failed = 失败
+dashboard-queue = 队列
+dashboard-queue-empty = 没有等待中的步骤
+dashboard-blocked-by = 等待 { $step }
+dashboard-help = ←/→ 选择 · Enter 展开 · c 取消步骤 · v 详细 · Ctrl-C 全部取消
+
+## 交互式升级选择
+
+select-title = 待升级项目
+select-header-tool = 工具
+select-header-name = 名称
+select-header-version = 版本
+select-header-class = 级别
+select-filter = 过滤
+select-help-filter = 输入过滤 · Enter 完成 · Esc 清除
+select-help = ↑/↓ 移动 · 空格 勾选 · a 全选该工具 · / 过滤 · Enter 升级 · q 退出
+
+## 通知
+
+notify-title-success = devtool 更新完成（{ $host }）
+notify-title-failure = devtool 更新失败（{ $host }）
+notify-tool-line = { $tool }：{ $status }
+notify-tool-failed = 失败
+notify-tool-upgraded = { $count ->
+    [0] 已是最新
+   *[other] 升级 { $count } 项
+}
+notify-took = 耗时 { $duration }
+
+## 列表
+
+list-separator = 、
+
+## doctor
+
+doctor-git-ok = 已找到 git：{ $path }
+doctor-git-missing = 未安装 git，Homebrew 和 mise 插件依赖 git
+doctor-git-missing-fix = 安装 git：xcode-select --install（macOS）或使用系统包管理器
+doctor-tools-missing = PATH 中未找到 brew、rustup 或 mise
+doctor-tools-missing-fix = 至少安装其中一个，或将其 bin 目录加入 PATH
+doctor-tools-ok = 已找到 { $tools }
+doctor-rustc-ok = PATH 中只有一个 rustc
+doctor-rustc-multiple = PATH 中有多个 rustc：{ $list }
+doctor-rustc-fix-brew = brew uninstall rust  # 保留 rustup 管理的工具链
+doctor-rustc-fix = 删除多余的 rustc，或将 ~/.cargo/bin 放在 PATH 最前面
+doctor-mise-ok = mise 管理的工具均指向 mise
+doctor-mise-shadowed = 以下 mise 工具被遮蔽：{ $list }
+doctor-mise-fix = 在 shell 中激活 mise（eval "$(mise activate zsh)"），使其优先于其他路径
+doctor-mise-fix-brew = brew uninstall { $names }  # 或在 shell 配置中于 Homebrew 之后激活 mise
+doctor-prefix-ok = Homebrew 前缀 { $prefix } 下的受管目录可写
+doctor-prefix-readonly = Homebrew 前缀 { $prefix } 下的目录不可写：{ $dirs }
+doctor-lock-stale = 残留的锁文件 { $path }（{ $minutes } 分钟前）会阻止 brew update
+doctor-lock-active = { $path } 存在，可能有 brew update 正在运行
+doctor-lock-active-fix = 等待正在运行的 brew 命令完成
+doctor-lock-ok = 没有残留的 brew update 锁
+doctor-rustup-default-ok = 默认工具链：{ $toolchain }
+doctor-rustup-default-missing = rustup 未设置默认工具链
+doctor-cache-ok = 缓存目录 { $path } 可写
+doctor-cache-error = 无法创建缓存目录：{ $error }
+doctor-cache-error-fix = 检查 ~/.cache（或 $XDG_CACHE_HOME）的权限
+
+## 单实例锁
+
+lock-held = 另一个 devtool 运行正在进行（PID { $pid }，开始于 { $started }）：{ $command }
+lock-held-unknown = 另一个 devtool 运行正在进行
+lock-waiting = 等待其结束...
+lock-wait-hint = 使用 --wait 可等待其结束后再运行（锁文件：{ $path }）
+
+## 更新策略
+
+policy-allowed = 当前允许更新
+policy-frozen = 当前处于更新冻结期：{ $from } 至 { $to }{ $reason }
+policy-outside-window = 当前不在维护窗口内：{ $windows }
+policy-next-allowed = 下一个允许的时间：{ $time }
+policy-no-window = 一年内没有允许更新的时间
+
+## status
+
+status-running = 正在更新（pid { $pid }，{ $started } 开始，已用 { $elapsed }）
+status-idle = 当前没有正在运行的更新
+policy-unrestricted = 未配置维护窗口或冻结期，随时允许更新
+policy-windows-heading = 维护窗口：
+policy-freezes-heading = 冻结期：
+
+## 运行结果
+
+notify-send-failed = 发送 { $sink } 通知失败：{ $error }
+ci-summary-write-failed = 警告：写入任务摘要失败：{ $error }
+compact-finished = 更新完成，耗时 { $duration }
+compact-deferred = { $tool } 暂缓升级（隔离期内）：{ $name } { $version }（剩余 { $remaining }）
+update-shadowed = { $command } 已通过 { $via } 升级，但 PATH 中生效的是 { $path }（{ $owner }）
+
+## serve
+
+serve-listening = HTTP API 监听于 { $address }
+serve-token = 访问令牌：{ $token }
+serve-stop-hint = 按 Ctrl-C 停止
+
+## schedule
+
+schedule-installed = 已安装定时更新（{ $backend }）：{ $schedule }
+schedule-log = 日志：{ $path }
+schedule-enabled = 已启用
+schedule-disabled = 未启用
+schedule-status = 定时更新（{ $backend }，{ $state }）：{ $schedule }
+schedule-next-run = 下次运行：{ $time }
+schedule-not-installed = 未安装定时更新
+schedule-run-succeeded = 成功
+schedule-run-failed = 失败
+schedule-last-run = 上次定时运行：{ $time }（{ $result }）
+schedule-removed = 已移除定时更新（{ $backend }）
+
+## check / sync
+
+check-requires = 要求
+check-installed = 已安装
+check-missing = 未安装
+check-mismatch = 版本不符
+check-clean = 所有工具均符合团队清单
+check-drift = { $count } 项与团队清单不符，运行 devtool sync --manifest 进行同步
+sync-brew-downgrade = Homebrew 无法将 { $name } 降级到 { $version }；请安装带版本号的 formula（例如 { $name }@{ $hint }）或手动固定版本
+sync-brew-downgrade-range = { $name } { $installed } 高于 { $req } 允许的版本，Homebrew 无法降级；请安装带版本号的 formula 或手动固定版本
+sync-brew-pinned = { $name } 固定为 { $version }，当前安装的是 { $installed }；brew upgrade 会越过固定版本升级到最新版，请手动安装 { $version }（例如带版本号的 formula）或固定版本
+sync-manual = 无法自动选择符合 { $req } 的 { $name } 版本，请手动安装符合要求的版本
+
+## bootstrap
+
+bootstrap-nothing-to-do = 本机已包含状态文件中的所有工具
+bootstrap-plan = 安装计划（{ $count } 步）：
+bootstrap-step-done = （已完成）
+bootstrap-resuming = 继续上次中断的初始化，剩余 { $count } 步
+bootstrap-complete = 初始化完成
+bootstrap-failed = 部分步骤失败，修复后重新运行 devtool bootstrap { $state } 将从中断处继续
+doctor-summary = 正常 { $ok } 项，警告 { $warnings } 项，失败 { $failures } 项
+
+## 选择性更新（--interactive / --plan）
+
+select-requires-terminal = --interactive 需要在终端中运行
+select-checking = 正在检查可用的升级...
+select-up-to-date = 所有工具均已是最新版本
+select-nothing-selected = 未选择任何项目
+select-plan-saved = 升级计划已保存到 { $path }，使用 devtool update --plan { $path } 执行
+select-plan = 升级计划（{ $count } 项）：
+select-all-upgraded = 选定的项目均已升级
+
+## conflicts / which / upgrade
+
+conflicts-none = 没有被多个工具同时提供的命令
+conflicts-winner = 生效：{ $path }（{ $owner }）
+conflicts-shadowed = 被遮蔽：{ $path }（{ $owner }）
+conflicts-summary = 共 { $count } 个命令存在冲突，PATH 中靠前的副本生效
+which-not-found = PATH 中找不到命令：{ $command }
+which-path = 路径
+which-resolved = 实际文件
+which-provider = 提供者
+which-toolchain = 工具链
+which-component = 组件
+which-version = 版本
+which-upgrade = 升级
+which-unknown = 未知
+which-unmanaged = 不受 devtool 支持的工具管理
+upgrade-unmanaged = { $path }（{ $owner }）不受 devtool 支持的工具管理，无法升级
+upgrade-done = { $command } 已升级（当前版本 { $version }）
+
+## diff / snapshot
+
+diff-identical = 两个环境一致
+diff-summary = 缺失 { $missing } 项，多余 { $extra } 项，版本不同 { $changed } 项
+snapshot-tool-missing = 未检测到 { $tool }，无法导出该格式
+snapshot-saved = 快照已保存到 { $path }
+
+## update
+
+policy-forced = （--force：忽略限制继续执行）
+policy-dry-run = （试运行：忽略限制）
+policy-force-hint = 使用 --force 可忽略限制强制更新
+
+tool-homebrew = Homebrew 更新、升级与清理
+tool-rustup = Rustup 更新所有工具链
+tool-mise = Mise 更新托管工具
+update-banner = 开始 devtool 更新：{ $time }
+update-no-tools = 未检测到可执行步骤。跳过：{ $skipped }
+steps-count = 将执行 { $count } 个步骤：
+compact-steps = devtool 更新：{ $count } 个步骤（{ $tools }）
+update-parallel-mode = 并行执行模式（最大并发数：{ $jobs }）
+update-sequential-mode = 顺序执行模式
+update-complete = 更新完成：{ $time }（耗时：{ $duration }）
+update-updated = 已更新：{ $tools }
+update-no-updates = 无更新应用。
+update-actions = 已执行动作：{ $actions }
+update-unchanged = 已是最新：{ $tools }
+update-details-heading = { $tool } 升级详情：
+update-detail-new = { $name }: 新安装 → { $version }
+update-detail-downgrade = { $name }: { $old } → { $new }（降级）
+update-toolchains-stable = Stable 工具链：
+update-toolchains-beta = Beta 工具链：
+update-toolchains-nightly = Nightly 工具链：
+update-toolchains-other = 其他工具链：
+update-deferred-heading = { $tool } 暂缓升级（隔离期内）：
+update-deferred-item = { $name } { $version }（剩余 { $remaining }）
+update-unchecked = { $tool } 已跳过：无法列出新版本，无法确认是否已满隔离期
+update-failed = 失败：{ $tools }
+
+## feedback
+
+feedback-title = devtool 用户反馈
+feedback-select-type = 请选择反馈类型：
+feedback-type-bug = 问题报告
+feedback-type-feature = 功能请求
+feedback-type-ux = 用户体验问题
+feedback-type-performance = 性能问题
+feedback-type-documentation = 文档问题
+feedback-type-other = 其他
+feedback-choice-prompt = 请输入选项（1-6）：
+feedback-describe = 请描述你的反馈：
+feedback-empty = 反馈内容不能为空！
+feedback-saved = 反馈已保存到：{ $path }
+feedback-summary = 反馈摘要：
+feedback-summary-type = 类型：{ $kind }
+feedback-summary-content = 内容：{ $content }
+feedback-system-info = 系统信息：
+feedback-channels = 也可以通过以下渠道提交反馈：
+update-tool-error = 执行 { $tool } 时出错：{ $error }
+
+## 日志
+
+log-save-failed = 警告：保存运行日志失败：{ $error }
+logs-empty = 还没有保存的运行日志（失败的运行会自动保留；使用 `devtool update --keep-logs` 保留成功的运行）
+logs-empty-tool = 没有 { $tool } 的日志
+logs-hint = 使用 `devtool logs [工具] [--run N] --step <步骤>` 打开步骤日志，或使用 `devtool logs grep <模式>` 搜索
+logs-run-not-found = 没有第 { $run } 次运行（共保存了 { $count } 次）
+logs-step-not-found = 第 { $run } 次运行中没有步骤 { $step }（可用：{ $steps }）
+logs-grep-no-match = 没有匹配 { $pattern } 的内容
+logs-follow-step = ==> { $tool } { $step }（{ $path }）<==
+logs-status-ok = 成功
+logs-status-failed = 失败
+logs-status-incomplete = 未完成
+logs-run-kept = 本次运行的日志：{ $path }（`devtool logs --run 1`）
+logs-prune-failed = 警告：清理旧的运行日志失败：{ $error }
+logs-retention-invalid = 警告：[logs] 设置有误，不清理旧的运行日志：{ $error }
+
+## 主题
+
+theme-invalid = 警告：[theme] 设置无效，使用默认主题：{ $error }
+env-deprecated = 警告：{ $name } 已弃用，将在以后的版本中移除，请改用 { $replacement }
+redact-invalid = 警告：[redact] 设置无效，只使用内置的脱敏规则：{ $error }
+
+## 语言选择
+
+lang-unsupported = 警告：没有语言 "{ $lang }" 的消息目录，使用英语（可用：{ $available }）
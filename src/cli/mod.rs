@@ -1,4 +1,7 @@
 use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+use crate::parallel::Tool;
 
 /// 支持的 Shell 类型
 #[derive(Clone, Debug, PartialEq, ValueEnum)]
@@ -34,6 +37,43 @@ pub enum FeedbackType {
     Other,
 }
 
+/// 输出格式
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// 人类可读文本
+    #[default]
+    Text,
+    /// JSON（适用于脚本和 CI）
+    Json,
+}
+
+/// 快照导出格式
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum SnapshotFormat {
+    /// 完整的 JSON 快照
+    Json,
+    /// Homebrew 的 Brewfile
+    Brewfile,
+    /// 默认工具链的 rust-toolchain.toml
+    RustToolchain,
+    /// mise 的 config.toml
+    MiseConfig,
+}
+
+impl SnapshotFormat {
+    /// 根据输出文件名推断格式，无法识别时使用 JSON
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.file_name().and_then(|name| name.to_str()) {
+            Some("Brewfile") => SnapshotFormat::Brewfile,
+            Some("rust-toolchain.toml") | Some("rust-toolchain") => SnapshotFormat::RustToolchain,
+            Some("config.toml") | Some("mise.toml") | Some(".mise.toml") => {
+                SnapshotFormat::MiseConfig
+            }
+            _ => SnapshotFormat::Json,
+        }
+    }
+}
+
 /// devtool - 开发工具统一更新管理器
 #[derive(Parser, Debug)]
 #[command(name = "devtool")]
@@ -45,10 +85,85 @@ pub enum FeedbackType {
 )]
 #[command(version = env!("CARGO_PKG_VERSION"))]
 pub struct Args {
+    /// 界面语言（如 en、zh），优先于配置文件和系统环境
+    #[arg(long = "lang", value_name = "LANG", global = true)]
+    pub lang: Option<String>,
+
+    /// 不对日志文件、运行记录、通知和反馈报告中的令牌和凭据脱敏
+    #[arg(long = "no-redact", global = true)]
+    pub no_redact: bool,
+
+    /// 显示界面语言的协商过程后退出
+    #[arg(long = "print-locale")]
+    pub print_locale: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+/// 定时任务的实现方式
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ScheduleBackend {
+    /// systemd 用户服务和定时器
+    Systemd,
+    /// crontab 条目
+    Cron,
+}
+
+/// `schedule` 子命令
+#[derive(Subcommand, Debug)]
+pub enum ScheduleAction {
+    /// 安装并启用定时更新
+    Install {
+        /// 每天运行的时间（HH:MM，本地时间）
+        #[arg(long = "daily", value_name = "HH:MM")]
+        daily: String,
+
+        /// 只在指定的星期运行（如 Mon-Fri、weekends）
+        #[arg(long = "days", value_name = "DAYS")]
+        days: Option<String>,
+
+        /// 实现方式（默认优先 systemd，不可用时使用 cron）
+        #[arg(long = "backend", value_enum)]
+        backend: Option<ScheduleBackend>,
+
+        /// 只显示将要生成的内容，不安装
+        #[arg(short = 'n', long = "dry-run")]
+        dry_run: bool,
+    },
+    /// 显示已安装的定时更新和最近一次定时运行
+    Status,
+    /// 移除定时更新
+    Remove,
+}
+
+/// `logs` 子命令
+#[derive(Subcommand, Debug)]
+pub enum LogsAction {
+    /// 在保存的日志中搜索（正则表达式），可按工具、运行和步骤限定范围
+    Grep {
+        /// 搜索的正则表达式
+        #[arg(value_name = "PATTERN")]
+        pattern: String,
+
+        /// 只搜索该工具的日志
+        #[arg(value_enum)]
+        tool: Option<Tool>,
+
+        /// 只搜索第 N 次运行（1 为最近一次，也可以是运行目录的时间戳）
+        #[arg(long = "run", value_name = "N")]
+        run: Option<u64>,
+
+        /// 只搜索该步骤的日志（如 upgrade、homebrew/upgrade，或完整文件名）
+        #[arg(long = "step", value_name = "STEP")]
+        step: Option<String>,
+
+        /// 忽略大小写
+        #[arg(short = 'i', long = "ignore-case")]
+        ignore_case: bool,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// 更新开发工具（默认命令）
@@ -65,7 +180,7 @@ pub enum Commands {
         #[arg(long = "no-color")]
         no_color: bool,
 
-        /// 保留日志文件到 ~/.cache/devtool/
+        /// 成功的运行也保留日志到 ~/.cache/devtool/runs/（失败的运行总是保留）
         #[arg(long = "keep-logs")]
         keep_logs: bool,
 
@@ -77,8 +192,12 @@ pub enum Commands {
         #[arg(long = "sequential")]
         sequential: bool,
 
-        /// 并行任务数量限制
-        #[arg(long = "jobs", default_value_t = 3)]
+        /// 并行任务数量限制，至少为 1
+        #[arg(
+            long = "jobs",
+            default_value_t = 3,
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+        )]
         jobs: usize,
 
         /// 不显示启动横幅
@@ -88,6 +207,231 @@ pub enum Commands {
         /// 使用紧凑输出格式（适用于非交互环境）
         #[arg(long = "compact")]
         compact: bool,
+
+        /// 屏幕阅读器友好的输出：不显示进度条和图标，每次状态变化输出一个完整的句子
+        #[arg(long = "accessible")]
+        accessible: bool,
+
+        /// 新版本隔离期，首次发现未满该时长的版本暂缓升级（如 7d、36h，覆盖配置文件）
+        #[arg(long = "min-age", value_name = "DURATION")]
+        min_age: Option<String>,
+
+        /// 忽略维护窗口和冻结期限制，强制执行更新
+        #[arg(long = "force")]
+        force: bool,
+
+        /// 以计划任务模式运行：不在允许的时间内时静默跳过
+        #[arg(long = "scheduled")]
+        scheduled: bool,
+
+        /// 在终端界面中选择要升级的项目
+        #[arg(short = 'i', long = "interactive", conflicts_with = "scheduled")]
+        interactive: bool,
+
+        /// 将交互式选择保存为升级计划，不执行升级
+        #[arg(long = "save-plan", value_name = "FILE", requires = "interactive")]
+        save_plan: Option<PathBuf>,
+
+        /// 执行保存的升级计划
+        #[arg(long = "plan", value_name = "FILE", conflicts_with = "interactive")]
+        plan: Option<PathBuf>,
+
+        /// 使用全屏仪表盘显示每个工具的实时日志（非终端环境下回退为进度条）
+        #[arg(long = "tui")]
+        tui: bool,
+
+        /// 另一个 devtool 运行正在进行时等待其结束，而不是立即退出
+        #[arg(long = "wait")]
+        wait: bool,
+    },
+    /// 显示正在运行的更新和更新策略状态（维护窗口、冻结期和下一个允许的时间）
+    Status {
+        /// 持续显示正在运行的更新状态，每次变化输出一行
+        #[arg(short = 'w', long = "watch")]
+        watch: bool,
+
+        /// 输出格式
+        #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// 列出保存的运行日志，在 $PAGER 中打开步骤日志，或跟随正在运行的步骤
+    #[command(args_conflicts_with_subcommands = true)]
+    Logs {
+        /// 只显示该工具的运行和步骤
+        #[arg(value_enum)]
+        tool: Option<Tool>,
+
+        /// 选择第 N 次运行（1 为最近一次，也可以是运行目录的时间戳）
+        #[arg(long = "run", value_name = "N")]
+        run: Option<u64>,
+
+        /// 在 $PAGER 中打开该步骤的日志（如 upgrade、homebrew/upgrade，或完整文件名）
+        #[arg(long = "step", value_name = "STEP")]
+        step: Option<String>,
+
+        /// 持续输出正在运行的步骤日志，直到更新结束
+        #[arg(short = 'f', long = "follow", conflicts_with_all = ["run", "step"])]
+        follow: bool,
+
+        #[command(subcommand)]
+        action: Option<LogsAction>,
+    },
+    /// 启动本地 HTTP API，供仪表盘和机器人查询状态、触发更新
+    Serve {
+        /// 监听地址（只允许本机回环地址）
+        #[arg(long = "listen", value_name = "ADDR", default_value = "127.0.0.1:7878")]
+        listen: String,
+
+        /// 改为监听 Unix 套接字
+        #[arg(long = "socket", value_name = "PATH")]
+        socket: Option<PathBuf>,
+
+        /// 访问令牌（默认读取 DEVTOOL_SERVE_TOKEN，未设置时随机生成）
+        #[arg(long = "token", value_name = "TOKEN")]
+        token: Option<String>,
+    },
+    /// 管理定时更新（systemd 用户定时器，或 crontab）
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleAction,
+    },
+    /// 按团队清单安装、升级或降级工具
+    Sync {
+        /// 团队清单文件（默认在当前目录及上级目录查找 devtool.team.toml）
+        #[arg(
+            long = "manifest",
+            value_name = "PATH",
+            num_args = 0..=1,
+            default_value = "devtool.team.toml",
+            default_missing_value = "devtool.team.toml"
+        )]
+        manifest: PathBuf,
+
+        /// 只显示将要执行的操作
+        #[arg(short = 'n', long = "dry-run")]
+        dry_run: bool,
+
+        /// 详细输出模式
+        #[arg(short = 'v', long = "verbose")]
+        verbose: bool,
+
+        /// 另一个 devtool 运行正在进行时等待其结束，而不是立即退出
+        #[arg(long = "wait")]
+        wait: bool,
+    },
+    /// 检查已安装版本是否符合团队清单，存在偏差时以非零状态退出
+    Check {
+        /// 团队清单文件（默认在当前目录及上级目录查找 devtool.team.toml）
+        #[arg(
+            long = "manifest",
+            value_name = "PATH",
+            num_args = 0..=1,
+            default_value = "devtool.team.toml",
+            default_missing_value = "devtool.team.toml"
+        )]
+        manifest: PathBuf,
+
+        /// 输出格式
+        #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// 导出当前环境快照（已安装的工具、版本、tap、工具链组件和目标）
+    Snapshot {
+        /// 输出文件（默认输出到标准输出）
+        #[arg(short = 'o', long = "output", value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// 导出格式（默认根据输出文件名推断，否则为 JSON）
+        #[arg(long = "format", value_enum)]
+        format: Option<SnapshotFormat>,
+    },
+    /// 对比两个环境快照，或将本机与快照对比
+    Diff {
+        /// 基准快照
+        #[arg(
+            value_name = "BASE",
+            required_unless_present = "against",
+            requires = "other"
+        )]
+        base: Option<PathBuf>,
+
+        /// 对比的快照
+        #[arg(value_name = "OTHER")]
+        other: Option<PathBuf>,
+
+        /// 以该快照为基准，对比本机当前状态
+        #[arg(long = "against", value_name = "SNAPSHOT", conflicts_with = "base")]
+        against: Option<PathBuf>,
+
+        /// 输出格式
+        #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// 根据环境状态文件初始化新机器，安装缺失的工具（可中断后继续）
+    Bootstrap {
+        /// 环境状态文件（devtool snapshot 生成的 JSON）
+        #[arg(value_name = "STATE")]
+        state: PathBuf,
+
+        /// 只显示安装计划，不实际执行
+        #[arg(short = 'n', long = "dry-run")]
+        dry_run: bool,
+
+        /// 详细输出模式
+        #[arg(short = 'v', long = "verbose")]
+        verbose: bool,
+
+        /// 另一个 devtool 运行正在进行时等待其结束，而不是立即退出
+        #[arg(long = "wait")]
+        wait: bool,
+    },
+    /// 诊断开发环境中常见的更新失败原因
+    Doctor {
+        /// 输出格式
+        #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// 列出被多个工具同时提供的命令及 PATH 中实际生效的副本
+    Conflicts {
+        /// 输出格式
+        #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// 显示命令由哪个工具提供、当前版本及升级方式
+    Which {
+        /// 要查询的命令，例如 rg
+        #[arg(value_name = "COMMAND")]
+        command: String,
+
+        /// 输出格式
+        #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// 只升级提供指定命令的项目
+    Upgrade {
+        /// 要升级的命令，例如 rg
+        #[arg(value_name = "COMMAND")]
+        command: String,
+
+        /// 只显示升级命令，不实际执行
+        #[arg(short = 'n', long = "dry-run")]
+        dry_run: bool,
+
+        /// 详细输出模式
+        #[arg(short = 'v', long = "verbose")]
+        verbose: bool,
+
+        /// 另一个 devtool 运行正在进行时等待其结束，而不是立即退出
+        #[arg(long = "wait")]
+        wait: bool,
+
+        /// 新版本隔离期，首次发现未满该时长的版本暂缓升级（如 7d、36h，覆盖配置文件）
+        #[arg(long = "min-age", value_name = "DURATION")]
+        min_age: Option<String>,
+
+        /// 忽略维护窗口和冻结期限制，强制执行升级
+        #[arg(long = "force")]
+        force: bool,
     },
     /// 生成 shell 补全脚本
     Completion {
@@ -150,6 +494,384 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_args_update_min_age() {
+        let args = Args::parse_from(["devtool", "update", "--min-age", "7d"]);
+        match args.command {
+            Some(Commands::Update { min_age, .. }) => {
+                assert_eq!(min_age.as_deref(), Some("7d"));
+            }
+            _ => panic!("Expected Update command"),
+        }
+    }
+
+    #[test]
+    fn test_args_check_manifest() {
+        let args = Args::parse_from(["devtool", "check", "--manifest", "--format", "json"]);
+        match args.command {
+            Some(Commands::Check { manifest, format }) => {
+                assert_eq!(manifest, PathBuf::from("devtool.team.toml"));
+                assert_eq!(format, OutputFormat::Json);
+            }
+            _ => panic!("Expected Check command"),
+        }
+    }
+
+    #[test]
+    fn test_args_sync_manifest_path() {
+        let args = Args::parse_from(["devtool", "sync", "--manifest", "team.toml", "-n"]);
+        match args.command {
+            Some(Commands::Sync {
+                manifest, dry_run, ..
+            }) => {
+                assert_eq!(manifest, PathBuf::from("team.toml"));
+                assert!(dry_run);
+            }
+            _ => panic!("Expected Sync command"),
+        }
+    }
+
+    #[test]
+    fn test_args_snapshot() {
+        let args = Args::parse_from(["devtool", "snapshot", "-o", "env.json"]);
+        match args.command {
+            Some(Commands::Snapshot { output, format }) => {
+                assert_eq!(output, Some(PathBuf::from("env.json")));
+                assert!(format.is_none());
+            }
+            _ => panic!("Expected Snapshot command"),
+        }
+    }
+
+    #[test]
+    fn test_args_diff() {
+        let args = Args::parse_from(["devtool", "diff", "a.json", "b.json"]);
+        match args.command {
+            Some(Commands::Diff {
+                base,
+                other,
+                against,
+                ..
+            }) => {
+                assert_eq!(base, Some(PathBuf::from("a.json")));
+                assert_eq!(other, Some(PathBuf::from("b.json")));
+                assert!(against.is_none());
+            }
+            _ => panic!("Expected Diff command"),
+        }
+
+        let args = Args::parse_from(["devtool", "diff", "--against", "b.json"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Diff {
+                against: Some(_),
+                ..
+            })
+        ));
+
+        assert!(Args::try_parse_from(["devtool", "diff", "a.json"]).is_err());
+        assert!(Args::try_parse_from(["devtool", "diff"]).is_err());
+    }
+
+    #[test]
+    fn test_args_bootstrap() {
+        let args = Args::parse_from(["devtool", "bootstrap", "env.json", "--dry-run"]);
+        match args.command {
+            Some(Commands::Bootstrap { state, dry_run, .. }) => {
+                assert_eq!(state, PathBuf::from("env.json"));
+                assert!(dry_run);
+            }
+            _ => panic!("Expected Bootstrap command"),
+        }
+    }
+
+    #[test]
+    fn test_args_logs() {
+        let args = Args::parse_from([
+            "devtool", "logs", "homebrew", "--run", "2", "--step", "upgrade",
+        ]);
+        match args.command {
+            Some(Commands::Logs {
+                tool,
+                run,
+                step,
+                follow,
+                action,
+            }) => {
+                assert_eq!(tool, Some(Tool::Homebrew));
+                assert_eq!(run, Some(2));
+                assert_eq!(step.as_deref(), Some("upgrade"));
+                assert!(!follow);
+                assert!(action.is_none());
+            }
+            _ => panic!("Expected Logs command"),
+        }
+
+        let args = Args::parse_from(["devtool", "logs", "grep", "error", "mise", "-i"]);
+        match args.command {
+            Some(Commands::Logs {
+                action:
+                    Some(LogsAction::Grep {
+                        pattern,
+                        tool,
+                        ignore_case,
+                        ..
+                    }),
+                ..
+            }) => {
+                assert_eq!(pattern, "error");
+                assert_eq!(tool, Some(Tool::Mise));
+                assert!(ignore_case);
+            }
+            _ => panic!("Expected Logs grep command"),
+        }
+
+        assert!(Args::try_parse_from(["devtool", "logs", "--follow"]).is_ok());
+        assert!(Args::try_parse_from(["devtool", "logs", "--step", "upgrade"]).is_ok());
+        assert!(
+            Args::try_parse_from(["devtool", "logs", "mise", "--follow", "--run", "1"]).is_err()
+        );
+        assert!(Args::try_parse_from(["devtool", "logs", "brew"]).is_err());
+    }
+
+    #[test]
+    fn test_args_doctor() {
+        let args = Args::parse_from(["devtool", "doctor", "--format", "json"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Doctor {
+                format: OutputFormat::Json
+            })
+        ));
+    }
+
+    #[test]
+    fn test_args_which_and_upgrade() {
+        let args = Args::parse_from(["devtool", "which", "rg", "--format", "json"]);
+        match args.command {
+            Some(Commands::Which { command, format }) => {
+                assert_eq!(command, "rg");
+                assert_eq!(format, OutputFormat::Json);
+            }
+            _ => panic!("Expected Which command"),
+        }
+
+        let args = Args::parse_from(["devtool", "upgrade", "rg", "-n"]);
+        match args.command {
+            Some(Commands::Upgrade {
+                command, dry_run, ..
+            }) => {
+                assert_eq!(command, "rg");
+                assert!(dry_run);
+            }
+            _ => panic!("Expected Upgrade command"),
+        }
+    }
+
+    #[test]
+    fn test_args_update_interactive() {
+        let args = Args::parse_from(["devtool", "update", "-i", "--save-plan", "plan.json"]);
+        match args.command {
+            Some(Commands::Update {
+                interactive,
+                save_plan,
+                plan,
+                ..
+            }) => {
+                assert!(interactive);
+                assert_eq!(save_plan, Some(PathBuf::from("plan.json")));
+                assert!(plan.is_none());
+            }
+            _ => panic!("Expected Update command"),
+        }
+
+        // --save-plan 需要 --interactive，--plan 与 --interactive 互斥
+        assert!(Args::try_parse_from(["devtool", "update", "--save-plan", "p.json"]).is_err());
+        assert!(Args::try_parse_from(["devtool", "update", "-i", "--plan", "p.json"]).is_err());
+    }
+
+    #[test]
+    fn test_args_update_tui() {
+        let args = Args::parse_from(["devtool", "update", "--tui", "--jobs", "2"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Update {
+                tui: true,
+                jobs: 2,
+                ..
+            })
+        ));
+        assert!(Args::try_parse_from(["devtool", "update", "--jobs", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_args_wait() {
+        let args = Args::parse_from(["devtool", "update", "--wait"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Update { wait: true, .. })
+        ));
+        let args = Args::parse_from(["devtool", "upgrade", "rg", "--wait"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Upgrade { wait: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_args_lang() {
+        let args = Args::parse_from(["devtool", "doctor", "--lang", "zh"]);
+        assert_eq!(args.lang.as_deref(), Some("zh"));
+        let args = Args::parse_from(["devtool", "--lang", "en"]);
+        assert_eq!(args.lang.as_deref(), Some("en"));
+        assert!(!args.print_locale);
+        let args = Args::parse_from(["devtool", "--lang", "zh_TW", "--print-locale"]);
+        assert!(args.print_locale);
+        assert!(args.command.is_none());
+    }
+
+    #[test]
+    fn test_args_conflicts() {
+        let args = Args::parse_from(["devtool", "conflicts"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Conflicts {
+                format: OutputFormat::Text
+            })
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_format_from_path() {
+        use std::path::Path;
+        assert_eq!(
+            SnapshotFormat::from_path(Path::new("env.json")),
+            SnapshotFormat::Json
+        );
+        assert_eq!(
+            SnapshotFormat::from_path(Path::new("out/Brewfile")),
+            SnapshotFormat::Brewfile
+        );
+        assert_eq!(
+            SnapshotFormat::from_path(Path::new("rust-toolchain.toml")),
+            SnapshotFormat::RustToolchain
+        );
+        assert_eq!(
+            SnapshotFormat::from_path(Path::new(".mise.toml")),
+            SnapshotFormat::MiseConfig
+        );
+    }
+
+    #[test]
+    fn test_args_update_force() {
+        let args = Args::parse_from(["devtool", "update", "--force"]);
+        match args.command {
+            Some(Commands::Update {
+                force, scheduled, ..
+            }) => {
+                assert!(force);
+                assert!(!scheduled);
+            }
+            _ => panic!("Expected Update command"),
+        }
+    }
+
+    #[test]
+    fn test_args_update_accessible() {
+        let args = Args::parse_from(["devtool", "update", "--accessible"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Update {
+                accessible: true,
+                compact: false,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_args_status() {
+        let args = Args::parse_from(["devtool", "status"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Status {
+                watch: false,
+                format: OutputFormat::Text
+            })
+        ));
+
+        let args = Args::parse_from(["devtool", "status", "--watch", "--format", "json"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Status {
+                watch: true,
+                format: OutputFormat::Json
+            })
+        ));
+    }
+
+    #[test]
+    fn test_args_serve() {
+        let args = Args::parse_from(["devtool", "serve"]);
+        match args.command {
+            Some(Commands::Serve {
+                listen,
+                socket,
+                token,
+            }) => {
+                assert_eq!(listen, "127.0.0.1:7878");
+                assert!(socket.is_none());
+                assert!(token.is_none());
+            }
+            _ => panic!("Expected Serve command"),
+        }
+
+        let args = Args::parse_from(["devtool", "serve", "--socket", "/tmp/devtool-api.sock"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Serve {
+                socket: Some(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_args_schedule() {
+        let args = Args::parse_from([
+            "devtool", "schedule", "install", "--daily", "09:30", "--days", "Mon-Fri",
+        ]);
+        match args.command {
+            Some(Commands::Schedule {
+                action:
+                    ScheduleAction::Install {
+                        daily,
+                        days,
+                        backend,
+                        dry_run,
+                    },
+            }) => {
+                assert_eq!(daily, "09:30");
+                assert_eq!(days.as_deref(), Some("Mon-Fri"));
+                assert!(backend.is_none());
+                assert!(!dry_run);
+            }
+            _ => panic!("Expected Schedule install command"),
+        }
+
+        let args = Args::parse_from(["devtool", "schedule", "remove"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Schedule {
+                action: ScheduleAction::Remove
+            })
+        ));
+
+        // --daily 必填；计划任务不能交互
+        assert!(Args::try_parse_from(["devtool", "schedule", "install"]).is_err());
+        assert!(Args::try_parse_from(["devtool", "update", "--scheduled", "-i"]).is_err());
+    }
+
     #[test]
     fn test_args_completion() {
         let args = Args::parse_from(["devtool", "completion", "bash"]);
@@ -171,4 +893,39 @@ mod tests {
             _ => panic!("Expected Completion command with nushell"),
         }
     }
+
+    #[test]
+    fn test_checked_in_completions_are_current() {
+        use clap::CommandFactory;
+        use clap_complete::Shell;
+
+        let generate = |shell: &dyn Fn(&mut clap::Command, &mut Vec<u8>)| {
+            let mut out = Vec::new();
+            shell(&mut Args::command(), &mut out);
+            String::from_utf8(out).unwrap()
+        };
+        // 修改命令行参数后用 `devtool completion <shell>` 重新生成 completions/ 下的文件
+        for (generated, checked_in) in [
+            (
+                generate(&|cmd, out| clap_complete::generate(Shell::Bash, cmd, "devtool", out)),
+                include_str!("../../completions/devtool-completions.bash"),
+            ),
+            (
+                generate(&|cmd, out| clap_complete::generate(Shell::Zsh, cmd, "devtool", out)),
+                include_str!("../../completions/devtool-completions.zsh"),
+            ),
+            (
+                generate(&|cmd, out| clap_complete::generate(Shell::Fish, cmd, "devtool", out)),
+                include_str!("../../completions/devtool-completions.fish"),
+            ),
+            (
+                generate(&|cmd, out| {
+                    clap_complete::generate(clap_complete_nushell::Nushell, cmd, "devtool", out)
+                }),
+                include_str!("../../completions/devtool-completions.nu"),
+            ),
+        ] {
+            assert_eq!(generated, checked_in);
+        }
+    }
 }
@@ -7,8 +7,12 @@ use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use crate::commands::pending::PendingUpgrade;
 use crate::commands::upgrade_details::{UpgradeDetail, UpgradeDetails, UpgradeDetailsManager};
+use crate::parallel::Tool;
+use crate::quarantine::{save_deferred, Candidate, Quarantine};
 use crate::runner::Runner;
+use crate::utils::shell_quote;
 
 /// 创建调试日志文件的辅助函数
 ///
@@ -25,7 +29,10 @@ struct OutdatedPackage {
     name: String,
     installed_versions: Vec<String>,
     current_version: String,
+    /// cask 条目不包含该字段
+    #[serde(default)]
     pinned: bool,
+    #[serde(default)]
     pinned_version: Option<String>,
 }
 
@@ -138,7 +145,7 @@ fn get_outdated_packages_json(
         let _ = writeln!(debug_file, "执行命令: brew outdated --json");
     }
 
-    let (rc, out) = runner.run("brew outdated --json", logfile, false)?;
+    let (rc, out) = runner.capture("brew outdated --json", logfile, false)?;
 
     // 记录命令执行结果
     if let Ok(mut debug_file) = File::create(tmpdir.join("brew_detailed_debug.log")) {
@@ -272,7 +279,7 @@ fn get_outdated_packages_text(
     _tmpdir: &Path,
     logfile: &Path,
 ) -> Result<Vec<SimpleOutdatedPackage>> {
-    let (_, out) = runner.run("brew outdated", logfile, false)?;
+    let (_, out) = runner.capture("brew outdated", logfile, false)?;
 
     let mut packages = Vec::new();
     for line in out.lines() {
@@ -290,6 +297,120 @@ fn get_outdated_packages_text(
     Ok(packages)
 }
 
+/// 使用 `brew list --pinned` 获取已固定版本的 formula，失败时返回空列表
+fn get_pinned_formulae(runner: &dyn Runner, tmpdir: &Path) -> Vec<String> {
+    let logfile = tmpdir.join("brew_pinned.log");
+    match runner.capture("brew list --pinned", &logfile, false) {
+        Ok((0, output)) => parse_pinned(&output),
+        _ => Vec::new(),
+    }
+}
+
+/// 解析 `brew list --pinned` 输出，每行一个 formula 名称
+fn parse_pinned(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// 已安装的 Homebrew 软件包
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledPackage {
+    /// 软件包名称
+    pub name: String,
+    /// 已安装的最新版本
+    pub version: String,
+    /// 是否为 cask
+    pub cask: bool,
+}
+
+/// 解析 `brew outdated --json=v2` 输出，跳过已固定版本的软件包
+fn parse_outdated_json(output: &str) -> Vec<PendingUpgrade> {
+    let Ok(outdated) = serde_json::from_str::<OutdatedPackages>(output.trim()) else {
+        return Vec::new();
+    };
+    let formulae = outdated.formulae.into_iter().map(|p| (p, false));
+    let casks = outdated.casks.into_iter().map(|p| (p, true));
+    formulae
+        .chain(casks)
+        .filter(|(package, _)| !package.pinned)
+        .map(|(package, cask)| PendingUpgrade {
+            tool: Tool::Homebrew,
+            current: package
+                .installed_versions
+                .last()
+                .cloned()
+                .unwrap_or_else(|| "-".to_string()),
+            name: package.name,
+            cask,
+            latest: package.current_version,
+        })
+        .collect()
+}
+
+/// 列出可升级的 formula 和 cask
+pub fn pending_upgrades(runner: &dyn Runner, tmpdir: &Path) -> Result<Vec<PendingUpgrade>> {
+    let logfile = tmpdir.join("brew_outdated_v2.log");
+    let (_, output) = runner.capture(
+        "HOMEBREW_NO_AUTO_UPDATE=1 brew outdated --json=v2",
+        &logfile,
+        false,
+    )?;
+    Ok(parse_outdated_json(&output))
+}
+
+/// 解析 `brew list --versions` 输出
+///
+/// 每行格式为 "name version1 version2 ..."，取最后一个（最新安装的）版本
+fn parse_brew_list_versions(output: &str, cask: bool) -> Vec<InstalledPackage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?;
+            let version = parts.last()?;
+            Some(InstalledPackage {
+                name: name.to_string(),
+                version: version.to_string(),
+                cask,
+            })
+        })
+        .collect()
+}
+
+/// 获取已安装的 Homebrew formulae 和 casks
+pub fn installed_packages(runner: &dyn Runner, tmpdir: &Path) -> Result<Vec<InstalledPackage>> {
+    let (_, formulae) = runner.capture(
+        "brew list --formula --versions",
+        &tmpdir.join("brew_list_formula.log"),
+        false,
+    )?;
+    let (_, casks) = runner.capture(
+        "brew list --cask --versions",
+        &tmpdir.join("brew_list_cask.log"),
+        false,
+    )?;
+
+    let mut packages = parse_brew_list_versions(&formulae, false);
+    packages.extend(parse_brew_list_versions(&casks, true));
+    Ok(packages)
+}
+
+/// 获取已添加的 Homebrew tap
+pub fn installed_taps(runner: &dyn Runner, tmpdir: &Path) -> Result<Vec<String>> {
+    let logfile = tmpdir.join("brew_tap.log");
+    let (_, output) = runner.capture("brew tap", &logfile, false)?;
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect())
+}
+
 /// Homebrew 更新软件包索引
 ///
 /// 执行 `brew update` 更新 Homebrew 的软件包索引
@@ -362,6 +483,7 @@ pub fn brew_update(
 /// * `runner` - 命令执行器
 /// * `tmpdir` - 临时目录路径，用于存储日志文件和升级详情
 /// * `verbose` - 是否输出详细信息
+/// * `quarantine` - 新版本隔离期策略，未满隔离期的软件包不会被升级
 ///
 /// # 返回值
 /// 返回元组 (状态, 退出码, 日志文件路径)
@@ -372,6 +494,7 @@ pub fn brew_upgrade(
     runner: &dyn Runner,
     tmpdir: &Path,
     verbose: bool,
+    quarantine: &Quarantine,
 ) -> Result<(String, i32, PathBuf)> {
     let logfile = tmpdir.join("brew_upgrade.log");
 
@@ -394,6 +517,28 @@ pub fn brew_upgrade(
         }
     }
 
+    // 记录候选版本首次出现时间，并根据隔离期划分可升级的软件包；
+    // 已固定版本的 formula 不参与划分，避免显式传给 brew upgrade 时被解除固定
+    let pinned = get_pinned_formulae(runner, tmpdir);
+    let candidates = outdated_packages
+        .iter()
+        .filter(|pkg| !pinned.contains(&pkg.name))
+        .map(|pkg| Candidate {
+            name: pkg.name.clone(),
+            candidate_version: pkg.current_version.clone(),
+        })
+        .collect();
+    let (allowed, deferred) = quarantine.partition("homebrew", candidates);
+
+    if !deferred.is_empty() {
+        save_deferred(tmpdir, "brew", &deferred);
+
+        // 所有候选版本都在隔离期内，跳过本次升级
+        if allowed.is_empty() {
+            return Ok(("unchanged".to_string(), 0, logfile));
+        }
+    }
+
     // 即使没有过时软件包，也执行 brew upgrade 命令
     // 因为 brew upgrade 可能会执行其他操作（如依赖检查、缓存清理等）
     if outdated_packages.is_empty() {
@@ -402,10 +547,17 @@ pub fn brew_upgrade(
         }
     }
 
-    // 执行升级
+    // 有暂缓项时只升级已过隔离期的软件包
+    let targets = if deferred.is_empty() {
+        String::new()
+    } else {
+        let names: Vec<String> = allowed.iter().map(|c| shell_quote(&c.name)).collect();
+        format!(" {}", names.join(" "))
+    };
+
     // 执行升级 - 完全禁用 Homebrew 的进度条显示和额外输出
     let (rc_upgrade, _out_upgrade) = runner.run(
-        "HOMEBREW_NO_PROGRESS=1 HOMEBREW_NO_ANALYTICS=1 HOMEBREW_NO_INSECURE_REDIRECT=1 HOMEBREW_NO_EMOJI=1 HOMEBREW_NO_AUTO_UPDATE=1 HOMEBREW_NO_ENV_HINTS=1 brew upgrade --quiet 2>&1",
+        &format!("HOMEBREW_NO_PROGRESS=1 HOMEBREW_NO_ANALYTICS=1 HOMEBREW_NO_INSECURE_REDIRECT=1 HOMEBREW_NO_EMOJI=1 HOMEBREW_NO_AUTO_UPDATE=1 HOMEBREW_NO_ENV_HINTS=1 brew upgrade --quiet{} 2>&1", targets),
         &logfile,
         verbose,
     )?;
@@ -564,3 +716,51 @@ pub fn brew_cleanup(
 
     Ok((state.to_string(), rc_cleanup, logfile))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_brew_list_versions() {
+        let output = "git 2.45.0\nopenssl@3 3.3.0 3.3.1\n\n";
+        let packages = parse_brew_list_versions(output, false);
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "git");
+        assert_eq!(packages[1].name, "openssl@3");
+        assert_eq!(packages[1].version, "3.3.1");
+        assert!(!packages[1].cask);
+    }
+
+    #[test]
+    fn test_parse_pinned() {
+        assert_eq!(
+            parse_pinned("node\npostgresql@14\n\n"),
+            ["node", "postgresql@14"]
+        );
+        assert!(parse_pinned("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_outdated_json() {
+        let output = r#"{"formulae": [
+            {"name": "git", "installed_versions": ["2.44.0", "2.45.0"], "current_version": "2.46.0", "pinned": false, "pinned_version": null},
+            {"name": "node", "installed_versions": ["20.0.0"], "current_version": "21.0.0", "pinned": true, "pinned_version": "20.0.0"}
+        ], "casks": [
+            {"name": "firefox", "installed_versions": ["125.0"], "current_version": "126.0"}
+        ]}"#;
+        let pending = parse_outdated_json(output);
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].name, "git");
+        assert_eq!(pending[0].current, "2.45.0");
+        assert_eq!(pending[0].latest, "2.46.0");
+        assert!(pending[1].cask);
+        assert!(parse_outdated_json("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_brew_list_versions_skips_names_without_version() {
+        let packages = parse_brew_list_versions("orphan\n", true);
+        assert!(packages.is_empty());
+    }
+}
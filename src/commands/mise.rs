@@ -8,8 +8,12 @@ use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use crate::commands::pending::PendingUpgrade;
 use crate::commands::upgrade_details::{UpgradeDetail, UpgradeDetails, UpgradeDetailsManager};
+use crate::parallel::Tool;
+use crate::quarantine::{save_deferred, save_unchecked, Candidate, Quarantine};
 use crate::runner::Runner;
+use crate::utils::shell_quote;
 
 /// Mise 工具版本信息
 #[derive(Debug, Deserialize, Serialize)]
@@ -53,7 +57,7 @@ fn get_mise_versions_json(runner: &dyn Runner, tmpdir: &Path) -> Result<Vec<Mise
 
 /// 使用 mise ls --current 获取工具版本信息
 fn get_mise_versions_from_ls(runner: &dyn Runner, tmpdir: &Path) -> Result<Vec<MiseToolVersion>> {
-    let (_, versions_output) = runner.run(
+    let (_, versions_output) = runner.capture(
         "mise ls --current",
         &tmpdir.join("mise_versions.log"),
         false,
@@ -89,7 +93,7 @@ fn get_mise_versions_from_ls_simple(
     tmpdir: &Path,
 ) -> Result<Vec<MiseToolVersion>> {
     let (_, versions_output) =
-        runner.run("mise ls", &tmpdir.join("mise_versions_simple.log"), false)?;
+        runner.capture("mise ls", &tmpdir.join("mise_versions_simple.log"), false)?;
 
     // 验证输出
     if versions_output.trim().is_empty() {
@@ -115,6 +119,18 @@ fn get_mise_versions_from_ls_simple(
     Ok(tool_versions)
 }
 
+/// 获取 Mise 当前使用的工具及版本
+///
+/// 返回按工具名称排序的 (名称, 版本) 列表
+pub fn installed_tools(runner: &dyn Runner, tmpdir: &Path) -> Result<Vec<(String, String)>> {
+    let mut tools: Vec<(String, String)> = get_mise_versions_json(runner, tmpdir)?
+        .into_iter()
+        .map(|tool| (tool.name, tool.version))
+        .collect();
+    tools.sort();
+    Ok(tools)
+}
+
 /// 解析 Mise 版本信息
 ///
 /// 从 mise ls 的输出中解析工具名称和版本号
@@ -222,6 +238,67 @@ fn parse_mise_versions_simple(output: &str) -> HashMap<String, String> {
     versions
 }
 
+/// `mise outdated --json` 中的单个工具条目
+#[derive(Debug, Deserialize)]
+struct MiseOutdatedEntry {
+    #[serde(default)]
+    current: Option<String>,
+    latest: String,
+}
+
+/// 解析 `mise outdated --json` 输出
+///
+/// 输出格式为以工具名为键的对象，例如
+/// `{"node": {"requested": "20", "current": "20.10.0", "latest": "20.11.0"}}`
+fn parse_mise_outdated(output: &str) -> Vec<PendingUpgrade> {
+    let entries: HashMap<String, MiseOutdatedEntry> =
+        serde_json::from_str(output.trim()).unwrap_or_default();
+
+    let mut candidates: Vec<PendingUpgrade> = entries
+        .into_iter()
+        .map(|(name, entry)| PendingUpgrade {
+            tool: Tool::Mise,
+            name,
+            cask: false,
+            current: entry.current.unwrap_or_else(|| "-".to_string()),
+            latest: entry.latest,
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.name.cmp(&b.name));
+    candidates
+}
+
+/// 列出可升级的工具
+pub fn pending_upgrades(runner: &dyn Runner, tmpdir: &Path) -> Result<Vec<PendingUpgrade>> {
+    let logfile = tmpdir.join("mise_outdated.log");
+    let (rc, output) = runner.capture("mise outdated --json", &logfile, false)?;
+    if rc != 0 {
+        anyhow::bail!("mise outdated --json exited with code {}", rc);
+    }
+    Ok(parse_mise_outdated(&output))
+}
+
+/// 使用 mise outdated 获取可升级的工具，无法列出时返回 `None`
+fn get_outdated_tools(runner: &dyn Runner, tmpdir: &Path) -> Option<Vec<Candidate>> {
+    match pending_upgrades(runner, tmpdir) {
+        Ok(pending) => Some(
+            pending
+                .into_iter()
+                .map(|p| Candidate {
+                    name: p.name,
+                    candidate_version: p.latest,
+                })
+                .collect(),
+        ),
+        Err(e) => {
+            if let Ok(mut file) = File::create(tmpdir.join("mise_errors.log")) {
+                let _ = writeln!(file, "mise outdated failed: {:#}", e);
+            }
+            None
+        }
+    }
+}
+
 /// Mise 更新托管工具
 ///
 /// 执行 `mise up` 更新 Mise 管理的所有工具
@@ -233,6 +310,7 @@ fn parse_mise_versions_simple(output: &str) -> HashMap<String, String> {
 /// * `runner` - 命令执行器
 /// * `tmpdir` - 临时目录，用于存储日志和版本信息
 /// * `verbose` - 是否输出详细信息
+/// * `quarantine` - 新版本隔离期策略，未满隔离期的工具不会被升级
 ///
 /// # 返回值
 /// 返回元组 (状态, 退出码, 日志文件路径)
@@ -243,14 +321,41 @@ pub fn mise_up(
     runner: &dyn Runner,
     tmpdir: &Path,
     verbose: bool,
+    quarantine: &Quarantine,
 ) -> Result<(String, i32, PathBuf)> {
     let logfile = tmpdir.join("mise_up.log");
 
+    // 通过 mise outdated 获取候选版本，记录首次出现时间（与 Homebrew 一致，未启用隔离期时也记录），
+    // 并根据隔离期划分可升级的工具
+    let mut up_cmd = "mise up".to_string();
+    let candidates = match get_outdated_tools(runner, tmpdir) {
+        Some(candidates) => candidates,
+        // 启用隔离期时无法确认哪些版本已满隔离期，跳过本次升级
+        None if quarantine.is_enabled() => {
+            save_unchecked(tmpdir, "mise");
+            return Ok(("unchanged".to_string(), 0, logfile));
+        }
+        None => Vec::new(),
+    };
+    let (allowed, deferred) = quarantine.partition("mise", candidates);
+
+    if !deferred.is_empty() {
+        save_deferred(tmpdir, "mise", &deferred);
+
+        // 所有候选版本都在隔离期内，跳过本次升级
+        if allowed.is_empty() {
+            return Ok(("unchanged".to_string(), 0, logfile));
+        }
+
+        let tools: Vec<String> = allowed.iter().map(|c| shell_quote(&c.name)).collect();
+        up_cmd = format!("mise up {}", tools.join(" "));
+    }
+
     // 获取升级前的工具版本信息
     let versions_before = get_mise_versions_json(runner, tmpdir)?;
 
     // 执行更新
-    let (rc, out) = runner.run("mise up", &logfile, verbose)?;
+    let (rc, out) = runner.run(&up_cmd, &logfile, verbose)?;
 
     // 检查输出中是否包含更新标记
     let outl = out.to_lowercase();
@@ -318,6 +423,41 @@ pub fn mise_up(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::quarantine::is_unchecked;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    /// 所有命令都以 `rc` 退出的执行器，记录执行过的命令
+    struct FailingRunner {
+        commands: Mutex<Vec<String>>,
+        rc: i32,
+    }
+
+    impl Runner for FailingRunner {
+        fn run(&self, cmd: &str, logfile: &Path, _verbose: bool) -> Result<(i32, String)> {
+            self.commands.lock().unwrap().push(cmd.to_string());
+            std::fs::write(logfile, "mise ERROR failed to fetch versions")?;
+            Ok((self.rc, String::new()))
+        }
+    }
+
+    #[test]
+    fn test_mise_up_skipped_when_outdated_fails_under_quarantine() {
+        let tmp = tempdir().unwrap();
+        let runner = FailingRunner {
+            commands: Mutex::new(Vec::new()),
+            rc: 1,
+        };
+        let quarantine = Quarantine::with_store_dir(
+            Some(chrono::Duration::days(7)),
+            tmp.path().join("quarantine"),
+        );
+        let (state, rc, _) = mise_up(&runner, tmp.path(), false, &quarantine).unwrap();
+        assert_eq!(state, "unchanged");
+        assert_eq!(rc, 0);
+        assert_eq!(*runner.commands.lock().unwrap(), ["mise outdated --json"]);
+        assert!(is_unchecked(tmp.path(), "mise"));
+    }
 
     #[test]
     fn test_parse_mise_versions_tool_at_version() {
@@ -358,6 +498,18 @@ mod tests {
         assert_eq!(versions.get("python"), Some(&"3.11.5".to_string()));
     }
 
+    #[test]
+    fn test_parse_mise_outdated() {
+        let output = r#"{"node": {"requested": "20", "current": "20.10.0", "latest": "20.11.0"},
+            "python": {"requested": "3.11", "current": "3.11.5", "latest": "3.11.6"}}"#;
+        let candidates = parse_mise_outdated(output);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].name, "node");
+        assert_eq!(candidates[0].current, "20.10.0");
+        assert_eq!(candidates[0].latest, "20.11.0");
+        assert!(parse_mise_outdated("not json").is_empty());
+    }
+
     #[test]
     fn test_parse_mise_versions_invalid_lines() {
         let output = "\n   \n{}\n\"\"\nnodejs 20.11.0";
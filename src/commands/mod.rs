@@ -3,6 +3,7 @@
 
 pub mod homebrew;
 pub mod mise;
+pub mod pending;
 pub mod rustup;
 pub mod upgrade_details;
 
@@ -10,4 +11,5 @@ pub mod upgrade_details;
 pub use homebrew::{brew_cleanup, brew_update, brew_upgrade};
 pub use mise::mise_up;
 pub use rustup::rustup_update;
+// 版本探测函数供 manifest 等模块通过 commands::<tool>:: 路径使用
 // upgrade_details 模块的公共 API 由各个工具模块直接导入使用
@@ -0,0 +1,88 @@
+// 待升级项目
+// 各工具列出可升级的单个软件包、工具链或工具，并生成只升级该项目的命令
+
+use serde::{Deserialize, Serialize};
+
+use crate::parallel::Tool;
+use crate::utils::shell_quote;
+use crate::version::{Version, VersionDistance};
+
+/// 单个可升级项目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingUpgrade {
+    pub tool: Tool,
+    /// 软件包、工具链或工具名称
+    pub name: String,
+    /// 是否为 Homebrew cask
+    #[serde(default)]
+    pub cask: bool,
+    /// 当前版本
+    pub current: String,
+    /// 可升级到的版本
+    pub latest: String,
+}
+
+impl PendingUpgrade {
+    /// 语义化版本差距
+    pub fn distance(&self) -> Option<VersionDistance> {
+        Version::parse(&self.current).distance(&Version::parse(&self.latest))
+    }
+
+    /// 只升级该项目的命令
+    pub fn command(&self) -> String {
+        let name = shell_quote(&self.name);
+        match self.tool {
+            Tool::Homebrew if self.cask => format!(
+                "HOMEBREW_NO_AUTO_UPDATE=1 HOMEBREW_NO_ENV_HINTS=1 brew upgrade --quiet --cask {}",
+                name
+            ),
+            Tool::Homebrew => format!(
+                "HOMEBREW_NO_AUTO_UPDATE=1 HOMEBREW_NO_ENV_HINTS=1 brew upgrade --quiet {}",
+                name
+            ),
+            Tool::Rustup => format!("rustup update {}", name),
+            Tool::Mise => format!("mise upgrade {}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(tool: Tool, name: &str, cask: bool) -> PendingUpgrade {
+        PendingUpgrade {
+            tool,
+            name: name.to_string(),
+            cask,
+            current: "1.2.3".to_string(),
+            latest: "1.3.0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_command_targets_single_item() {
+        assert!(pending(Tool::Homebrew, "git", false)
+            .command()
+            .ends_with("brew upgrade --quiet 'git'"));
+        assert!(pending(Tool::Homebrew, "firefox", true)
+            .command()
+            .ends_with("brew upgrade --quiet --cask 'firefox'"));
+        assert_eq!(
+            pending(Tool::Rustup, "stable-aarch64-apple-darwin", false).command(),
+            "rustup update 'stable-aarch64-apple-darwin'"
+        );
+        assert_eq!(
+            pending(Tool::Mise, "node", false).command(),
+            "mise upgrade 'node'"
+        );
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(
+            pending(Tool::Mise, "node", false).distance(),
+            Some(VersionDistance::Minor)
+        );
+    }
+}
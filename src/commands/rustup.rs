@@ -7,8 +7,12 @@ use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use crate::commands::pending::PendingUpgrade;
 use crate::commands::upgrade_details::{UpgradeDetail, UpgradeDetails, UpgradeDetailsManager};
+use crate::parallel::Tool;
+use crate::quarantine::{save_deferred, save_unchecked, Candidate, Quarantine};
 use crate::runner::Runner;
+use crate::utils::shell_quote;
 
 /// Rustup 工具链版本信息
 #[derive(Debug, Deserialize, Serialize)]
@@ -62,7 +66,7 @@ fn get_toolchain_versions_from_show(
 
     // 获取所有已安装的工具链
     let (_, toolchains_output) =
-        runner.run("rustup show", &tmpdir.join("rustup_show.log"), false)?;
+        runner.capture("rustup show", &tmpdir.join("rustup_show.log"), false)?;
 
     // 验证输出
     if toolchains_output.trim().is_empty() {
@@ -79,7 +83,7 @@ fn get_toolchain_versions_from_show(
                 if !toolchain.is_empty() && toolchain.len() > 3 {
                     // 获取该工具链的 rustc 版本
                     let cmd = format!("rustup run {} rustc --version", toolchain);
-                    match runner.run(&cmd, &tmpdir.join("toolchain_version.log"), false) {
+                    match runner.capture(&cmd, &tmpdir.join("toolchain_version.log"), false) {
                         Ok((_, version_output)) => {
                             if let Some(version) = extract_rust_version(&version_output) {
                                 // 验证版本号
@@ -124,7 +128,7 @@ fn get_toolchain_versions_from_list(
     let mut versions = Vec::new();
 
     // 获取工具链列表
-    let (_, toolchains_output) = runner.run(
+    let (_, toolchains_output) = runner.capture(
         "rustup toolchain list",
         &tmpdir.join("rustup_toolchain_list.log"),
         false,
@@ -144,7 +148,7 @@ fn get_toolchain_versions_from_list(
             if !toolchain.is_empty() {
                 // 获取该工具链的 rustc 版本
                 let cmd = format!("rustup run {} rustc --version", toolchain);
-                match runner.run(&cmd, &tmpdir.join("toolchain_version.log"), false) {
+                match runner.capture(&cmd, &tmpdir.join("toolchain_version.log"), false) {
                     Ok((_, version_output)) => {
                         if let Some(version) = extract_rust_version(&version_output) {
                             // 验证版本号
@@ -189,6 +193,233 @@ fn extract_rust_version(version_output: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+/// 解析 `rustup check` 输出中的可用更新
+///
+/// 例如: "stable-x86_64-apple-darwin - Update available : 1.70.0 (90c541806 2023-05-31) -> 1.71.0 (8ede3aae2 2023-07-12)"
+/// 解析为工具链名称 "stable-x86_64-apple-darwin"、当前版本 "1.70.0" 和候选版本 "1.71.0"。
+/// rustup 自身的更新不计入工具链候选项。
+fn parse_rustup_check(output: &str) -> Vec<PendingUpgrade> {
+    let mut candidates = Vec::new();
+
+    for line in output.lines() {
+        let Some((toolchain, status)) = line.trim().split_once(" - ") else {
+            continue;
+        };
+        if toolchain == "rustup" || !status.starts_with("Update available") {
+            continue;
+        }
+        let Some((_, versions)) = status.split_once(':') else {
+            continue;
+        };
+        if let Some((old, new)) = versions.split_once("->") {
+            if let Some(version) = new.split_whitespace().next() {
+                candidates.push(PendingUpgrade {
+                    tool: Tool::Rustup,
+                    name: toolchain.to_string(),
+                    cask: false,
+                    current: old
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string(),
+                    latest: version.to_string(),
+                });
+            }
+        }
+    }
+
+    candidates
+}
+
+/// 列出可更新的工具链
+pub fn pending_upgrades(runner: &dyn Runner, tmpdir: &Path) -> Result<Vec<PendingUpgrade>> {
+    let logfile = tmpdir.join("rustup_check.log");
+    let (rc, output) = runner.capture("rustup check", &logfile, false)?;
+    let pending = parse_rustup_check(&output);
+    // 较新的 rustup 在有可用更新时也以非零退出码结束，只有没有解析出更新时才视为失败
+    if rc != 0 && pending.is_empty() {
+        anyhow::bail!("rustup check exited with code {}", rc);
+    }
+    Ok(pending)
+}
+
+/// 使用 rustup check 获取可用的工具链更新，无法列出时返回 `None`
+fn get_available_updates(runner: &dyn Runner, tmpdir: &Path) -> Option<Vec<Candidate>> {
+    match pending_upgrades(runner, tmpdir) {
+        Ok(pending) => Some(
+            pending
+                .into_iter()
+                .map(|p| Candidate {
+                    name: p.name,
+                    candidate_version: p.latest,
+                })
+                .collect(),
+        ),
+        Err(e) => {
+            if let Ok(mut file) = File::create(tmpdir.join("rustup_errors.log")) {
+                let _ = writeln!(file, "rustup check failed: {:#}", e);
+            }
+            None
+        }
+    }
+}
+
+/// 解析 `rustup default` 输出中的工具链名称
+///
+/// 例如: "stable-x86_64-apple-darwin (default)" -> "stable-x86_64-apple-darwin"
+fn parse_default_toolchain(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("info:"))
+        .and_then(|line| line.split_whitespace().next())
+        .filter(|name| *name != "no")
+        .map(|name| name.to_string())
+}
+
+/// 获取默认工具链及其 rustc 版本
+///
+/// 未设置默认工具链时返回 `None`
+pub fn default_toolchain_version(
+    runner: &dyn Runner,
+    tmpdir: &Path,
+) -> Result<Option<(String, String)>> {
+    let (_, output) =
+        runner.capture("rustup default", &tmpdir.join("rustup_default.log"), false)?;
+    let Some(toolchain) = parse_default_toolchain(&output) else {
+        return Ok(None);
+    };
+
+    let (_, version_output) = runner.capture(
+        &format!("rustup run {} rustc --version", toolchain),
+        &tmpdir.join("toolchain_version.log"),
+        false,
+    )?;
+    Ok(extract_rust_version(&version_output).map(|version| (toolchain, version)))
+}
+
+/// 已安装的 Rust 工具链详情
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledToolchain {
+    /// 工具链名称，不含主机三元组（例如 "stable"、"1.80.0"）
+    pub name: String,
+    /// rustc 版本
+    pub version: Option<String>,
+    /// 已安装的组件，不含三元组后缀（例如 "clippy"、"rust-src"）
+    pub components: Vec<String>,
+    /// 主机以外的已安装编译目标
+    pub targets: Vec<String>,
+}
+
+/// Rustup 安装状态
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RustupInventory {
+    /// 主机三元组
+    pub host: Option<String>,
+    /// 默认工具链名称，不含主机三元组
+    pub default_toolchain: Option<String>,
+    /// 已安装的工具链
+    pub toolchains: Vec<InstalledToolchain>,
+}
+
+/// 解析 `rustup show` 输出中的主机三元组
+fn parse_default_host(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Default host:"))
+        .map(|host| host.trim().to_string())
+}
+
+/// 解析 `rustup toolchain list` 输出中的工具链名称
+fn parse_toolchain_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("no "))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// 去除工具链名称中的主机三元组，例如 "stable-x86_64-apple-darwin" -> "stable"
+fn strip_host(name: &str, host: Option<&str>) -> String {
+    host.and_then(|host| name.strip_suffix(&format!("-{}", host)))
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// 解析 `rustup component list --installed` 输出
+///
+/// rust-std 由编译目标表示，其余组件去掉三元组后缀
+fn parse_components(output: &str, targets: &[String]) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("rust-std"))
+        .map(|line| {
+            targets
+                .iter()
+                .find_map(|target| line.strip_suffix(&format!("-{}", target)))
+                .unwrap_or(line)
+                .to_string()
+        })
+        .collect()
+}
+
+/// 获取 Rustup 安装状态：主机、默认工具链以及各工具链的版本、组件和目标
+pub fn inventory(runner: &dyn Runner, tmpdir: &Path) -> Result<RustupInventory> {
+    let read_output = |cmd: &str, name: &str| -> Result<String> {
+        Ok(runner.capture(cmd, &tmpdir.join(name), false)?.1)
+    };
+
+    let host = parse_default_host(&read_output("rustup show", "rustup_show.log")?);
+    let default_toolchain =
+        parse_default_toolchain(&read_output("rustup default", "rustup_default.log")?);
+
+    let mut toolchains = Vec::new();
+    for name in parse_toolchain_list(&read_output(
+        "rustup toolchain list",
+        "rustup_toolchain_list.log",
+    )?) {
+        let (_, version_output) = runner.capture(
+            &format!("rustup run {} rustc --version", name),
+            &tmpdir.join("toolchain_version.log"),
+            false,
+        )?;
+        let installed_targets: Vec<String> = read_output(
+            &format!("rustup target list --installed --toolchain {}", name),
+            "rustup_target_list.log",
+        )?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+        let components = parse_components(
+            &read_output(
+                &format!("rustup component list --installed --toolchain {}", name),
+                "rustup_component_list.log",
+            )?,
+            &installed_targets,
+        );
+
+        toolchains.push(InstalledToolchain {
+            name: strip_host(&name, host.as_deref()),
+            version: extract_rust_version(&version_output),
+            components,
+            targets: installed_targets
+                .into_iter()
+                .filter(|target| Some(target) != host.as_ref())
+                .collect(),
+        });
+    }
+
+    Ok(RustupInventory {
+        default_toolchain: default_toolchain.map(|name| strip_host(&name, host.as_deref())),
+        host,
+        toolchains,
+    })
+}
+
 /// 检测版本变化
 ///
 /// 比较升级前后的工具链版本信息，检测是否有版本变化或新安装的工具链
@@ -300,6 +531,7 @@ fn determine_upgrade_status(
 /// * `runner` - 命令执行器
 /// * `tmpdir` - 临时目录，用于存储日志和版本信息
 /// * `verbose` - 是否输出详细信息
+/// * `quarantine` - 新版本隔离期策略，未满隔离期的工具链不会被更新
 ///
 /// # 返回值
 /// 返回元组 (状态, 退出码, 日志文件路径)
@@ -310,14 +542,41 @@ pub fn rustup_update(
     runner: &dyn Runner,
     tmpdir: &Path,
     verbose: bool,
+    quarantine: &Quarantine,
 ) -> Result<(String, i32, PathBuf)> {
     let logfile = tmpdir.join("rustup_update.log");
 
+    // 通过 rustup check 获取候选版本，记录首次出现时间（与 Homebrew 一致，未启用隔离期时也记录），
+    // 并根据隔离期划分可更新的工具链
+    let mut update_cmd = "rustup update".to_string();
+    let candidates = match get_available_updates(runner, tmpdir) {
+        Some(candidates) => candidates,
+        // 启用隔离期时无法确认哪些版本已满隔离期，跳过本次更新
+        None if quarantine.is_enabled() => {
+            save_unchecked(tmpdir, "rustup");
+            return Ok(("unchanged".to_string(), 0, logfile));
+        }
+        None => Vec::new(),
+    };
+    let (allowed, deferred) = quarantine.partition("rustup", candidates);
+
+    if !deferred.is_empty() {
+        save_deferred(tmpdir, "rustup", &deferred);
+
+        // 所有候选版本都在隔离期内，跳过本次更新
+        if allowed.is_empty() {
+            return Ok(("unchanged".to_string(), 0, logfile));
+        }
+
+        let toolchains: Vec<String> = allowed.iter().map(|c| shell_quote(&c.name)).collect();
+        update_cmd = format!("rustup update {}", toolchains.join(" "));
+    }
+
     // 获取更新前的工具链版本信息
     let versions_before = get_toolchain_versions_json(runner, tmpdir)?;
 
-    // 执行更新 - 更新所有已安装的工具链
-    let (rc, out) = runner.run("rustup update", &logfile, verbose)?;
+    // 执行更新 - 默认更新所有已安装的工具链
+    let (rc, out) = runner.run(&update_cmd, &logfile, verbose)?;
 
     if rc != 0 {
         return Ok(("failed".to_string(), rc, logfile));
@@ -402,6 +661,82 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_parse_rustup_check() {
+        let output = "stable-x86_64-apple-darwin - Update available : 1.70.0 (90c541806 2023-05-31) -> 1.71.0 (8ede3aae2 2023-07-12)
+nightly-x86_64-apple-darwin - Up to date : 1.73.0-nightly (0bcb6c1f0 2023-07-12)
+rustup - Update available : 1.26.0 -> 1.27.0";
+        let candidates = parse_rustup_check(output);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].name, "stable-x86_64-apple-darwin");
+        assert_eq!(candidates[0].current, "1.70.0");
+        assert_eq!(candidates[0].latest, "1.71.0");
+    }
+
+    /// 写入固定输出并以 `rc` 退出的执行器
+    struct FixedRunner {
+        output: &'static str,
+        rc: i32,
+    }
+
+    impl Runner for FixedRunner {
+        fn run(&self, _cmd: &str, logfile: &Path, _verbose: bool) -> Result<(i32, String)> {
+            std::fs::write(logfile, self.output)?;
+            Ok((self.rc, self.output.to_string()))
+        }
+    }
+
+    #[test]
+    fn test_pending_upgrades_checks_exit_code() {
+        let tmp = tempfile::tempdir().unwrap();
+        let update = FixedRunner {
+            output: "stable-x86_64-apple-darwin - Update available : 1.70.0 (90c541806 2023-05-31) -> 1.71.0 (8ede3aae2 2023-07-12)",
+            rc: 100,
+        };
+        assert_eq!(pending_upgrades(&update, tmp.path()).unwrap().len(), 1);
+
+        let failed = FixedRunner {
+            output: "error: could not download nonexistent component",
+            rc: 1,
+        };
+        assert!(pending_upgrades(&failed, tmp.path()).is_err());
+    }
+
+    #[test]
+    fn test_parse_toolchain_inventory() {
+        let show = "Default host: x86_64-unknown-linux-gnu\nrustup home:  /root/.rustup\n";
+        let host = parse_default_host(show);
+        assert_eq!(host.as_deref(), Some("x86_64-unknown-linux-gnu"));
+
+        let list =
+            "stable-x86_64-unknown-linux-gnu (active, default)\nnightly-x86_64-unknown-linux-gnu\n";
+        let names = parse_toolchain_list(list);
+        assert_eq!(names.len(), 2);
+        assert_eq!(strip_host(&names[0], host.as_deref()), "stable");
+
+        let targets = vec![
+            "wasm32-unknown-unknown".to_string(),
+            "x86_64-unknown-linux-gnu".to_string(),
+        ];
+        let components = parse_components(
+            "cargo-x86_64-unknown-linux-gnu\nclippy-x86_64-unknown-linux-gnu\nrust-src\nrust-std-wasm32-unknown-unknown\n",
+            &targets,
+        );
+        assert_eq!(components, vec!["cargo", "clippy", "rust-src"]);
+    }
+
+    #[test]
+    fn test_parse_default_toolchain() {
+        assert_eq!(
+            parse_default_toolchain("stable-x86_64-apple-darwin (default)\n"),
+            Some("stable-x86_64-apple-darwin".to_string())
+        );
+        assert_eq!(
+            parse_default_toolchain("no default toolchain configured"),
+            None
+        );
+    }
+
     #[test]
     fn test_detect_version_changes_with_version_upgrade() {
         let before = vec![ToolchainVersion {
@@ -7,6 +7,8 @@ use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
+use crate::t;
+
 /// 升级详情条目
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpgradeDetail {
@@ -55,11 +57,11 @@ impl UpgradeDetail {
         }
     }
 
-    /// 创建新安装详情
+    /// 创建新安装详情，旧版本为空
     pub fn new_installation(name: String, version: String) -> Self {
         Self {
             name,
-            old_version: "未安装".to_string(),
+            old_version: String::new(),
             new_version: version,
             upgrade_type: UpgradeType::NewInstallation,
         }
@@ -76,68 +78,46 @@ impl UpgradeDetail {
         }
     }
 
-    /// 格式化为显示字符串
-    #[allow(dead_code)]
-    pub fn to_display_string(&self) -> String {
+    /// 格式化为本地化的显示字符串，`mark_downgrade` 为真时标注降级
+    fn format(&self, lang: &str, mark_downgrade: bool) -> String {
         match self.upgrade_type {
-            UpgradeType::VersionUpgrade => {
+            UpgradeType::NewInstallation => t!(
+                lang,
+                "update-detail-new",
+                name = self.name.as_str(),
+                version = self.new_version.as_str()
+            ),
+            UpgradeType::Downgrade if mark_downgrade => t!(
+                lang,
+                "update-detail-downgrade",
+                name = self.name.as_str(),
+                old = self.old_version.as_str(),
+                new = self.new_version.as_str()
+            ),
+            UpgradeType::VersionUpgrade | UpgradeType::Downgrade => {
                 format!("{}: {} → {}", self.name, self.old_version, self.new_version)
             }
-            UpgradeType::NewInstallation => {
-                format!("{}: new installation → {}", self.name, self.new_version)
-            }
-            UpgradeType::Downgrade => {
-                format!(
-                    "{}: {} → {} (降级)",
-                    self.name, self.old_version, self.new_version
-                )
-            }
         }
     }
 
-    /// 格式化为简单字符串（兼容现有格式）
-    pub fn to_legacy_string(&self) -> String {
-        match self.upgrade_type {
-            UpgradeType::VersionUpgrade => {
-                format!("{}: {} → {}", self.name, self.old_version, self.new_version)
-            }
-            UpgradeType::NewInstallation => {
-                format!("{}: new installation → {}", self.name, self.new_version)
-            }
-            UpgradeType::Downgrade => {
-                format!("{}: {} → {}", self.name, self.old_version, self.new_version)
-            }
-        }
+    /// 格式化为显示字符串
+    #[allow(dead_code)]
+    pub fn to_display_string(&self, lang: &str) -> String {
+        self.format(lang, true)
+    }
+
+    /// 格式化为简单字符串（兼容现有格式，不标注降级）
+    pub fn to_legacy_string(&self, lang: &str) -> String {
+        self.format(lang, false)
     }
 
     /// 格式化为增强的显示字符串（支持工具链类型标识）
-    pub fn to_enhanced_string(&self) -> String {
+    pub fn to_enhanced_string(&self, lang: &str) -> String {
         let toolchain_type = self.get_toolchain_type();
-        let type_indicator = if !toolchain_type.is_empty() {
-            format!("[{}] ", toolchain_type)
+        if toolchain_type.is_empty() {
+            self.format(lang, true)
         } else {
-            String::new()
-        };
-
-        match self.upgrade_type {
-            UpgradeType::VersionUpgrade => {
-                format!(
-                    "{}{}: {} → {}",
-                    type_indicator, self.name, self.old_version, self.new_version
-                )
-            }
-            UpgradeType::NewInstallation => {
-                format!(
-                    "{}{}: new installation → {}",
-                    type_indicator, self.name, self.new_version
-                )
-            }
-            UpgradeType::Downgrade => {
-                format!(
-                    "{}{}: {} → {} (降级)",
-                    type_indicator, self.name, self.old_version, self.new_version
-                )
-            }
+            format!("[{}] {}", toolchain_type, self.format(lang, true))
         }
     }
 
@@ -219,14 +199,20 @@ impl UpgradeDetails {
 
     /// 格式化为显示字符串列表
     #[allow(dead_code)]
-    pub fn to_display_strings(&self) -> Vec<String> {
-        self.details.iter().map(|d| d.to_display_string()).collect()
+    pub fn to_display_strings(&self, lang: &str) -> Vec<String> {
+        self.details
+            .iter()
+            .map(|d| d.to_display_string(lang))
+            .collect()
     }
 
     /// 格式化为传统字符串列表（兼容现有格式）
     #[allow(dead_code)]
-    pub fn to_legacy_strings(&self) -> Vec<String> {
-        self.details.iter().map(|d| d.to_legacy_string()).collect()
+    pub fn to_legacy_strings(&self, lang: &str) -> Vec<String> {
+        self.details
+            .iter()
+            .map(|d| d.to_legacy_string(lang))
+            .collect()
     }
 
     /// 保存到文件（JSON 格式）
@@ -238,16 +224,16 @@ impl UpgradeDetails {
     }
 
     /// 保存到文件（传统文本格式）
-    pub fn save_to_text_file(&self, file_path: &Path) -> Result<()> {
+    pub fn save_to_text_file(&self, file_path: &Path, lang: &str) -> Result<()> {
         let mut file = File::create(file_path)?;
         for detail in &self.details {
-            writeln!(file, "{}", detail.to_legacy_string())?;
+            writeln!(file, "{}", detail.to_legacy_string(lang))?;
         }
         Ok(())
     }
 
     /// 保存到增强文本文件（支持工具链类型分组）
-    pub fn save_to_enhanced_text_file(&self, file_path: &Path) -> Result<()> {
+    pub fn save_to_enhanced_text_file(&self, file_path: &Path, lang: &str) -> Result<()> {
         let mut file = File::create(file_path)?;
 
         // 按工具链类型分组
@@ -267,34 +253,24 @@ impl UpgradeDetails {
         }
 
         // 写入分组后的内容
-        if !stable_upgrades.is_empty() {
-            writeln!(file, "Stable 工具链:")?;
-            for detail in stable_upgrades {
-                writeln!(file, "  {}", detail.to_enhanced_string())?;
+        let groups = [
+            ("update-toolchains-stable", stable_upgrades),
+            ("update-toolchains-beta", beta_upgrades),
+            ("update-toolchains-nightly", nightly_upgrades),
+            ("update-toolchains-other", other_upgrades),
+        ];
+        let mut first = true;
+        for (heading, upgrades) in groups {
+            if upgrades.is_empty() {
+                continue;
             }
-            writeln!(file)?;
-        }
-
-        if !beta_upgrades.is_empty() {
-            writeln!(file, "Beta 工具链:")?;
-            for detail in beta_upgrades {
-                writeln!(file, "  {}", detail.to_enhanced_string())?;
+            if !first {
+                writeln!(file)?;
             }
-            writeln!(file)?;
-        }
-
-        if !nightly_upgrades.is_empty() {
-            writeln!(file, "Nightly 工具链:")?;
-            for detail in nightly_upgrades {
-                writeln!(file, "  {}", detail.to_enhanced_string())?;
-            }
-            writeln!(file)?;
-        }
-
-        if !other_upgrades.is_empty() {
-            writeln!(file, "其他工具链:")?;
-            for detail in other_upgrades {
-                writeln!(file, "  {}", detail.to_enhanced_string())?;
+            first = false;
+            writeln!(file, "{}", t!(lang, heading))?;
+            for detail in upgrades {
+                writeln!(file, "  {}", detail.to_enhanced_string(lang))?;
             }
         }
 
@@ -319,24 +295,24 @@ impl UpgradeDetails {
                 continue;
             }
 
-            // 解析传统格式: "name: old → new" 或 "name: new installation → new"
+            // 解析传统格式: "name: old → new"，或任一语言的新安装格式
             if let Some((name_part, version_part)) = line.split_once(':') {
                 let name = name_part.trim().to_string();
-
-                if version_part.contains("new installation") {
-                    // 新安装格式: "name: new installation → version"
-                    if let Some((_, version)) = version_part.split_once("→") {
-                        let version = version.trim().to_string();
-                        details.add_detail(UpgradeDetail::new_installation(name, version));
-                    }
-                } else if let Some((old, new)) = version_part.split_once("→") {
-                    // 版本升级格式: "name: old → new"
-                    let old_version = old.trim().to_string();
-                    let new_version = new.trim().to_string();
+                let Some((old, new)) = version_part.split_once("→") else {
+                    continue;
+                };
+                let new_version = new.trim().to_string();
+                let new_installation = UpgradeDetail::new_installation(name, new_version);
+                let is_new_installation = crate::i18n::available_languages()
+                    .into_iter()
+                    .any(|lang| new_installation.to_legacy_string(lang) == line);
+                if is_new_installation {
+                    details.add_detail(new_installation);
+                } else {
                     details.add_detail(UpgradeDetail::version_upgrade(
-                        name,
-                        old_version,
-                        new_version,
+                        new_installation.name,
+                        old.trim().to_string(),
+                        new_installation.new_version,
                     ));
                 }
             }
@@ -388,13 +364,14 @@ impl UpgradeDetailsManager {
         details.save_to_json_file(&json_file)?;
 
         // 保存文本格式（用于显示）
+        let lang = crate::i18n::detect_system_language();
         let text_file = tmpdir.join(format!("{}_upgrade_details.txt", tool_name));
-        details.save_to_text_file(&text_file)?;
+        details.save_to_text_file(&text_file, &lang)?;
 
         // 为 Rustup 保存增强格式（支持工具链类型分组）
         if tool_name == "rustup" {
             let enhanced_file = tmpdir.join(format!("{}_upgrade_details_enhanced.txt", tool_name));
-            details.save_to_enhanced_text_file(&enhanced_file)?;
+            details.save_to_enhanced_text_file(&enhanced_file, &lang)?;
         }
 
         Ok(())
@@ -435,7 +412,10 @@ mod tests {
         assert_eq!(detail.old_version, "1.0.0");
         assert_eq!(detail.new_version, "1.1.0");
         assert!(matches!(detail.upgrade_type, UpgradeType::VersionUpgrade));
-        assert_eq!(detail.to_display_string(), "test-package: 1.0.0 → 1.1.0");
+        assert_eq!(
+            detail.to_display_string("en"),
+            "test-package: 1.0.0 → 1.1.0"
+        );
     }
 
     #[test]
@@ -444,13 +424,25 @@ mod tests {
             UpgradeDetail::new_installation("new-package".to_string(), "2.0.0".to_string());
 
         assert_eq!(detail.name, "new-package");
-        assert_eq!(detail.old_version, "未安装");
+        assert_eq!(detail.old_version, "");
         assert_eq!(detail.new_version, "2.0.0");
         assert!(matches!(detail.upgrade_type, UpgradeType::NewInstallation));
         assert_eq!(
-            detail.to_display_string(),
+            detail.to_display_string("en"),
             "new-package: new installation → 2.0.0"
         );
+        assert_eq!(
+            detail.to_display_string("zh"),
+            "new-package: 新安装 → 2.0.0"
+        );
+
+        let downgrade =
+            UpgradeDetail::downgrade("pkg".to_string(), "2.0.0".to_string(), "1.9.0".to_string());
+        assert_eq!(
+            downgrade.to_display_string("en"),
+            "pkg: 2.0.0 → 1.9.0 (downgrade)"
+        );
+        assert_eq!(downgrade.to_legacy_string("zh"), "pkg: 2.0.0 → 1.9.0");
     }
 
     #[test]
@@ -472,5 +464,13 @@ mod tests {
         assert_eq!(details.upgrade_count(), 2);
         assert_eq!(details.version_upgrade_count(), 1);
         assert_eq!(details.new_installation_count(), 1);
+
+        // 按任一语言保存的文本格式都能读回
+        let tmp = tempfile::tempdir().unwrap();
+        let text_file = tmp.path().join("details.txt");
+        details.save_to_text_file(&text_file, "zh-Hant").unwrap();
+        let loaded = UpgradeDetails::load_from_text_file(&text_file).unwrap();
+        assert_eq!(loaded.version_upgrade_count(), 1);
+        assert_eq!(loaded.new_installation_count(), 1);
     }
 }
@@ -0,0 +1,354 @@
+// 配置文件模块
+// 读取 ~/.config/devtool/config.toml，所有字段均为可选
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// devtool 配置
+///
+/// 配置文件不存在时使用默认值；命令行参数优先于配置文件。
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// 界面语言，例如 "en"、"zh"；未设置时根据系统环境检测
+    pub lang: Option<String>,
+    /// 屏幕阅读器友好的输出，与 `devtool update --accessible` 相同
+    pub accessible: bool,
+    /// 图标和颜色主题
+    pub theme: ThemeConfig,
+    /// 新版本隔离期设置
+    pub quarantine: QuarantineConfig,
+    /// 维护窗口与冻结期设置
+    pub policy: PolicyConfig,
+    /// 运行结束时的通知
+    pub notify: Vec<NotifyConfig>,
+    /// 运行日志的保留策略
+    pub logs: LogsConfig,
+    /// 日志和报告中的敏感信息脱敏
+    pub redact: RedactConfig,
+}
+
+/// 主题配置（`[theme]`）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// 内置主题：default、ascii、nerd-font、monochrome
+    pub name: Option<String>,
+    /// 覆盖角色颜色，例如 `success = "bright green"`、`arrow = "#888888"`、`info = "none"`
+    pub colors: ThemeColorsConfig,
+    /// 覆盖图标，例如 `success = "OK"`
+    pub glyphs: ThemeGlyphsConfig,
+}
+
+/// 各语义角色的颜色
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeColorsConfig {
+    pub success: Option<String>,
+    pub failure: Option<String>,
+    pub warning: Option<String>,
+    pub info: Option<String>,
+    /// 横幅和工具标题
+    pub header: Option<String>,
+    /// 版本箭头
+    pub arrow: Option<String>,
+}
+
+/// 各图标的字符
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeGlyphsConfig {
+    pub success: Option<String>,
+    pub failure: Option<String>,
+    pub warning: Option<String>,
+    pub info: Option<String>,
+    pub arrow: Option<String>,
+    pub rocket: Option<String>,
+    pub clipboard: Option<String>,
+    pub package: Option<String>,
+    pub rust: Option<String>,
+    pub wrench: Option<String>,
+    pub pause: Option<String>,
+    pub tools: Option<String>,
+    pub refresh: Option<String>,
+    pub memo: Option<String>,
+    pub bulb: Option<String>,
+}
+
+/// 新版本隔离期配置
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct QuarantineConfig {
+    /// 新版本首次出现后需等待的时长，例如 "7d"、"36h"
+    pub min_age: Option<String>,
+}
+
+/// 运行日志保留策略（`[logs]`），每次更新开始时清理超出限制的旧运行
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogsConfig {
+    /// 最多保留的运行数量，0 表示不限制
+    pub keep_runs: Option<usize>,
+    /// 运行日志的最长保留时间，例如 "30d"
+    pub max_age: Option<String>,
+    /// 全部运行日志的总大小上限，例如 "500MB"
+    pub max_size: Option<String>,
+}
+
+/// 敏感信息脱敏（`[redact]`），在内置规则之外追加
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedactConfig {
+    /// 值需要脱敏的环境变量名，例如 "ARTIFACTORY_CREDS"
+    pub env: Vec<String>,
+    /// 需要脱敏的正则表达式，例如 "corp-[0-9a-f]{32}"
+    pub patterns: Vec<String>,
+}
+
+/// 更新策略配置
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PolicyConfig {
+    /// 允许更新的维护窗口（本地时间），例如 "Mon-Fri 08:00-10:00"
+    pub windows: Vec<String>,
+    /// 禁止更新的冻结期
+    pub freeze: Vec<FreezeConfig>,
+}
+
+/// 冻结期配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct FreezeConfig {
+    /// 开始日期（包含），格式 YYYY-MM-DD
+    pub from: String,
+    /// 结束日期（包含），格式 YYYY-MM-DD
+    pub to: String,
+    /// 冻结原因
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// 通知配置（`[[notify]]`）
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct NotifyConfig {
+    /// 触发条件
+    #[serde(default)]
+    pub on: NotifyOn,
+    /// 通知方式，由 `type` 字段区分
+    #[serde(flatten)]
+    pub sink: SinkConfig,
+}
+
+/// 通知触发条件
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyOn {
+    /// 每次运行结束
+    #[default]
+    Always,
+    /// 只在有工具失败时
+    Failure,
+    /// 只在有软件包被升级时
+    Upgrades,
+}
+
+/// 通知方式
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SinkConfig {
+    /// 通用 JSON webhook
+    Webhook {
+        url: String,
+        #[serde(default)]
+        format: WebhookFormat,
+        /// 自定义请求体模板，覆盖 format
+        #[serde(default)]
+        template: Option<String>,
+    },
+    /// SMTP 邮件
+    Email {
+        /// 例如 smtps://smtp.example.com:465
+        smtp_url: String,
+        from: String,
+        to: Vec<String>,
+        #[serde(default)]
+        username: Option<String>,
+        /// 保存密码的环境变量名
+        #[serde(default)]
+        password_env: Option<String>,
+    },
+    /// 桌面通知（notify-send）
+    Desktop,
+    /// ntfy 推送，url 包含主题，例如 https://ntfy.sh/my-topic
+    Ntfy {
+        url: String,
+        #[serde(default)]
+        token: Option<String>,
+    },
+    /// gotify 推送
+    Gotify { url: String, token: String },
+}
+
+/// webhook 请求体格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// 完整的运行摘要 JSON
+    #[default]
+    Json,
+    Slack,
+    Discord,
+    Feishu,
+}
+
+/// 获取配置文件路径
+///
+/// 可通过 `DEVTOOL_CONFIG` 环境变量覆盖默认位置
+pub fn get_config_path() -> PathBuf {
+    if let Ok(path) = std::env::var("DEVTOOL_CONFIG") {
+        return PathBuf::from(path);
+    }
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("~/.config"))
+        .join("devtool")
+        .join("config.toml")
+}
+
+impl Config {
+    /// 加载配置文件，文件不存在时返回默认配置
+    pub fn load() -> Result<Self> {
+        let path = get_config_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content =
+            std::fs::read_to_string(&path).with_context(|| format!("read config {:?}", path))?;
+        Self::parse(&content).with_context(|| format!("parse config {:?}", path))
+    }
+
+    /// 从 TOML 文本解析配置
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_empty_config() {
+        let config = Config::parse("").unwrap();
+        assert!(config.quarantine.min_age.is_none());
+    }
+
+    #[test]
+    fn test_parse_quarantine() {
+        let config =
+            Config::parse("lang = \"zh\"\naccessible = true\n[quarantine]\nmin_age = \"7d\"\n")
+                .unwrap();
+        assert_eq!(config.lang.as_deref(), Some("zh"));
+        assert!(config.accessible);
+        assert_eq!(config.quarantine.min_age.as_deref(), Some("7d"));
+    }
+
+    #[test]
+    fn test_parse_logs() {
+        let config =
+            Config::parse("[logs]\nkeep_runs = 5\nmax_age = \"14d\"\nmax_size = \"1GB\"\n")
+                .unwrap();
+        assert_eq!(config.logs.keep_runs, Some(5));
+        assert_eq!(config.logs.max_age.as_deref(), Some("14d"));
+        assert_eq!(config.logs.max_size.as_deref(), Some("1GB"));
+        assert!(Config::parse("[logs]\nkeep = 5\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_redact() {
+        let config = Config::parse(
+            "[redact]\nenv = [\"ARTIFACTORY_CREDS\"]\npatterns = [\"corp-[0-9a-f]{32}\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.redact.env, vec!["ARTIFACTORY_CREDS"]);
+        assert_eq!(config.redact.patterns, vec!["corp-[0-9a-f]{32}"]);
+        assert!(Config::parse("[redact]\npattern = \"x\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        let config = Config::parse(
+            r#"
+[policy]
+windows = ["Mon-Fri 08:00-10:00"]
+
+[[policy.freeze]]
+from = "2026-12-15"
+to = "2027-01-05"
+reason = "year-end freeze"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.policy.windows.len(), 1);
+        assert_eq!(config.policy.freeze[0].to, "2027-01-05");
+        assert_eq!(
+            config.policy.freeze[0].reason.as_deref(),
+            Some("year-end freeze")
+        );
+    }
+
+    #[test]
+    fn test_parse_notify() {
+        let config = Config::parse(
+            r#"
+[[notify]]
+type = "webhook"
+url = "https://hooks.slack.com/services/x"
+format = "slack"
+on = "failure"
+
+[[notify]]
+type = "desktop"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.notify.len(), 2);
+        assert_eq!(config.notify[0].on, NotifyOn::Failure);
+        assert_eq!(
+            config.notify[0].sink,
+            SinkConfig::Webhook {
+                url: "https://hooks.slack.com/services/x".to_string(),
+                format: WebhookFormat::Slack,
+                template: None,
+            }
+        );
+        assert_eq!(config.notify[1].on, NotifyOn::Always);
+        assert_eq!(config.notify[1].sink, SinkConfig::Desktop);
+        assert!(Config::parse("[[notify]]\ntype = \"pager\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        let config = Config::parse(
+            r#"
+[theme]
+name = "ascii"
+
+[theme.colors]
+success = "bright green"
+
+[theme.glyphs]
+arrow = "=>"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.theme.name.as_deref(), Some("ascii"));
+        assert_eq!(config.theme.colors.success.as_deref(), Some("bright green"));
+        assert_eq!(config.theme.glyphs.arrow.as_deref(), Some("=>"));
+        assert!(Config::parse("[theme.colors]\nsucess = \"red\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_config() {
+        assert!(Config::parse("[quarantine\n").is_err());
+    }
+}
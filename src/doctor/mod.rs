@@ -0,0 +1,482 @@
+// 环境诊断模块
+// 检查 PATH 冲突、Homebrew 前缀权限、残留锁文件、缺失依赖等常见的更新失败原因
+
+use serde::Serialize;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use which::which;
+
+use crate::commands::{mise, rustup};
+use crate::runner::Runner;
+use crate::t;
+use crate::utils::ensure_cache_dir;
+
+/// 检查结果状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// 单项诊断结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    /// 检查项标识，例如 "duplicate-rustc"
+    pub id: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+    /// 修复建议
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl Diagnostic {
+    fn ok(id: &'static str, message: String) -> Self {
+        Self {
+            id,
+            status: CheckStatus::Ok,
+            message,
+            fix: None,
+        }
+    }
+
+    fn warn(id: &'static str, message: String, fix: String) -> Self {
+        Self {
+            id,
+            status: CheckStatus::Warn,
+            message,
+            fix: Some(fix),
+        }
+    }
+
+    fn fail(id: &'static str, message: String, fix: String) -> Self {
+        Self {
+            id,
+            status: CheckStatus::Fail,
+            message,
+            fix: Some(fix),
+        }
+    }
+}
+
+/// 超过该时长的 git 锁文件视为残留
+const STALE_LOCK_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// 在 PATH 中查找所有同名可执行文件，按 PATH 顺序返回（去除指向同一文件的重复项）
+fn find_all_in_path(name: &str, path_var: &OsStr) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = Vec::new();
+    let mut seen: Vec<PathBuf> = Vec::new();
+    for dir in std::env::split_paths(path_var) {
+        let candidate = dir.join(name);
+        if !candidate.is_file() {
+            continue;
+        }
+        let canonical = candidate
+            .canonicalize()
+            .unwrap_or_else(|_| candidate.clone());
+        if !seen.contains(&canonical) {
+            seen.push(canonical);
+            found.push(candidate);
+        }
+    }
+    found
+}
+
+/// 检查 git 是否可用（Homebrew 和 mise 插件依赖 git）
+fn check_git(lang: &str, git: Option<PathBuf>) -> Diagnostic {
+    match git {
+        Some(path) => Diagnostic::ok(
+            "git",
+            t!(lang, "doctor-git-ok", path = path.display().to_string()),
+        ),
+        None => Diagnostic::fail(
+            "git",
+            t!(lang, "doctor-git-missing"),
+            t!(lang, "doctor-git-missing-fix"),
+        ),
+    }
+}
+
+/// 检查是否检测到任何受支持的工具
+fn check_supported_tools(lang: &str, found: &[&str]) -> Diagnostic {
+    if found.is_empty() {
+        Diagnostic::fail(
+            "supported-tools",
+            t!(lang, "doctor-tools-missing"),
+            t!(lang, "doctor-tools-missing-fix"),
+        )
+    } else {
+        Diagnostic::ok(
+            "supported-tools",
+            t!(
+                lang,
+                "doctor-tools-ok",
+                tools = found.join(&t!(lang, "list-separator"))
+            ),
+        )
+    }
+}
+
+/// 检查 PATH 中是否有多个 rustc（例如 rustup 与 Homebrew 的 rust 同时存在）
+fn check_duplicate_rustc(lang: &str, rustc: &[PathBuf], brew_prefix: Option<&Path>) -> Diagnostic {
+    if rustc.len() <= 1 {
+        return Diagnostic::ok("duplicate-rustc", t!(lang, "doctor-rustc-ok"));
+    }
+
+    let list = rustc
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let from_brew = brew_prefix.is_some_and(|prefix| rustc.iter().any(|p| p.starts_with(prefix)));
+    let fix = if from_brew {
+        t!(lang, "doctor-rustc-fix-brew")
+    } else {
+        t!(lang, "doctor-rustc-fix")
+    };
+    Diagnostic::warn(
+        "duplicate-rustc",
+        t!(lang, "doctor-rustc-multiple", list = list),
+        fix,
+    )
+}
+
+/// 检查 mise 管理的工具是否被 PATH 中的其他同名程序遮蔽
+///
+/// `resolved` 为 (工具名, 版本, PATH 中第一个同名程序)
+fn check_mise_shadowing(
+    lang: &str,
+    resolved: &[(String, String, PathBuf)],
+    brew_prefix: Option<&Path>,
+) -> Diagnostic {
+    let shadowed: Vec<&(String, String, PathBuf)> = resolved
+        .iter()
+        .filter(|(_, _, path)| !path.to_string_lossy().contains("mise"))
+        .collect();
+
+    if shadowed.is_empty() {
+        return Diagnostic::ok("mise-shadowed", t!(lang, "doctor-mise-ok"));
+    }
+
+    let list = shadowed
+        .iter()
+        .map(|(name, version, path)| format!("{}@{} → {}", name, version, path.display()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let brew_names: Vec<&str> = shadowed
+        .iter()
+        .filter(|(_, _, path)| brew_prefix.is_some_and(|prefix| path.starts_with(prefix)))
+        .map(|(name, _, _)| name.as_str())
+        .collect();
+    let fix = if brew_names.is_empty() {
+        t!(lang, "doctor-mise-fix")
+    } else {
+        t!(lang, "doctor-mise-fix-brew", names = brew_names.join(" "))
+    };
+    Diagnostic::warn(
+        "mise-shadowed",
+        t!(lang, "doctor-mise-shadowed", list = list),
+        fix,
+    )
+}
+
+/// Homebrew 在前缀下管理、需要当前用户可写的目录（与 brew doctor 检查的目录一致）；
+/// 前缀本身（例如 Intel macOS 上的 /usr/local）按设计属于 root，不在检查范围内
+const BREW_MANAGED_DIRS: [&str; 13] = [
+    "bin",
+    "etc",
+    "include",
+    "lib",
+    "sbin",
+    "share",
+    "opt",
+    "var/homebrew/linked",
+    "var/homebrew/locks",
+    "var/log",
+    "Frameworks",
+    "Cellar",
+    "Caskroom",
+];
+
+/// 检查 Homebrew 管理的目录是否可写，不存在的目录跳过
+fn check_brew_prefix_writable(lang: &str, prefix: &Path) -> Diagnostic {
+    let display = prefix.display().to_string();
+    let readonly: Vec<String> = BREW_MANAGED_DIRS
+        .iter()
+        .map(|dir| prefix.join(dir))
+        .filter(|dir| dir.exists() && tempfile::tempfile_in(dir).is_err())
+        .map(|dir| dir.display().to_string())
+        .collect();
+    if readonly.is_empty() {
+        return Diagnostic::ok(
+            "brew-prefix",
+            t!(lang, "doctor-prefix-ok", prefix = display.as_str()),
+        );
+    }
+    Diagnostic::fail(
+        "brew-prefix",
+        t!(
+            lang,
+            "doctor-prefix-readonly",
+            prefix = display.as_str(),
+            dirs = readonly.join(", ")
+        ),
+        format!("sudo chown -R \"$(whoami)\" {}", readonly.join(" ")),
+    )
+}
+
+/// 检查 Homebrew 仓库中是否残留 git 锁文件（通常由中断的 brew update 留下）
+fn check_brew_update_lock(lang: &str, lock: &Path, now: SystemTime) -> Diagnostic {
+    let age = std::fs::metadata(lock)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok());
+    let path = lock.display().to_string();
+
+    match age {
+        Some(age) if age >= STALE_LOCK_AGE => Diagnostic::fail(
+            "brew-update-lock",
+            t!(
+                lang,
+                "doctor-lock-stale",
+                path = path.as_str(),
+                minutes = age.as_secs() / 60
+            ),
+            format!("rm {}", path),
+        ),
+        Some(_) => Diagnostic::warn(
+            "brew-update-lock",
+            t!(lang, "doctor-lock-active", path = path.as_str()),
+            t!(lang, "doctor-lock-active-fix"),
+        ),
+        None => Diagnostic::ok("brew-update-lock", t!(lang, "doctor-lock-ok")),
+    }
+}
+
+/// 检查 rustup 是否配置了默认工具链
+fn check_rustup_default(lang: &str, default: Option<&str>) -> Diagnostic {
+    match default {
+        Some(toolchain) => Diagnostic::ok(
+            "rustup-default",
+            t!(lang, "doctor-rustup-default-ok", toolchain = toolchain),
+        ),
+        None => Diagnostic::fail(
+            "rustup-default",
+            t!(lang, "doctor-rustup-default-missing"),
+            "rustup default stable".to_string(),
+        ),
+    }
+}
+
+/// 检查缓存目录是否可写
+fn check_cache_dir(lang: &str) -> Diagnostic {
+    match ensure_cache_dir() {
+        Ok(dir) => Diagnostic::ok(
+            "cache-dir",
+            t!(lang, "doctor-cache-ok", path = dir.display().to_string()),
+        ),
+        Err(e) => Diagnostic::fail(
+            "cache-dir",
+            t!(lang, "doctor-cache-error", error = e.to_string()),
+            t!(lang, "doctor-cache-error-fix"),
+        ),
+    }
+}
+
+/// 运行命令并返回去除首尾空白的输出，失败时返回 `None`
+fn command_output(runner: &dyn Runner, cmd: &str, logfile: &Path) -> Option<String> {
+    match runner.run(cmd, logfile, false) {
+        Ok((0, output)) if !output.trim().is_empty() => Some(output.trim().to_string()),
+        _ => None,
+    }
+}
+
+/// 运行全部诊断
+pub fn run_checks(runner: &dyn Runner, tmpdir: &Path, lang: &str) -> Vec<Diagnostic> {
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    let has_brew = which("brew").is_ok();
+    let has_rustup = which("rustup").is_ok();
+    let has_mise = which("mise").is_ok();
+
+    let mut diagnostics = vec![check_git(lang, which("git").ok())];
+
+    let found: Vec<&str> = [
+        ("brew", has_brew),
+        ("rustup", has_rustup),
+        ("mise", has_mise),
+    ]
+    .iter()
+    .filter(|(_, present)| *present)
+    .map(|(name, _)| *name)
+    .collect();
+    diagnostics.push(check_supported_tools(lang, &found));
+    diagnostics.push(check_cache_dir(lang));
+
+    let brew_prefix = if has_brew {
+        command_output(
+            runner,
+            "brew --prefix",
+            &tmpdir.join("doctor_brew_prefix.log"),
+        )
+        .map(PathBuf::from)
+    } else {
+        None
+    };
+
+    if let Some(prefix) = &brew_prefix {
+        diagnostics.push(check_brew_prefix_writable(lang, prefix));
+        if let Some(repository) = command_output(
+            runner,
+            "brew --repository",
+            &tmpdir.join("doctor_brew_repository.log"),
+        ) {
+            let lock = PathBuf::from(repository).join(".git").join("index.lock");
+            diagnostics.push(check_brew_update_lock(lang, &lock, SystemTime::now()));
+        }
+    }
+
+    diagnostics.push(check_duplicate_rustc(
+        lang,
+        &find_all_in_path("rustc", &path_var),
+        brew_prefix.as_deref(),
+    ));
+
+    if has_rustup {
+        let default = rustup::default_toolchain_version(runner, tmpdir)
+            .ok()
+            .flatten()
+            .map(|(toolchain, _)| toolchain);
+        diagnostics.push(check_rustup_default(lang, default.as_deref()));
+    }
+
+    if has_mise {
+        let resolved: Vec<(String, String, PathBuf)> = mise::installed_tools(runner, tmpdir)
+            .unwrap_or_default()
+            .into_iter()
+            // 带后端前缀的工具（如 npm:prettier）的可执行文件名与工具名不同，跳过
+            .filter(|(name, _)| !name.contains(':'))
+            .filter_map(|(name, version)| {
+                let first = find_all_in_path(&name, &path_var).into_iter().next()?;
+                Some((name, version, first))
+            })
+            .collect();
+        diagnostics.push(check_mise_shadowing(
+            lang,
+            &resolved,
+            brew_prefix.as_deref(),
+        ));
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_all_in_path() {
+        let a = tempdir().unwrap();
+        let b = tempdir().unwrap();
+        File::create(a.path().join("rustc")).unwrap();
+        File::create(b.path().join("rustc")).unwrap();
+        let path_var = std::env::join_paths([a.path(), b.path(), a.path()]).unwrap();
+
+        let found = find_all_in_path("rustc", &path_var);
+        assert_eq!(found.len(), 2);
+        assert!(found[0].starts_with(a.path()));
+        assert!(find_all_in_path("cargo", &path_var).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_rustc_from_brew() {
+        let rustc = vec![
+            PathBuf::from("/opt/homebrew/bin/rustc"),
+            PathBuf::from("/Users/me/.cargo/bin/rustc"),
+        ];
+        let diagnostic = check_duplicate_rustc("en", &rustc, Some(Path::new("/opt/homebrew")));
+        assert_eq!(diagnostic.status, CheckStatus::Warn);
+        assert!(diagnostic.fix.unwrap().starts_with("brew uninstall rust"));
+
+        let single = check_duplicate_rustc("en", &rustc[1..], None);
+        assert_eq!(single.status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_mise_shadowing() {
+        let resolved = vec![
+            (
+                "node".to_string(),
+                "20.11.0".to_string(),
+                PathBuf::from("/opt/homebrew/bin/node"),
+            ),
+            (
+                "python".to_string(),
+                "3.12.1".to_string(),
+                PathBuf::from("/Users/me/.local/share/mise/shims/python"),
+            ),
+        ];
+        let diagnostic = check_mise_shadowing("en", &resolved, Some(Path::new("/opt/homebrew")));
+        assert_eq!(diagnostic.status, CheckStatus::Warn);
+        assert!(diagnostic.message.contains("node@20.11.0"));
+        assert!(!diagnostic.message.contains("python"));
+        assert!(diagnostic.fix.unwrap().starts_with("brew uninstall node"));
+
+        let clean = check_mise_shadowing("en", &resolved[1..], None);
+        assert_eq!(clean.status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_brew_update_lock_age() {
+        let tmp = tempdir().unwrap();
+        let lock = tmp.path().join("index.lock");
+        let now = SystemTime::now();
+
+        assert_eq!(
+            check_brew_update_lock("en", &lock, now).status,
+            CheckStatus::Ok
+        );
+        File::create(&lock).unwrap();
+        assert_eq!(
+            check_brew_update_lock("en", &lock, now).status,
+            CheckStatus::Warn
+        );
+        let later = now + Duration::from_secs(60 * 60);
+        let diagnostic = check_brew_update_lock("en", &lock, later);
+        assert_eq!(diagnostic.status, CheckStatus::Fail);
+        assert_eq!(diagnostic.fix, Some(format!("rm {}", lock.display())));
+    }
+
+    #[test]
+    fn test_prefix_writable_and_missing_git() {
+        let tmp = tempdir().unwrap();
+        // 没有受管目录时不检查前缀本身
+        assert_eq!(
+            check_brew_prefix_writable("en", tmp.path()).status,
+            CheckStatus::Ok
+        );
+        std::fs::create_dir(tmp.path().join("bin")).unwrap();
+        assert_eq!(
+            check_brew_prefix_writable("en", tmp.path()).status,
+            CheckStatus::Ok
+        );
+        // 无法在其中创建文件的受管目录（这里用同名文件模拟）
+        std::fs::write(tmp.path().join("Cellar"), "").unwrap();
+        let diagnostic = check_brew_prefix_writable("en", tmp.path());
+        assert_eq!(diagnostic.status, CheckStatus::Fail);
+        assert_eq!(
+            diagnostic.fix,
+            Some(format!(
+                "sudo chown -R \"$(whoami)\" {}",
+                tmp.path().join("Cellar").display()
+            ))
+        );
+        assert_eq!(check_git("zh", None).status, CheckStatus::Fail);
+        assert_eq!(check_rustup_default("en", None).status, CheckStatus::Fail);
+    }
+}
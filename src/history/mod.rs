@@ -0,0 +1,105 @@
+// 运行历史
+// 每次更新结束后在缓存目录的 history.jsonl 中追加一条记录，供定时任务状态查询等使用
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::parallel::Tool;
+use crate::utils::get_cache_dir;
+
+/// 历史文件路径
+pub fn history_path() -> PathBuf {
+    get_cache_dir().join("history.jsonl")
+}
+
+/// 单个工具的执行结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolResult {
+    pub tool: Tool,
+    pub success: bool,
+    pub output: String,
+    /// 升级详情（与更新摘要相同）
+    pub upgrades: Vec<String>,
+}
+
+/// 一次更新的记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// 是否由定时任务触发
+    #[serde(default)]
+    pub scheduled: bool,
+    pub tools: Vec<ToolResult>,
+}
+
+impl RunRecord {
+    pub fn success(&self) -> bool {
+        self.tools.iter().all(|t| t.success)
+    }
+}
+
+/// 追加一条记录
+pub fn append(path: &Path, record: &RunRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("open history {:?}", path))?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+/// 读取全部记录（按时间顺序），跳过无法解析的行
+pub fn load(path: &Path) -> Vec<RunRecord> {
+    std::fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_append_and_load() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("history.jsonl");
+        assert!(load(&path).is_empty());
+
+        let record = RunRecord {
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+            scheduled: true,
+            tools: vec![ToolResult {
+                tool: Tool::Mise,
+                success: false,
+                output: "Mise failed".to_string(),
+                upgrades: Vec::new(),
+            }],
+        };
+        append(&path, &record).unwrap();
+        std::fs::write(
+            &path,
+            format!("{}not json\n", std::fs::read_to_string(&path).unwrap()),
+        )
+        .unwrap();
+        append(&path, &record).unwrap();
+
+        let records = load(&path);
+        assert_eq!(records, vec![record.clone(), record]);
+        assert!(!records[0].success());
+    }
+}
@@ -1,97 +1,276 @@
-use std::process::Command;
+// 国际化模块
+// 界面文本来自 locales/*.ftl 中的 Fluent 消息目录，构建时嵌入二进制；
+// 新增语言只需添加一个目录文件
 
-/// 检测系统语言
-/// 返回 "zh" 或 "en"
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use fluent_langneg::negotiate::filter_matches;
+use fluent_langneg::NegotiationStrategy;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+include!(concat!(env!("OUT_DIR"), "/catalogs.rs"));
+
+/// 当前语言缺少某条消息时回退到的语言
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// 决定消息语言的 POSIX 环境变量，按优先级排列
+const LOCALE_VARIABLES: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+static BUNDLES: OnceLock<HashMap<&'static str, FluentBundle<FluentResource>>> = OnceLock::new();
+/// `--lang` / 配置文件中的原始设置及其对应的目录
+static LANGUAGE_OVERRIDE: OnceLock<(String, &'static str)> = OnceLock::new();
+static DETECTED_LANGUAGE: OnceLock<&'static str> = OnceLock::new();
+
+/// 检测界面语言，返回消息目录名（例如 "en"、"zh"、"zh-Hant"）
+///
+/// 优先使用 `--lang` / 配置文件设置的语言，否则按系统语言环境协商，结果会被缓存。
 pub fn detect_system_language() -> String {
-    // 检查环境变量（优先级最高） / Check environment variables (highest priority)
-    if let Ok(lang) = std::env::var("LANG") {
-        if lang.starts_with("zh") {
-            return "zh".to_string();
-        } else if lang.starts_with("en") {
-            return "en".to_string();
-        }
+    if let Some((_, lang)) = LANGUAGE_OVERRIDE.get() {
+        return lang.to_string();
     }
+    DETECTED_LANGUAGE
+        .get_or_init(|| negotiate(&system_locales()).unwrap_or(FALLBACK_LANGUAGE))
+        .to_string()
+}
 
-    if let Ok(lang) = std::env::var("LC_ALL") {
-        if lang.starts_with("zh") {
-            return "zh".to_string();
-        } else if lang.starts_with("en") {
-            return "en".to_string();
-        }
-    }
+/// 设置界面语言，优先于系统检测（来自 `--lang` 或配置文件的 `lang`）
+///
+/// 只有第一次调用生效；没有对应目录时使用英语并返回 `false`。
+pub fn set_language(lang: &str) -> bool {
+    let resolved = parse_locale(lang).and_then(|locale| negotiate(&[locale]));
+    let _ = LANGUAGE_OVERRIDE.set((lang.to_string(), resolved.unwrap_or(FALLBACK_LANGUAGE)));
+    resolved.is_some()
+}
 
-    if let Ok(lang) = std::env::var("LC_CTYPE") {
-        if lang.starts_with("zh") {
-            return "zh".to_string();
-        } else if lang.starts_with("en") {
-            return "en".to_string();
-        }
+/// 把 "zh_TW.UTF-8"、"en-US" 之类的 POSIX 或 BCP 47 标签解析为语言标识
+///
+/// "C"、"POSIX"（包括 "C.UTF-8"）表示不做本地化，返回 `None`。
+/// 只带地区的中文标签补上书写系统，使 zh_TW / zh_HK / zh_MO 对应繁体中文。
+fn parse_locale(value: &str) -> Option<LanguageIdentifier> {
+    let tag = value.split(['.', '@']).next().unwrap_or(value).trim();
+    if tag.is_empty() || tag.eq_ignore_ascii_case("C") || tag.eq_ignore_ascii_case("POSIX") {
+        return None;
     }
-
-    // 检查 LANGUAGE 环境变量 / Check LANGUAGE environment variable
-    if let Ok(lang) = std::env::var("LANGUAGE") {
-        // LANGUAGE 格式通常是 "zh_CN:en_US"，取第一个语言
-        if let Some(first_lang) = lang.split(':').next() {
-            if first_lang.starts_with("zh") {
-                return "zh".to_string();
-            } else if first_lang.starts_with("en") {
-                return "en".to_string();
+    let mut locale: LanguageIdentifier = tag.replace('_', "-").parse().ok()?;
+    if locale.language.is_empty() {
+        return None;
+    }
+    if locale.language.as_str() == "zh" && locale.script.is_none() {
+        if let Some(region) = locale.region {
+            if matches!(region.as_str(), "TW" | "HK" | "MO") {
+                locale.script = "Hant".parse().ok();
             }
         }
     }
+    Some(locale)
+}
 
-    // 检查系统语言设置（macOS） / Check system language settings (macOS)
-    if let Ok(output) = Command::new("defaults")
+/// 根据环境变量得到按优先级排列的请求语言
+///
+/// 取 LC_ALL、LC_MESSAGES、LANG 中第一个非空值；与 gettext 一致，该值不是 C/POSIX 时，
+/// `LANGUAGE` 中以冒号分隔的语言列表排在它之前。没有设置任何语言环境时返回 `None`。
+fn requested_locales(var: impl Fn(&str) -> Option<String>) -> Option<Vec<LanguageIdentifier>> {
+    let var = |name: &str| var(name).filter(|value| !value.is_empty());
+    let locale = LOCALE_VARIABLES.iter().find_map(|name| var(name))?;
+    let Some(locale) = parse_locale(&locale) else {
+        return Some(Vec::new());
+    };
+    let mut requested: Vec<LanguageIdentifier> = var("LANGUAGE")
+        .unwrap_or_default()
+        .split(':')
+        .filter_map(parse_locale)
+        .collect();
+    requested.push(locale);
+    Some(requested)
+}
+
+/// 系统的请求语言：环境变量未设置时，macOS 上读取系统偏好语言
+fn system_locales() -> Vec<LanguageIdentifier> {
+    requested_locales(|name| std::env::var(name).ok()).unwrap_or_else(preferred_languages)
+}
+
+/// macOS 系统偏好中的语言列表（`defaults read -g AppleLanguages`）
+#[cfg(target_os = "macos")]
+fn preferred_languages() -> Vec<LanguageIdentifier> {
+    let Ok(output) = std::process::Command::new("defaults")
         .args(["read", "-g", "AppleLanguages"])
         .output()
-    {
-        if let Ok(lang_str) = String::from_utf8(output.stdout) {
-            if lang_str.contains("zh") {
-                return "zh".to_string();
-            }
-        }
-    }
+    else {
+        return Vec::new();
+    };
+    // 输出形如 ("zh-Hant-TW", "en-US")，每行一项
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            line.trim()
+                .trim_matches(|c| matches!(c, '(' | ')' | ',' | '"'))
+        })
+        .filter_map(parse_locale)
+        .collect()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn preferred_languages() -> Vec<LanguageIdentifier> {
+    Vec::new()
+}
 
-    // 默认返回英语 / Default to English
-    "en".to_string()
+/// 在内置目录中为请求语言选出最合适的一个，没有可用目录时返回 `None`
+///
+/// 依次尝试每个请求语言：完全匹配，其次是作为范围的目录（"zh" 匹配 "zh-CN"），
+/// 最后忽略地区。更具体的目录排在前面，使 "zh-Hant-TW" 选中 "zh-Hant" 而不是 "zh"。
+fn negotiate(requested: &[LanguageIdentifier]) -> Option<&'static str> {
+    let mut available: Vec<(LanguageIdentifier, &'static str)> = CATALOGS
+        .iter()
+        .filter_map(|(name, _)| Some((name.parse().ok()?, *name)))
+        .collect();
+    available.sort_by_key(|(locale, _)| {
+        std::cmp::Reverse(
+            locale.script.is_some() as usize
+                + locale.region.is_some() as usize
+                + locale.variants().len(),
+        )
+    });
+    let locales: Vec<&LanguageIdentifier> = available.iter().map(|(locale, _)| locale).collect();
+    let matched = filter_matches(requested, &locales, NegotiationStrategy::Lookup);
+    let name = matched.first().and_then(|found| {
+        available
+            .iter()
+            .find(|(locale, _)| locale == **found)
+            .map(|(_, name)| *name)
+    });
+    name
 }
 
-/// 本地化字符串结构 / Localized string structure
+/// 界面语言的协商过程，供 `devtool --print-locale` 排查问题
 #[derive(Debug, Clone)]
-pub struct LocalizedStrings {
-    pub banner: String,
-    pub steps_count: String,
-    pub update_complete: String,
-    pub time_taken: String,
-    pub no_updates: String,
-    pub actions_executed: String,
-    pub already_latest: String,
-}
-
-impl LocalizedStrings {
-    /// 根据语言代码创建本地化字符串
-    pub fn new(lang: &str) -> Self {
-        match lang {
-            "zh" => Self {
-                banner: "开始 devtool 更新：".to_string(),
-                steps_count: "将执行 {} 个步骤：".to_string(),
-                update_complete: "更新完成：".to_string(),
-                time_taken: "耗时".to_string(),
-                no_updates: "无更新应用。".to_string(),
-                actions_executed: "已执行动作：".to_string(),
-                already_latest: "已是最新：".to_string(),
-            },
-            _ => Self {
-                banner: "Starting devtool update: ".to_string(),
-                steps_count: "Will execute {} steps:".to_string(),
-                update_complete: "Update completed: ".to_string(),
-                time_taken: "Time taken".to_string(),
-                no_updates: "No updates applied.".to_string(),
-                actions_executed: "Actions executed: ".to_string(),
-                already_latest: "Already latest: ".to_string(),
-            },
+pub struct LocaleReport {
+    /// 相关环境变量及其值
+    pub variables: Vec<(&'static str, Option<String>)>,
+    /// `--lang` 或配置文件中的设置
+    pub setting: Option<String>,
+    /// 按优先级排列的请求语言
+    pub requested: Vec<String>,
+    /// 最终使用的消息目录
+    pub selected: String,
+}
+
+/// 收集当前的语言协商结果
+pub fn locale_report() -> LocaleReport {
+    let variables = LOCALE_VARIABLES
+        .iter()
+        .chain(["LANGUAGE"].iter())
+        .map(|name| (*name, std::env::var(name).ok()))
+        .collect();
+    let setting = LANGUAGE_OVERRIDE.get().map(|(lang, _)| lang.clone());
+    let requested = match &setting {
+        Some(lang) => parse_locale(lang).into_iter().collect(),
+        None => system_locales(),
+    };
+    LocaleReport {
+        variables,
+        setting,
+        requested: requested.iter().map(|locale| locale.to_string()).collect(),
+        selected: detect_system_language(),
+    }
+}
+
+/// 已内置的语言
+pub fn available_languages() -> Vec<&'static str> {
+    CATALOGS.iter().map(|(lang, _)| *lang).collect()
+}
+
+fn load_bundle(lang: &str, source: &str) -> FluentBundle<FluentResource> {
+    let langid: LanguageIdentifier = lang.parse().unwrap_or_default();
+    let resource = FluentResource::try_new(source.to_string()).unwrap_or_else(|(res, _)| res);
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // 终端输出不需要 Unicode 方向隔离符
+    bundle.set_use_isolating(false);
+    let _ = bundle.add_resource(resource);
+    bundle
+}
+
+fn bundles() -> &'static HashMap<&'static str, FluentBundle<FluentResource>> {
+    BUNDLES.get_or_init(|| {
+        CATALOGS
+            .iter()
+            .map(|(lang, source)| (*lang, load_bundle(lang, source)))
+            .collect()
+    })
+}
+
+/// 查找并格式化一条消息；当前语言缺少该消息时回退到英语，仍找不到则返回消息 ID
+///
+/// 一般通过 [`t!`](crate::t) 宏调用。
+pub fn translate(lang: &str, id: &str, args: Option<&FluentArgs>) -> String {
+    for candidate in [lang, FALLBACK_LANGUAGE] {
+        let Some(bundle) = bundles().get(candidate) else {
+            continue;
+        };
+        if let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) {
+            let mut errors = Vec::new();
+            return bundle
+                .format_pattern(pattern, args, &mut errors)
+                .into_owned();
         }
     }
+    id.to_string()
+}
+
+/// 格式化一条本地化消息
+///
+/// `t!(lang, "update-complete")`，或带参数 `t!(lang, "steps-count", count = 3)`。
+#[macro_export]
+macro_rules! t {
+    ($lang:expr, $id:expr) => {
+        $crate::i18n::translate($lang, $id, None)
+    };
+    ($lang:expr, $id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::translate($lang, $id, Some(&args))
+    }};
+}
+
+/// 本地化的时长，例如 "1h 2m 3s" / "1小时2分3秒"
+pub fn format_duration(lang: &str, secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        crate::t!(
+            lang,
+            "duration-hms",
+            hours = hours,
+            minutes = minutes,
+            seconds = seconds
+        )
+    } else if minutes > 0 {
+        crate::t!(lang, "duration-ms", minutes = minutes, seconds = seconds)
+    } else {
+        crate::t!(lang, "duration-s", seconds = seconds)
+    }
+}
+
+/// 用完整单位表示的时长，供无障碍输出朗读，只保留最大的两个非零单位，
+/// 例如 "6 days, 23 hours" / "6天23小时"
+pub fn spell_duration(lang: &str, secs: u64) -> String {
+    let units = [
+        ("duration-days", secs / 86400),
+        ("duration-hours", secs % 86400 / 3600),
+        ("duration-minutes", secs % 3600 / 60),
+        ("duration-seconds", secs % 60),
+    ];
+    let parts: Vec<String> = units
+        .iter()
+        .skip_while(|(_, count)| *count == 0)
+        .take(2)
+        .filter(|(_, count)| *count > 0)
+        .map(|(id, count)| crate::t!(lang, id, count = *count))
+        .collect();
+    if parts.is_empty() {
+        return crate::t!(lang, "duration-seconds", count = 0);
+    }
+    parts.join(&crate::t!(lang, "duration-separator"))
 }
 
 #[cfg(test)]
@@ -101,20 +280,109 @@ mod tests {
     #[test]
     fn test_detect_system_language() {
         let lang = detect_system_language();
-        assert!(lang == "zh" || lang == "en");
+        assert!(available_languages().contains(&lang.as_str()));
+    }
+
+    fn requested(vars: &[(&str, &str)]) -> Option<Vec<String>> {
+        let var = |name: &str| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        };
+        requested_locales(var).map(|locales| locales.iter().map(|l| l.to_string()).collect())
     }
 
     #[test]
-    fn test_localized_strings_zh() {
-        let strings = LocalizedStrings::new("zh");
-        assert!(strings.banner.contains("开始"));
-        assert!(strings.update_complete.contains("完成"));
+    fn test_requested_locales() {
+        assert_eq!(requested(&[]), None);
+        // LC_ALL 优先于 LANG，空值视为未设置
+        assert_eq!(
+            requested(&[("LANG", "en_US.UTF-8"), ("LC_ALL", "zh_CN.UTF-8")]),
+            Some(vec!["zh-CN".to_string()])
+        );
+        assert_eq!(
+            requested(&[("LC_ALL", ""), ("LC_MESSAGES", "de_DE@euro")]),
+            Some(vec!["de-DE".to_string()])
+        );
+        // LANGUAGE 列表在前，C 语言环境下被忽略
+        assert_eq!(
+            requested(&[("LANG", "en_US.UTF-8"), ("LANGUAGE", "fr:zh_TW")]),
+            Some(vec![
+                "fr".to_string(),
+                "zh-Hant-TW".to_string(),
+                "en-US".to_string()
+            ])
+        );
+        assert_eq!(
+            requested(&[("LANG", "C.UTF-8"), ("LANGUAGE", "zh")]),
+            Some(Vec::new())
+        );
+        assert_eq!(requested(&[("LANG", "POSIX")]), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let pick = |tags: &[&str]| {
+            let locales: Vec<_> = tags.iter().filter_map(|tag| parse_locale(tag)).collect();
+            negotiate(&locales)
+        };
+        assert_eq!(pick(&["zh_TW.UTF-8"]), Some("zh-Hant"));
+        assert_eq!(pick(&["zh-HK"]), Some("zh-Hant"));
+        assert_eq!(pick(&["zh-Hant"]), Some("zh-Hant"));
+        assert_eq!(pick(&["zh_CN.UTF-8"]), Some("zh"));
+        assert_eq!(pick(&["zh-Hans-SG"]), Some("zh"));
+        assert_eq!(pick(&["en_GB"]), Some("en"));
+        assert_eq!(pick(&["EN"]), Some("en"));
+        assert_eq!(pick(&["de_DE", "fr", "zh-TW", "en"]), Some("zh-Hant"));
+        assert_eq!(pick(&["de_DE"]), None);
+        assert_eq!(pick(&["C.UTF-8"]), None);
+    }
+
+    /// 目录中定义的消息 ID（顶格、以 `=` 结尾的标识符行，不含以 `-` 开头的术语）
+    fn message_ids(source: &str) -> Vec<&str> {
+        source
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once('=').map(|(id, _)| id.trim()))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogs_complete() {
+        let (_, english) = CATALOGS
+            .iter()
+            .find(|(lang, _)| *lang == FALLBACK_LANGUAGE)
+            .unwrap();
+        for (lang, source) in CATALOGS {
+            assert!(
+                FluentResource::try_new(source.to_string()).is_ok(),
+                "{} catalog has syntax errors",
+                lang
+            );
+            for id in message_ids(english) {
+                assert!(
+                    bundles()[lang].has_message(id),
+                    "{} catalog is missing {}",
+                    lang,
+                    id
+                );
+            }
+        }
     }
 
     #[test]
-    fn test_localized_strings_en() {
-        let strings = LocalizedStrings::new("en");
-        assert!(strings.banner.contains("Starting"));
-        assert!(strings.update_complete.contains("completed"));
+    fn test_translate() {
+        assert_eq!(t!("en", "steps-count", count = 1), "Will execute 1 step:");
+        assert_eq!(t!("en", "steps-count", count = 3), "Will execute 3 steps:");
+        assert_eq!(t!("zh", "steps-count", count = 3), "将执行 3 个步骤：");
+        assert_eq!(t!("xx", "update-complete"), t!("en", "update-complete"));
+        assert_eq!(t!("en", "no-such-message"), "no-such-message");
+        assert_eq!(format_duration("en", 3723), "1h 2m 3s");
+        assert_eq!(format_duration("zh", 65), "1分5秒");
+        assert_eq!(spell_duration("en", 7 * 86400 - 3600), "6 days, 23 hours");
+        assert_eq!(spell_duration("en", 3601), "1 hour");
+        assert_eq!(spell_duration("en", 0), "0 seconds");
+        assert_eq!(spell_duration("zh", 65), "1分钟5秒");
+        assert_eq!(t!("zh-Hant", "steps-count", count = 3), "將執行 3 個步驟：");
     }
 }
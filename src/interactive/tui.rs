@@ -0,0 +1,172 @@
+// 交互式升级选择界面
+// 基于 ratatui 的全屏列表：勾选、过滤、按工具全选
+
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+
+use super::Selection;
+use crate::commands::pending::PendingUpgrade;
+use crate::t;
+use crate::version::VersionDistance;
+
+fn distance_label(distance: Option<VersionDistance>) -> (String, Color) {
+    match distance {
+        Some(VersionDistance::Major) => ("major".to_string(), Color::Red),
+        Some(VersionDistance::Minor) => ("minor".to_string(), Color::Yellow),
+        Some(VersionDistance::Patch) => ("patch".to_string(), Color::Green),
+        Some(other) => (other.to_string(), Color::Gray),
+        None => ("-".to_string(), Color::Gray),
+    }
+}
+
+fn render(frame: &mut Frame, selection: &Selection, filtering: bool, lang: &str) {
+    let [list_area, filter_area, help_area] = Layout::vertical([
+        Constraint::Min(3),
+        Constraint::Length(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let visible = selection.visible();
+    let rows = visible.iter().map(|&index| {
+        let item = &selection.items()[index];
+        let checkbox = if selection.is_selected(index) {
+            "[x]"
+        } else {
+            "[ ]"
+        };
+        let name = if item.cask {
+            format!("{} (cask)", item.name)
+        } else {
+            item.name.clone()
+        };
+        let (class, color) = distance_label(item.distance());
+        Row::new(vec![
+            checkbox.to_string(),
+            item.tool.display_name().to_string(),
+            name,
+            format!(
+                "{} {} {}",
+                item.current,
+                crate::ui::theme::current().glyphs.arrow,
+                item.latest
+            ),
+            class,
+        ])
+        .style(Style::default().fg(color))
+    });
+
+    let title = format!(
+        " {} ({}/{}) ",
+        t!(lang, "select-title"),
+        selection.selected_count(),
+        selection.items().len()
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Length(9),
+            Constraint::Percentage(40),
+            Constraint::Percentage(40),
+            Constraint::Length(6),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            String::new(),
+            t!(lang, "select-header-tool"),
+            t!(lang, "select-header-name"),
+            t!(lang, "select-header-version"),
+            t!(lang, "select-header-class"),
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::bordered().title(title))
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut state =
+        TableState::default().with_selected(selection.current().map(|_| selection.cursor()));
+    frame.render_stateful_widget(table, list_area, &mut state);
+
+    let filter_style = if filtering {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default()
+    };
+    let filter = Paragraph::new(selection.filter().to_string())
+        .style(filter_style)
+        .block(Block::bordered().title(format!(" / {} ", t!(lang, "select-filter"))));
+    frame.render_widget(filter, filter_area);
+
+    let help = if filtering {
+        t!(lang, "select-help-filter")
+    } else {
+        t!(lang, "select-help")
+    };
+    frame.render_widget(
+        Line::from(help).style(Style::default().fg(Color::DarkGray)),
+        help_area,
+    );
+}
+
+fn run(terminal: &mut DefaultTerminal, selection: &mut Selection, lang: &str) -> Result<bool> {
+    let mut filtering = false;
+    loop {
+        terminal.draw(|frame| render(frame, selection, filtering, lang))?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if filtering {
+            match key.code {
+                KeyCode::Enter => filtering = false,
+                KeyCode::Esc => {
+                    selection.clear_filter();
+                    filtering = false;
+                }
+                KeyCode::Backspace => selection.pop_filter(),
+                KeyCode::Char(c) => selection.push_filter(c),
+                KeyCode::Up => selection.move_up(),
+                KeyCode::Down => selection.move_down(),
+                _ => {}
+            }
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Enter => return Ok(true),
+            KeyCode::Up | KeyCode::Char('k') => selection.move_up(),
+            KeyCode::Down | KeyCode::Char('j') => selection.move_down(),
+            KeyCode::Char(' ') => selection.toggle_current(),
+            KeyCode::Char('a') => {
+                if let Some(index) = selection.current() {
+                    let tool = selection.items()[index].tool.clone();
+                    selection.toggle_tool(&tool);
+                }
+            }
+            KeyCode::Char('/') => filtering = true,
+            _ => {}
+        }
+    }
+}
+
+/// 打开选择界面，返回勾选的项目；用户取消时返回 `None`
+pub fn pick(items: Vec<PendingUpgrade>, lang: &str) -> Result<Option<Vec<PendingUpgrade>>> {
+    let mut selection = Selection::new(items);
+    let mut terminal = ratatui::init();
+    let confirmed = run(&mut terminal, &mut selection, lang);
+    ratatui::restore();
+
+    Ok(confirmed?.then(|| selection.selected_items()))
+}
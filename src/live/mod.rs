@@ -0,0 +1,470 @@
+// 实时运行状态
+// 正在执行的 devtool update 通过运行时目录下的 Unix 套接字公开各工具的状态，
+// 供 `devtool status`、状态栏和编辑器插件读取
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::parallel::Tool;
+use crate::ui::dashboard::{read_tail, running_logfile, step_index, steps, tail_lines};
+use crate::ui::progress::{ProgressDisplay, SimpleProgressState};
+
+/// 状态套接字路径
+///
+/// 优先使用 `DEVTOOL_STATUS_SOCKET`，否则为运行时目录（`XDG_RUNTIME_DIR`）下的 `devtool.sock`；
+/// 没有运行时目录时放在临时目录下按用户名区分的私有目录中
+pub fn socket_path() -> PathBuf {
+    if let Ok(path) = std::env::var("DEVTOOL_STATUS_SOCKET") {
+        return PathBuf::from(path);
+    }
+    match dirs::runtime_dir() {
+        Some(dir) => dir.join("devtool.sock"),
+        None => {
+            let user = std::env::var("USER").unwrap_or_else(|_| "user".to_string());
+            std::env::temp_dir()
+                .join(format!("devtool-{}", user))
+                .join("devtool.sock")
+        }
+    }
+}
+
+/// 工具的执行状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepState {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+impl From<&SimpleProgressState> for StepState {
+    fn from(state: &SimpleProgressState) -> Self {
+        match state {
+            SimpleProgressState::Preparing => StepState::Pending,
+            SimpleProgressState::Executing => StepState::Running,
+            SimpleProgressState::Completed => StepState::Done,
+            SimpleProgressState::Failed => StepState::Failed,
+        }
+    }
+}
+
+/// 单个工具的运行状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolStatus {
+    pub tool: Tool,
+    pub state: StepState,
+    /// 当前步骤名称（仅运行中）
+    pub step: Option<String>,
+    /// 当前步骤序号，从 1 开始（仅运行中）
+    pub step_index: Option<usize>,
+    pub step_count: usize,
+    pub elapsed_secs: Option<u64>,
+    /// 当前步骤日志的最后一行（仅运行中）
+    pub current_line: Option<String>,
+    /// 当前步骤的日志文件（仅运行中），供 `devtool logs --follow` 读取
+    #[serde(default)]
+    pub log: Option<PathBuf>,
+}
+
+/// 一次更新的运行状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunStatus {
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    pub elapsed_secs: u64,
+    pub tools: Vec<ToolStatus>,
+}
+
+impl RunStatus {
+    /// 单行摘要，适合状态栏显示，例如 `devtool: Homebrew upgrade 2/3`
+    pub fn summary(&self) -> String {
+        let running: Vec<String> = self
+            .tools
+            .iter()
+            .filter(|t| t.state == StepState::Running)
+            .map(|t| match (&t.step, t.step_index) {
+                (Some(step), Some(index)) => format!(
+                    "{} {} {}/{}",
+                    t.tool.display_name(),
+                    step,
+                    index,
+                    t.step_count
+                ),
+                _ => t.tool.display_name().to_string(),
+            })
+            .collect();
+        if !running.is_empty() {
+            return format!("devtool: {}", running.join(", "));
+        }
+        let finished = self
+            .tools
+            .iter()
+            .filter(|t| matches!(t.state, StepState::Done | StepState::Failed))
+            .count();
+        format!("devtool: {}/{}", finished, self.tools.len())
+    }
+}
+
+/// `devtool status --format json` 的输出
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    pub running: bool,
+    #[serde(flatten)]
+    pub run: Option<RunStatus>,
+}
+
+impl StatusReport {
+    pub fn new(run: Option<RunStatus>) -> Self {
+        Self {
+            running: run.is_some(),
+            run,
+        }
+    }
+}
+
+/// 读取正在运行的更新状态；没有正在运行的更新时返回 `None`
+pub fn query(path: &Path) -> Result<Option<RunStatus>> {
+    let Ok(stream) = UnixStream::connect(path) else {
+        return Ok(None);
+    };
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    if line.trim().is_empty() {
+        // 服务端正在退出
+        return Ok(None);
+    }
+    let status = serde_json::from_str(&line).context("parse run status")?;
+    Ok(Some(status))
+}
+
+/// 状态套接字服务：每个连接写入一行 JSON 后关闭
+pub struct StatusServer {
+    path: PathBuf,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl StatusServer {
+    /// 在 `path` 上监听；已有其他 devtool 进程在监听时返回 `None`
+    pub fn start<F>(path: &Path, status: F) -> Result<Option<Self>>
+    where
+        F: Fn() -> RunStatus + Send + 'static,
+    {
+        use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if !metadata.file_type().is_socket() {
+                anyhow::bail!("refusing to replace {:?}: not a socket", path);
+            }
+            if UnixStream::connect(path).is_ok() {
+                return Ok(None);
+            }
+            // 上次异常退出残留的套接字
+            let _ = std::fs::remove_file(path);
+        }
+        if let Some(parent) = path.parent().filter(|parent| !parent.exists()) {
+            // 新建的目录只允许当前用户访问
+            std::fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(parent)?;
+        }
+        let listener =
+            UnixListener::bind(path).with_context(|| format!("bind status socket {:?}", path))?;
+        // 只允许当前用户连接
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+        let handle = std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop_flag.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(mut stream) = stream {
+                    if let Ok(json) = serde_json::to_string(&status()) {
+                        let _ = writeln!(stream, "{}", json);
+                    }
+                }
+            }
+        });
+
+        Ok(Some(Self {
+            path: path.to_path_buf(),
+            stop,
+            handle: Some(handle),
+        }))
+    }
+
+    /// 停止监听并删除套接字文件
+    pub fn stop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            self.stop.store(true, Ordering::SeqCst);
+            // 连接一次以唤醒阻塞的 accept
+            let _ = UnixStream::connect(&self.path);
+            let _ = handle.join();
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+impl Drop for StatusServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// 单个工具的执行记录
+#[derive(Debug, Clone)]
+struct TrackedTool {
+    tool: Tool,
+    state: SimpleProgressState,
+    started: Option<Instant>,
+    elapsed: Option<Duration>,
+    logfile: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+struct Tracker {
+    started_at: DateTime<Utc>,
+    started: Instant,
+    tmpdir: PathBuf,
+    tools: Vec<TrackedTool>,
+}
+
+impl Tracker {
+    fn snapshot(&mut self) -> RunStatus {
+        let tmpdir = self.tmpdir.clone();
+        let tools = self
+            .tools
+            .iter_mut()
+            .map(|t| {
+                if let Some(log) = running_logfile(&t.tool, &tmpdir) {
+                    t.logfile = Some(log);
+                }
+                let running = t.state == SimpleProgressState::Executing;
+                let index = running.then(|| {
+                    t.logfile
+                        .as_deref()
+                        .map(|log| step_index(&t.tool, log))
+                        .unwrap_or(0)
+                });
+                ToolStatus {
+                    tool: t.tool.clone(),
+                    state: (&t.state).into(),
+                    step: index.map(|i| steps(&t.tool)[i].to_string()),
+                    step_index: index.map(|i| i + 1),
+                    step_count: steps(&t.tool).len(),
+                    elapsed_secs: t
+                        .elapsed
+                        .or_else(|| t.started.map(|s| s.elapsed()))
+                        .map(|d| d.as_secs()),
+                    current_line: if running {
+                        t.logfile
+                            .as_deref()
+                            .and_then(|log| tail_lines(&read_tail(log), 1).pop())
+                    } else {
+                        None
+                    },
+                    log: if running { t.logfile.clone() } else { None },
+                }
+            })
+            .collect();
+        RunStatus {
+            pid: std::process::id(),
+            started_at: self.started_at,
+            elapsed_secs: self.started.elapsed().as_secs(),
+            tools,
+        }
+    }
+}
+
+/// 在进度显示的基础上通过状态套接字公开运行状态
+pub struct LiveStatus {
+    inner: Box<dyn ProgressDisplay>,
+    tracker: Arc<Mutex<Tracker>>,
+    server: Option<StatusServer>,
+}
+
+impl LiveStatus {
+    pub fn new(inner: Box<dyn ProgressDisplay>, tmpdir: &Path) -> Self {
+        Self {
+            inner,
+            tracker: Arc::new(Mutex::new(Tracker {
+                started_at: Utc::now(),
+                started: Instant::now(),
+                tmpdir: tmpdir.to_path_buf(),
+                tools: Vec::new(),
+            })),
+            server: None,
+        }
+    }
+}
+
+impl ProgressDisplay for LiveStatus {
+    fn create_progress_bars(&mut self, tools: &[Tool]) {
+        if let Ok(mut tracker) = self.tracker.lock() {
+            tracker.tools = tools
+                .iter()
+                .map(|tool| TrackedTool {
+                    tool: tool.clone(),
+                    state: SimpleProgressState::Preparing,
+                    started: None,
+                    elapsed: None,
+                    logfile: None,
+                })
+                .collect();
+        }
+        if self.server.is_none() {
+            let tracker = self.tracker.clone();
+            // 状态套接字只是辅助功能，无法监听时不影响更新
+            self.server = StatusServer::start(&socket_path(), move || {
+                tracker
+                    .lock()
+                    .map(|mut t| t.snapshot())
+                    .unwrap_or_else(|poisoned| poisoned.into_inner().snapshot())
+            })
+            .ok()
+            .flatten();
+        }
+        self.inner.create_progress_bars(tools);
+    }
+
+    fn update_state(&mut self, tool: &Tool, new_state: SimpleProgressState) {
+        if let Ok(mut tracker) = self.tracker.lock() {
+            if let Some(t) = tracker.tools.iter_mut().find(|t| &t.tool == tool) {
+                match new_state {
+                    SimpleProgressState::Executing if t.started.is_none() => {
+                        t.started = Some(Instant::now());
+                    }
+                    SimpleProgressState::Completed | SimpleProgressState::Failed => {
+                        t.elapsed = t.started.map(|s| s.elapsed());
+                    }
+                    _ => {}
+                }
+                t.state = new_state.clone();
+            }
+        }
+        self.inner.update_state(tool, new_state);
+    }
+
+    fn finalize_all(&mut self) {
+        if let Some(mut server) = self.server.take() {
+            server.stop();
+        }
+        self.inner.finalize_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn tool_status(tool: Tool, state: StepState, step: Option<(&str, usize)>) -> ToolStatus {
+        ToolStatus {
+            step_count: steps(&tool).len(),
+            tool,
+            state,
+            step: step.map(|(name, _)| name.to_string()),
+            step_index: step.map(|(_, index)| index),
+            elapsed_secs: None,
+            current_line: None,
+            log: None,
+        }
+    }
+
+    fn run_status(tools: Vec<ToolStatus>) -> RunStatus {
+        RunStatus {
+            pid: 42,
+            started_at: Utc::now(),
+            elapsed_secs: 5,
+            tools,
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        let run = run_status(vec![
+            tool_status(Tool::Homebrew, StepState::Running, Some(("upgrade", 2))),
+            tool_status(Tool::Rustup, StepState::Done, None),
+        ]);
+        assert_eq!(run.summary(), "devtool: Homebrew upgrade 2/3");
+
+        let run = run_status(vec![
+            tool_status(Tool::Homebrew, StepState::Failed, None),
+            tool_status(Tool::Rustup, StepState::Done, None),
+            tool_status(Tool::Mise, StepState::Pending, None),
+        ]);
+        assert_eq!(run.summary(), "devtool: 2/3");
+    }
+
+    #[test]
+    fn test_server_query_roundtrip() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("devtool.sock");
+        assert!(query(&path).unwrap().is_none());
+
+        let expected = run_status(vec![tool_status(
+            Tool::Mise,
+            StepState::Running,
+            Some(("up", 1)),
+        )]);
+        let served = expected.clone();
+        let mut server = StatusServer::start(&path, move || served.clone())
+            .unwrap()
+            .unwrap();
+        assert_eq!(query(&path).unwrap(), Some(expected.clone()));
+
+        // 同一路径只允许一个服务端
+        assert!(StatusServer::start(&path, move || expected.clone())
+            .unwrap()
+            .is_none());
+
+        server.stop();
+        assert!(!path.exists());
+        assert!(query(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_server_socket_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("devtool-user").join("devtool.sock");
+        let mut server = StatusServer::start(&path, || run_status(Vec::new()))
+            .unwrap()
+            .unwrap();
+        let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(path.parent().unwrap()), 0o700);
+        assert_eq!(mode(&path), 0o600);
+        server.stop();
+
+        // 不会删除同名的普通文件
+        std::fs::write(&path, "data").unwrap();
+        assert!(StatusServer::start(&path, || run_status(Vec::new())).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "data");
+    }
+
+    #[test]
+    fn test_report_json() {
+        let json = serde_json::to_value(StatusReport::new(None)).unwrap();
+        assert_eq!(json, serde_json::json!({ "running": false }));
+
+        let run = run_status(vec![tool_status(Tool::Rustup, StepState::Pending, None)]);
+        let json = serde_json::to_value(StatusReport::new(Some(run))).unwrap();
+        assert_eq!(json["running"], true);
+        assert_eq!(json["pid"], 42);
+        assert_eq!(json["tools"][0]["state"], "pending");
+        assert_eq!(json["tools"][0]["tool"], "rustup");
+    }
+}
@@ -0,0 +1,191 @@
+// 单实例锁
+// 会修改工具链的命令对运行时目录（或缓存目录）下的锁文件加独占的建议锁（flock），
+// 防止定时运行和手动运行同时执行 brew upgrade 等操作；锁文件从不删除，
+// 其中记录的进程 ID 和开始时间只用于向等待者报告持有者
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, TryLockError};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::utils::get_cache_dir;
+
+/// 锁被占用但持有者信息还没写入时重读的次数和间隔
+const INFO_RETRIES: usize = 10;
+const INFO_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 锁文件路径：`DEVTOOL_LOCK_FILE`，否则为运行时目录下的 devtool.lock，没有运行时目录时放在缓存目录
+pub fn lock_path() -> PathBuf {
+    if let Ok(path) = std::env::var("DEVTOOL_LOCK_FILE") {
+        return PathBuf::from(path);
+    }
+    dirs::runtime_dir()
+        .unwrap_or_else(get_cache_dir)
+        .join("devtool.lock")
+}
+
+/// 锁文件内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockInfo {
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    /// 持有锁的命令行
+    pub command: String,
+}
+
+impl LockInfo {
+    fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            started_at: Utc::now(),
+            command: std::env::args().collect::<Vec<_>>().join(" "),
+        }
+    }
+}
+
+/// 已获取的锁，释放时清空持有者信息并解锁（进程退出时系统也会解锁）
+#[derive(Debug)]
+pub struct RunLock {
+    file: File,
+}
+
+impl Drop for RunLock {
+    fn drop(&mut self) {
+        let _ = self.file.set_len(0);
+        let _ = self.file.unlock();
+    }
+}
+
+/// 获取结果
+#[derive(Debug)]
+pub enum Acquire {
+    Acquired(RunLock),
+    /// 已被其他进程持有；持有者信息读不到时为 `None`
+    Held(Option<LockInfo>),
+}
+
+/// 读取锁文件中的持有者信息
+fn read_info(path: &Path) -> Option<LockInfo> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// 尝试获取锁；锁由内核维护，持有进程退出后自动释放，锁文件中残留的信息会被覆盖
+pub fn try_acquire(path: &Path) -> Result<Acquire> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .with_context(|| format!("open lock {:?}", path))?;
+    for _ in 0..INFO_RETRIES {
+        match file.try_lock() {
+            Ok(()) => {
+                file.set_len(0)?;
+                file.seek(SeekFrom::Start(0))?;
+                file.write_all(serde_json::to_string(&LockInfo::current())?.as_bytes())
+                    .with_context(|| format!("write lock {:?}", path))?;
+                return Ok(Acquire::Acquired(RunLock { file }));
+            }
+            Err(TryLockError::WouldBlock) => {
+                // 持有者刚加锁时可能还没写入信息，稍后重读
+                if let Some(info) = read_info(path) {
+                    return Ok(Acquire::Held(Some(info)));
+                }
+                std::thread::sleep(INFO_RETRY_INTERVAL);
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(e).with_context(|| format!("lock {:?}", path))
+            }
+        }
+    }
+    Ok(Acquire::Held(None))
+}
+
+/// 等待直到获取锁，持有者第一次出现或变化时调用 `on_wait`
+pub fn acquire_wait(path: &Path, mut on_wait: impl FnMut(Option<&LockInfo>)) -> Result<RunLock> {
+    let mut notified: Option<Option<u32>> = None;
+    loop {
+        match try_acquire(path)? {
+            Acquire::Acquired(lock) => return Ok(lock),
+            Acquire::Held(info) => {
+                let pid = info.as_ref().map(|info| info.pid);
+                if notified != Some(pid) {
+                    on_wait(info.as_ref());
+                    notified = Some(pid);
+                }
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_lock_is_exclusive_and_released() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("devtool.lock");
+
+        let lock = match try_acquire(&path).unwrap() {
+            Acquire::Acquired(lock) => lock,
+            Acquire::Held(_) => panic!("expected to acquire the lock"),
+        };
+        let info = read_info(&path).unwrap();
+        assert_eq!(info.pid, std::process::id());
+
+        // flock 作用于打开的文件，同一进程再次打开也会被拒绝
+        match try_acquire(&path).unwrap() {
+            Acquire::Held(holder) => assert_eq!(holder, Some(info)),
+            Acquire::Acquired(_) => panic!("lock should be held"),
+        }
+
+        // 释放后锁文件保留，持有者信息清空
+        drop(lock);
+        assert!(path.exists());
+        assert!(read_info(&path).is_none());
+        assert!(matches!(try_acquire(&path).unwrap(), Acquire::Acquired(_)));
+    }
+
+    #[test]
+    fn test_stale_info_is_replaced() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("devtool.lock");
+
+        // 已退出的进程留下的信息不影响加锁
+        let stale = LockInfo {
+            pid: 1,
+            started_at: Utc::now(),
+            command: "devtool update".to_string(),
+        };
+        std::fs::write(&path, serde_json::to_string(&stale).unwrap()).unwrap();
+        let _lock = match try_acquire(&path).unwrap() {
+            Acquire::Acquired(lock) => lock,
+            Acquire::Held(_) => panic!("stale info should not block"),
+        };
+        assert_eq!(read_info(&path).unwrap().pid, std::process::id());
+    }
+
+    #[test]
+    fn test_held_without_info() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("devtool.lock");
+
+        // 另一个持有者已加锁但还没写入信息
+        let holder = File::create(&path).unwrap();
+        holder.lock().unwrap();
+        assert!(matches!(try_acquire(&path).unwrap(), Acquire::Held(None)));
+        holder.unlock().unwrap();
+        assert!(matches!(try_acquire(&path).unwrap(), Acquire::Acquired(_)));
+    }
+}
@@ -0,0 +1,827 @@
+// 运行日志
+// 每次 devtool update 的所有步骤把日志写入缓存目录的 runs/<运行>/，结束时写入 manifest.json 索引；
+// 失败的运行总是保留，成功的运行在 --keep-logs 或定时任务时保留，更新开始时按保留策略清理旧运行。
+// 这里还负责列出运行和步骤、按编号定位、跨运行搜索，以及跟随正在运行的步骤日志
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::io::{IsTerminal, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::LogsConfig;
+use crate::history::RunRecord;
+use crate::live::{self, StepState};
+use crate::parallel::Tool;
+use crate::t;
+use crate::ui::dashboard::{log_prefix, read_tail, tail_lines};
+use crate::utils::{parse_duration, parse_size, shell_quote};
+
+/// 运行目录中的索引文件
+pub const MANIFEST: &str = "manifest.json";
+/// 默认最多保留的运行数量
+const DEFAULT_KEEP_RUNS: usize = 20;
+/// 默认的最长保留时间
+const DEFAULT_MAX_AGE: &str = "30d";
+/// 跟随日志时开始输出的行数
+const FOLLOW_LINES: usize = 10;
+/// 跟随日志时的轮询间隔
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 全部工具，按列出日志的顺序
+pub const TOOLS: [Tool; 3] = [Tool::Homebrew, Tool::Rustup, Tool::Mise];
+
+/// 保存运行日志的目录
+pub fn runs_dir(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("runs")
+}
+
+/// 工具在日志中的名称
+pub fn tool_dir_name(tool: &Tool) -> &'static str {
+    match tool {
+        Tool::Homebrew => "homebrew",
+        Tool::Rustup => "rustup",
+        Tool::Mise => "mise",
+    }
+}
+
+/// 根据文件名判断日志属于哪个工具，通知等运行级别的日志返回 `None`
+pub fn file_tool(file_name: &str) -> Option<Tool> {
+    // 不带工具前缀的中间文件
+    if file_name == "outdated_packages.json" {
+        return Some(Tool::Homebrew);
+    }
+    if file_name.starts_with("toolchain_versions") {
+        return Some(Tool::Rustup);
+    }
+    TOOLS
+        .iter()
+        .find(|tool| file_name.starts_with(log_prefix(tool)))
+        .cloned()
+}
+
+/// 日志文件对应的步骤名称：去掉工具前缀和 .log 后缀，例如 brew_upgrade.log 为 upgrade
+pub fn step_name(tool: Option<&Tool>, file_name: &str) -> String {
+    let name = tool
+        .and_then(|tool| file_name.strip_prefix(log_prefix(tool)))
+        .unwrap_or(file_name);
+    name.strip_suffix(".log").unwrap_or(name).to_string()
+}
+
+/// manifest.json 中的工具结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestTool {
+    pub tool: Tool,
+    pub success: bool,
+}
+
+/// manifest.json 中的日志文件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    pub file: String,
+    pub tool: Option<Tool>,
+    pub step: String,
+    pub size: u64,
+}
+
+/// 运行目录的索引
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub id: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub scheduled: bool,
+    pub success: bool,
+    pub tools: Vec<ManifestTool>,
+    pub files: Vec<ManifestFile>,
+}
+
+impl Manifest {
+    fn build(id: &str, dir: &Path, record: &RunRecord) -> Self {
+        let files = scan_steps(dir)
+            .into_iter()
+            .map(|step| ManifestFile {
+                file: step.file_name(),
+                tool: step.tool,
+                step: step.name,
+                size: step.size,
+            })
+            .collect();
+        Self {
+            id: id.to_string(),
+            started_at: record.started_at,
+            finished_at: record.finished_at,
+            scheduled: record.scheduled,
+            success: record.success(),
+            tools: record
+                .tools
+                .iter()
+                .map(|t| ManifestTool {
+                    tool: t.tool.clone(),
+                    success: t.success,
+                })
+                .collect(),
+            files,
+        }
+    }
+
+    /// 读取运行目录的索引，不存在或无法解析时返回 `None`
+    pub fn load(dir: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(dir.join(MANIFEST)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+}
+
+/// 本次运行的日志目录，所有步骤的日志都写在这里
+#[derive(Debug)]
+pub struct RunDir {
+    id: String,
+    path: PathBuf,
+}
+
+impl RunDir {
+    /// 在缓存目录下创建运行目录，目录名为开始时间的 Unix 时间戳，同一秒内的运行加序号
+    pub fn create(cache_dir: &Path) -> Result<Self> {
+        let runs = runs_dir(cache_dir);
+        std::fs::create_dir_all(&runs).with_context(|| format!("create {:?}", runs))?;
+        let timestamp = Utc::now().timestamp();
+        let mut n = 0;
+        loop {
+            let id = if n == 0 {
+                timestamp.to_string()
+            } else {
+                format!("{}-{}", timestamp, n)
+            };
+            let path = runs.join(&id);
+            match std::fs::create_dir(&path) {
+                Ok(()) => return Ok(Self { id, path }),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => n += 1,
+                Err(e) => return Err(e).with_context(|| format!("create {:?}", path)),
+            }
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 结束运行：失败或 `keep` 时写入 manifest.json 并更新 latest 链接，返回保留的目录；
+    /// 否则删除运行目录
+    pub fn finish(self, record: &RunRecord, keep: bool) -> Result<Option<PathBuf>> {
+        if !keep && record.success() {
+            self.discard();
+            return Ok(None);
+        }
+        let manifest = Manifest::build(&self.id, &self.path, record);
+        std::fs::write(
+            self.path.join(MANIFEST),
+            serde_json::to_string_pretty(&manifest)?,
+        )?;
+        if let Some(runs) = self.path.parent() {
+            let latest = runs.join("latest");
+            let _ = std::fs::remove_file(&latest);
+            std::os::unix::fs::symlink(&self.id, &latest).ok();
+        }
+        Ok(Some(self.path))
+    }
+
+    /// 删除运行目录（模拟执行或无需保留的成功运行）
+    pub fn discard(self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// 一次保存的运行
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRun {
+    /// 运行编号，1 为最近一次
+    pub number: usize,
+    /// 运行目录名
+    pub id: String,
+    pub dir: PathBuf,
+    pub started_at: DateTime<Utc>,
+    /// 运行中断时没有索引
+    pub manifest: Option<Manifest>,
+}
+
+impl LogRun {
+    /// 运行目录名开头的时间戳
+    pub fn timestamp(&self) -> i64 {
+        self.started_at.timestamp()
+    }
+
+    /// 运行是否包含该工具
+    pub fn includes(&self, tool: &Tool) -> bool {
+        match &self.manifest {
+            Some(manifest) => manifest.tools.iter().any(|t| &t.tool == tool),
+            None => step_logs(self)
+                .iter()
+                .any(|s| s.tool.as_ref() == Some(tool)),
+        }
+    }
+
+    /// 运行目录中所有文件的总大小
+    pub fn size(&self) -> u64 {
+        step_logs(self).iter().map(|s| s.size).sum()
+    }
+}
+
+/// 解析运行目录名 `<时间戳>` 或 `<时间戳>-<序号>`
+fn parse_run_id(id: &str) -> Option<(i64, u32)> {
+    let (timestamp, n) = match id.split_once('-') {
+        Some((timestamp, n)) => (timestamp, n.parse().ok()?),
+        None => (id, 0),
+    };
+    Some((timestamp.parse().ok()?, n))
+}
+
+/// 列出保存的运行，最近的在前；跳过 latest 链接和其他无关条目
+pub fn list_runs(cache_dir: &Path) -> Vec<LogRun> {
+    let Ok(entries) = std::fs::read_dir(runs_dir(cache_dir)) else {
+        return Vec::new();
+    };
+    let mut runs: Vec<((i64, u32), String, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .filter_map(|entry| {
+            let id = entry.file_name().to_str()?.to_string();
+            Some((parse_run_id(&id)?, id, entry.path()))
+        })
+        .collect();
+    runs.sort_by_key(|(key, _, _)| std::cmp::Reverse(*key));
+    runs.into_iter()
+        .enumerate()
+        .map(|(i, ((timestamp, _), id, dir))| {
+            let manifest = Manifest::load(&dir);
+            LogRun {
+                number: i + 1,
+                started_at: manifest
+                    .as_ref()
+                    .map(|m| m.started_at)
+                    .or_else(|| DateTime::from_timestamp(timestamp, 0))
+                    .unwrap_or_default(),
+                id,
+                dir,
+                manifest,
+            }
+        })
+        .collect()
+}
+
+/// 按编号（1 为最近一次）或时间戳查找运行
+pub fn find_run(runs: &[LogRun], selector: u64) -> Option<&LogRun> {
+    runs.iter()
+        .find(|run| run.number as u64 == selector)
+        .or_else(|| runs.iter().find(|run| run.timestamp() as u64 == selector))
+}
+
+/// 运行中的一个步骤日志
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepLog {
+    pub tool: Option<Tool>,
+    /// 步骤名称，见 [`step_name`]
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+impl StepLog {
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// 带工具名的步骤名称，例如 `homebrew/upgrade`
+    pub fn label(&self) -> String {
+        match &self.tool {
+            Some(tool) => format!("{}/{}", tool_dir_name(tool), self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// 运行目录中的日志文件（不含索引），按工具和名称排序
+fn scan_steps(dir: &Path) -> Vec<StepLog> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut steps: Vec<StepLog> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            let file_name = entry.file_name().to_str()?.to_string();
+            if file_name == MANIFEST {
+                return None;
+            }
+            let tool = file_tool(&file_name);
+            Some(StepLog {
+                name: step_name(tool.as_ref(), &file_name),
+                tool,
+                path: entry.path(),
+                size: metadata.len(),
+            })
+        })
+        .collect();
+    let order = |tool: &Option<Tool>| {
+        tool.as_ref()
+            .and_then(|tool| TOOLS.iter().position(|t| t == tool))
+            .unwrap_or(TOOLS.len())
+    };
+    steps.sort_by(|a, b| (order(&a.tool), &a.name).cmp(&(order(&b.tool), &b.name)));
+    steps
+}
+
+/// 运行的步骤日志
+pub fn step_logs(run: &LogRun) -> Vec<StepLog> {
+    scan_steps(&run.dir)
+}
+
+/// 按步骤名称、带工具名的名称或完整文件名查找步骤日志；名称对应多个工具的步骤时返回 `None`
+pub fn find_step<'a>(steps: &'a [StepLog], name: &str) -> Option<&'a StepLog> {
+    if let Some(step) = steps
+        .iter()
+        .find(|step| step.label() == name || step.file_name() == name)
+    {
+        return Some(step);
+    }
+    let mut matches = steps.iter().filter(|step| step.name == name);
+    match (matches.next(), matches.next()) {
+        (Some(step), None) => Some(step),
+        _ => None,
+    }
+}
+
+/// 格式化文件大小，例如 `12.3 KB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// 运行日志保留策略
+#[derive(Debug, Clone, PartialEq)]
+pub struct Retention {
+    pub keep_runs: Option<usize>,
+    pub max_age: Option<chrono::Duration>,
+    pub max_size: Option<u64>,
+}
+
+impl Retention {
+    /// 按配置构造保留策略，未配置时最多保留 20 次运行、30 天
+    pub fn from_config(config: &LogsConfig) -> Result<Self> {
+        let max_age = config.max_age.as_deref().unwrap_or(DEFAULT_MAX_AGE);
+        Ok(Self {
+            keep_runs: Some(config.keep_runs.unwrap_or(DEFAULT_KEEP_RUNS)).filter(|n| *n > 0),
+            max_age: Some(parse_duration(max_age)?),
+            max_size: config.max_size.as_deref().map(parse_size).transpose()?,
+        })
+    }
+
+    /// 需要删除的运行（最近的在前）：超出数量、超过保留时间，或累计大小超出上限；
+    /// 最近一次运行总是保留
+    fn expired<'a>(&self, runs: &'a [LogRun], now: DateTime<Utc>) -> Vec<&'a LogRun> {
+        let mut total = 0;
+        runs.iter()
+            .enumerate()
+            .filter(|(i, run)| {
+                if *i > 0
+                    && (self.keep_runs.is_some_and(|n| *i >= n)
+                        || self.max_age.is_some_and(|age| now - run.started_at > age))
+                {
+                    return true;
+                }
+                total += run.size();
+                *i > 0 && self.max_size.is_some_and(|size| total > size)
+            })
+            .map(|(_, run)| run)
+            .collect()
+    }
+
+    /// 删除超出保留策略的运行，返回删除的运行目录
+    pub fn prune(&self, cache_dir: &Path, now: DateTime<Utc>) -> Result<Vec<PathBuf>> {
+        let runs = list_runs(cache_dir);
+        let mut removed = Vec::new();
+        for run in self.expired(&runs, now) {
+            std::fs::remove_dir_all(&run.dir).with_context(|| format!("remove {:?}", run.dir))?;
+            removed.push(run.dir.clone());
+        }
+        // latest 指向被删除的运行时移除链接
+        let latest = runs_dir(cache_dir).join("latest");
+        if latest.is_symlink() && !latest.exists() {
+            let _ = std::fs::remove_file(&latest);
+        }
+        Ok(removed)
+    }
+}
+
+/// 搜索结果中的一行
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrepMatch {
+    pub run: usize,
+    /// 带工具名的步骤名称
+    pub step: String,
+    pub line_number: usize,
+    pub line: String,
+}
+
+impl std::fmt::Display for GrepMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "#{} {}:{}: {}",
+            self.run, self.step, self.line_number, self.line
+        )
+    }
+}
+
+/// 在运行的步骤日志中搜索，可限定工具和步骤
+pub fn grep(
+    runs: &[LogRun],
+    tool: Option<&Tool>,
+    step: Option<&str>,
+    pattern: &Regex,
+) -> Vec<GrepMatch> {
+    let mut matches = Vec::new();
+    for run in runs {
+        let steps: Vec<StepLog> = step_logs(run)
+            .into_iter()
+            .filter(|log| tool.is_none_or(|tool| log.tool.as_ref() == Some(tool)))
+            .collect();
+        let selected: Vec<&StepLog> = match step {
+            Some(name) => find_step(&steps, name).into_iter().collect(),
+            None => steps.iter().collect(),
+        };
+        for log in selected {
+            let Ok(bytes) = std::fs::read(&log.path) else {
+                continue;
+            };
+            let content = String::from_utf8_lossy(&bytes);
+            for (i, line) in content.lines().enumerate() {
+                if pattern.is_match(line) {
+                    matches.push(GrepMatch {
+                        run: run.number,
+                        step: log.label(),
+                        line_number: i + 1,
+                        line: line.trim_end_matches('\r').to_string(),
+                    });
+                }
+            }
+        }
+    }
+    matches
+}
+
+/// 用 `$PAGER`（默认 less）打开日志；标准输出不是终端时直接输出内容
+pub fn open_in_pager(path: &Path) -> Result<()> {
+    if std::io::stdout().is_terminal() {
+        let pager = std::env::var("PAGER")
+            .ok()
+            .filter(|pager| !pager.trim().is_empty())
+            .unwrap_or_else(|| "less".to_string());
+        let command = format!("{} {}", pager, shell_quote(&path.to_string_lossy()));
+        if std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .status()
+            .is_ok()
+        {
+            return Ok(());
+        }
+    }
+    let mut file = std::fs::File::open(path).with_context(|| format!("open log {:?}", path))?;
+    std::io::copy(&mut file, &mut std::io::stdout().lock())?;
+    Ok(())
+}
+
+/// 跟随正在运行的步骤日志，步骤切换时自动切换文件，运行（或指定工具）结束时返回；
+/// 没有正在运行的更新时返回 `false`
+pub fn follow(socket: &Path, tool: Option<&Tool>, lang: &str) -> Result<bool> {
+    let Some(mut status) = live::query(socket)? else {
+        return Ok(false);
+    };
+    let mut current: Option<(PathBuf, u64)> = None;
+    let mut out = std::io::stdout();
+    loop {
+        let tools: Vec<_> = status
+            .tools
+            .iter()
+            .filter(|t| tool.is_none_or(|wanted| &t.tool == wanted))
+            .collect();
+        let running = tools
+            .iter()
+            .find(|t| t.state == StepState::Running && t.log.is_some());
+        if let Some(running) = running {
+            let log = running.log.clone().unwrap_or_default();
+            if current.as_ref().map(|(path, _)| path) != Some(&log) {
+                if let Some((path, offset)) = &current {
+                    copy_from(path, *offset, &mut out)?;
+                }
+                println!(
+                    "{}",
+                    t!(
+                        lang,
+                        "logs-follow-step",
+                        tool = running.tool.display_name(),
+                        step = running.step.clone().unwrap_or_default(),
+                        path = log.display().to_string()
+                    )
+                );
+                for line in tail_lines(&read_tail(&log), FOLLOW_LINES) {
+                    println!("{}", line);
+                }
+                let len = std::fs::metadata(&log).map(|m| m.len()).unwrap_or(0);
+                current = Some((log, len));
+            }
+        } else if !tools.is_empty()
+            && tools
+                .iter()
+                .all(|t| matches!(t.state, StepState::Done | StepState::Failed))
+        {
+            break;
+        }
+
+        if let Some((path, offset)) = &mut current {
+            *offset = copy_from(path, *offset, &mut out)?;
+        }
+        std::thread::sleep(FOLLOW_INTERVAL);
+        match live::query(socket)? {
+            Some(next) => status = next,
+            None => break,
+        }
+    }
+    if let Some((path, offset)) = &current {
+        copy_from(path, *offset, &mut out)?;
+    }
+    Ok(true)
+}
+
+/// 输出文件从 `offset` 开始新增的内容，返回新的偏移量
+fn copy_from(path: &Path, offset: u64, out: &mut impl Write) -> Result<u64> {
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return Ok(offset);
+    };
+    let len = file.metadata()?.len();
+    // 文件被截断时从头开始
+    let offset = if len < offset { 0 } else { offset };
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    out.write_all(&buf)?;
+    out.flush()?;
+    Ok(offset + buf.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::ToolResult;
+    use tempfile::tempdir;
+
+    fn make_run(cache_dir: &Path, id: &str, logs: &[(&str, &str)]) -> PathBuf {
+        let dir = runs_dir(cache_dir).join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, content) in logs {
+            std::fs::write(dir.join(name), content).unwrap();
+        }
+        dir
+    }
+
+    fn record(success: bool) -> RunRecord {
+        RunRecord {
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+            scheduled: false,
+            tools: vec![ToolResult {
+                tool: Tool::Homebrew,
+                success,
+                output: String::new(),
+                upgrades: Vec::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_run_dir_finish() {
+        let tmp = tempdir().unwrap();
+
+        // 成功且不要求保留时删除
+        let run = RunDir::create(tmp.path()).unwrap();
+        let path = run.path().to_path_buf();
+        std::fs::write(path.join("brew_update.log"), "ok\n").unwrap();
+        assert_eq!(run.finish(&record(true), false).unwrap(), None);
+        assert!(!path.exists());
+
+        // 失败的运行总是保留，并写入索引
+        let run = RunDir::create(tmp.path()).unwrap();
+        std::fs::write(run.path().join("brew_upgrade.log"), "Error\n").unwrap();
+        std::fs::write(run.path().join("notify_webhook.log"), "").unwrap();
+        let kept = run.finish(&record(false), false).unwrap().unwrap();
+        let manifest = Manifest::load(&kept).unwrap();
+        assert!(!manifest.success);
+        assert_eq!(
+            manifest
+                .files
+                .iter()
+                .map(|f| (f.file.as_str(), f.tool.clone(), f.step.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("brew_upgrade.log", Some(Tool::Homebrew), "upgrade"),
+                ("notify_webhook.log", None, "notify_webhook"),
+            ]
+        );
+        assert_eq!(
+            std::fs::canonicalize(runs_dir(tmp.path()).join("latest")).unwrap(),
+            std::fs::canonicalize(&kept).unwrap()
+        );
+
+        // 同一秒内的运行使用不同目录
+        let a = RunDir::create(tmp.path()).unwrap();
+        let b = RunDir::create(tmp.path()).unwrap();
+        assert_ne!(a.path(), b.path());
+    }
+
+    #[test]
+    fn test_list_and_find_runs() {
+        let tmp = tempdir().unwrap();
+        make_run(tmp.path(), "1700000000", &[]);
+        make_run(tmp.path(), "1700000100", &[("rustup_update.log", "")]);
+        make_run(tmp.path(), "1700000100-1", &[]);
+        make_run(tmp.path(), "notes", &[]);
+        std::os::unix::fs::symlink("1700000100", runs_dir(tmp.path()).join("latest")).unwrap();
+
+        let runs = list_runs(tmp.path());
+        assert_eq!(
+            runs.iter()
+                .map(|r| (r.number, r.id.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, "1700000100-1"), (2, "1700000100"), (3, "1700000000")]
+        );
+        assert!(runs[1].manifest.is_none());
+        assert!(runs[1].includes(&Tool::Rustup));
+        assert!(!runs[1].includes(&Tool::Mise));
+        assert_eq!(find_run(&runs, 3).unwrap().id, "1700000000");
+        assert_eq!(find_run(&runs, 1700000000).unwrap().number, 3);
+        assert!(find_run(&runs, 4).is_none());
+    }
+
+    #[test]
+    fn test_step_logs() {
+        let tmp = tempdir().unwrap();
+        make_run(
+            tmp.path(),
+            "1700000000",
+            &[
+                ("brew_upgrade.log", "==> Upgrading git\n"),
+                ("brew_update.log", "Already up-to-date.\n"),
+                ("rustup_update.log", "info: syncing channel updates\n"),
+                ("outdated_packages.json", "[]"),
+                (MANIFEST, "{}"),
+            ],
+        );
+        let runs = list_runs(tmp.path());
+        let steps = step_logs(&runs[0]);
+        assert_eq!(
+            steps.iter().map(StepLog::label).collect::<Vec<_>>(),
+            vec![
+                "homebrew/outdated_packages.json",
+                "homebrew/update",
+                "homebrew/upgrade",
+                "rustup/update",
+            ]
+        );
+        assert_eq!(find_step(&steps, "upgrade").unwrap().size, 18);
+        assert_eq!(
+            find_step(&steps, "rustup/update").unwrap().file_name(),
+            "rustup_update.log"
+        );
+        assert_eq!(
+            find_step(&steps, "brew_update.log").unwrap().label(),
+            "homebrew/update"
+        );
+        // update 同时属于 Homebrew 和 Rustup
+        assert!(find_step(&steps, "update").is_none());
+        assert!(find_step(&steps, "cleanup").is_none());
+    }
+
+    #[test]
+    fn test_retention() {
+        let tmp = tempdir().unwrap();
+        let now = Utc::now();
+        let day = 24 * 3600;
+        let ts = now.timestamp();
+        for (age, size) in [(0, 10), (1, 10), (2, 100), (40, 10)] {
+            make_run(
+                tmp.path(),
+                &(ts - age * day).to_string(),
+                &[("brew_upgrade.log", &"x".repeat(size))],
+            );
+        }
+        let runs = list_runs(tmp.path());
+        let ids = |policy: Retention| {
+            policy
+                .expired(&runs, now)
+                .iter()
+                .map(|r| r.number)
+                .collect::<Vec<_>>()
+        };
+
+        let defaults = Retention::from_config(&LogsConfig::default()).unwrap();
+        assert_eq!(ids(defaults), vec![4]);
+        let by_count = Retention {
+            keep_runs: Some(2),
+            max_age: None,
+            max_size: None,
+        };
+        assert_eq!(ids(by_count), vec![3, 4]);
+        let by_size = Retention {
+            keep_runs: None,
+            max_age: None,
+            max_size: Some(50),
+        };
+        assert_eq!(ids(by_size), vec![3, 4]);
+        // 最近一次运行即使超出大小也保留
+        let tiny = Retention {
+            keep_runs: None,
+            max_age: None,
+            max_size: Some(1),
+        };
+        assert_eq!(ids(tiny), vec![2, 3, 4]);
+
+        let removed = Retention {
+            keep_runs: Some(3),
+            max_age: None,
+            max_size: None,
+        }
+        .prune(tmp.path(), now)
+        .unwrap();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(list_runs(tmp.path()).len(), 3);
+    }
+
+    #[test]
+    fn test_grep() {
+        let tmp = tempdir().unwrap();
+        make_run(
+            tmp.path(),
+            "1700000000",
+            &[("brew_upgrade.log", "ok\nError: git failed\n")],
+        );
+        make_run(
+            tmp.path(),
+            "1700000100",
+            &[
+                ("brew_upgrade.log", "ok\n"),
+                ("brew_cleanup.log", "error: permission denied\n"),
+                ("mise_up.log", "mise ERROR network\n"),
+            ],
+        );
+        let runs = list_runs(tmp.path());
+        let pattern = Regex::new("(?i)error").unwrap();
+
+        let matches = grep(&runs, None, None, &pattern);
+        assert_eq!(
+            matches.iter().map(|m| m.to_string()).collect::<Vec<_>>(),
+            vec![
+                "#1 homebrew/cleanup:1: error: permission denied",
+                "#1 mise/up:1: mise ERROR network",
+                "#2 homebrew/upgrade:2: Error: git failed",
+            ]
+        );
+        assert_eq!(grep(&runs, Some(&Tool::Homebrew), None, &pattern).len(), 2);
+        assert_eq!(grep(&runs, None, Some("upgrade"), &pattern).len(), 1);
+        assert!(grep(&runs[..1], None, Some("upgrade"), &pattern).is_empty());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(12_595), "12.3 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+
+    #[test]
+    fn test_copy_from() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("brew_upgrade.log");
+        std::fs::write(&path, "one\n").unwrap();
+        let mut out = Vec::new();
+        let offset = copy_from(&path, 0, &mut out).unwrap();
+        std::fs::write(&path, "one\ntwo\n").unwrap();
+        let offset = copy_from(&path, offset, &mut out).unwrap();
+        assert_eq!(offset, 8);
+        assert_eq!(String::from_utf8(out).unwrap(), "one\ntwo\n");
+    }
+}
@@ -1,45 +1,93 @@
 // devtool - 开发工具统一更新管理器
 // 统一管理 Homebrew、Rustup、Mise 等开发工具的更新
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{CommandFactory, Parser};
 use clap_complete::Shell;
 use clap_complete_nushell::Nushell;
 // 移除未使用的 indicatif 导入，现在使用 ProgressBarManager
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tempfile::tempdir;
-use ui::progress::{SimpleProgressManager, SimpleProgressState};
+use ui::accessible::AccessibleProgress;
+use ui::compact::{log_line, CompactProgress, SummaryRow};
+use ui::dashboard::Dashboard;
+use ui::progress::{ProgressDisplay, SimpleProgressManager, SimpleProgressState};
 use which::which;
 
 // 导入输出抑制助手函数
 use runner::{disable_output_suppression, enable_output_suppression};
 
 // 模块声明
+mod bootstrap;
+mod ci;
 mod cli;
 mod commands;
+mod config;
+mod doctor;
+mod history;
 mod i18n;
+mod interactive;
+mod live;
+mod lock;
+mod logs;
+mod manifest;
+mod notify;
+mod ownership;
 mod parallel;
+mod policy;
+mod quarantine;
+mod redact;
 mod runner;
+mod schedule;
+mod serve;
+mod snapshot;
 mod ui;
 mod utils;
+mod version;
 
 // 导入需要使用的项
-use cli::{Args, Commands, FeedbackType, ShellType};
+use bootstrap::{BootstrapProgress, BootstrapStep};
+use ci::CiProvider;
+use cli::{
+    Args, Commands, FeedbackType, LogsAction, OutputFormat, ScheduleAction, ScheduleBackend,
+    ShellType, SnapshotFormat,
+};
+use commands::pending::PendingUpgrade;
 use commands::{brew_cleanup, brew_update, brew_upgrade, mise_up, rustup_update};
-use i18n::LocalizedStrings;
+use config::{Config, LogsConfig, NotifyConfig};
+use doctor::CheckStatus;
+use history::{RunRecord, ToolResult};
+use interactive::UpgradePlan;
+use live::{LiveStatus, RunStatus, StatusReport, StepState};
+use lock::{Acquire, LockInfo, RunLock};
+use manifest::{check, plan_sync, CheckItem, InstalledVersions, SyncAction, TeamManifest};
+use ownership::{Owner, OwnershipContext, OwnershipMap};
 use parallel::{ParallelScheduler, TaskResult, Tool};
-use runner::ShellRunner;
-use std::path::Path;
-use ui::colors::{print_banner, print_error, print_info, print_success, print_warning};
+use policy::{PolicyDecision, UpdatePolicy};
+use quarantine::{
+    is_unchecked, load_deferred, save_deferred, Candidate, DeferredUpgrade, Quarantine,
+};
+use redact::Redactor;
+use runner::{Runner, ShellRunner};
+use schedule::{Backend, Job, Schedule};
+use serve::{AppState, Executor, Listen, RunRequest};
+use snapshot::diff::{diff, DiffEntry, DiffStatus};
+use snapshot::Snapshot;
+use std::path::{Path, PathBuf};
+use ui::colors::{
+    print_banner, print_error, print_header, print_info, print_success, print_warning,
+};
 use ui::icons::IconManager;
-use utils::ensure_cache_dir;
+use ui::theme::Theme;
+use utils::{ensure_cache_dir, format_duration_short, get_cache_dir, parse_duration};
 
 /// Get detailed description of what a tool will do
-fn get_tool_description(tool: &Tool) -> String {
+fn get_tool_description(tool: &Tool, lang: &str) -> String {
     match tool {
-        Tool::Homebrew => "Homebrew update & upgrade & cleanup".to_string(),
-        Tool::Rustup => "Rustup all toolchains update".to_string(),
-        Tool::Mise => "Mise tools update".to_string(),
+        Tool::Homebrew => t!(lang, "tool-homebrew"),
+        Tool::Rustup => t!(lang, "tool-rustup"),
+        Tool::Mise => t!(lang, "tool-mise"),
     }
 }
 
@@ -71,65 +119,124 @@ fn read_upgrade_details(tmpdir: &std::path::Path, tool: &Tool) -> Vec<String> {
     }
 }
 
+/// 单次更新运行的执行选项
+#[derive(Debug, Clone)]
+struct UpdateOptions {
+    dry_run: bool,
+    verbose: bool,
+    quarantine: Quarantine,
+}
+
+/// 检测已安装的工具，返回可执行的工具和跳过的工具名称
+fn detect_available_tools() -> (Vec<Tool>, Vec<&'static str>) {
+    let mut available_tools: Vec<Tool> = Vec::new();
+    let mut skipped: Vec<&str> = Vec::new();
+
+    // 检查并添加 Homebrew
+    if which("brew").is_ok() {
+        available_tools.push(Tool::Homebrew);
+    } else {
+        skipped.push("Homebrew");
+    }
+
+    // 检查并添加 Rustup
+    if which("rustup").is_ok() {
+        available_tools.push(Tool::Rustup);
+    } else {
+        skipped.push("Rust (rustup)");
+    }
+
+    // 检查并添加 Mise
+    if which("mise").is_ok() {
+        available_tools.push(Tool::Mise);
+    } else {
+        skipped.push("Mise");
+    }
+
+    (available_tools, skipped)
+}
+
+/// 是否使用全屏仪表盘：指定 --tui 且标准输出是终端
+fn use_dashboard(tui: bool) -> bool {
+    use std::io::IsTerminal;
+    tui && std::io::stdout().is_terminal()
+}
+
+/// 更新过程的显示方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DisplayMode {
+    /// 进度条
+    Bars,
+    /// 全屏仪表盘（`--tui`）
+    Dashboard,
+    /// 逐行输出（`--compact`）
+    Compact,
+    /// 逐句播报（`--accessible`）
+    Accessible,
+}
+
+/// 创建进度显示：进度条、全屏仪表盘、逐行输出或逐句播报
+///
+/// 都通过状态套接字公开运行状态，供 `devtool status` 读取
+fn new_progress_display(mode: DisplayMode, tmpdir: &Path) -> Box<dyn ProgressDisplay> {
+    let lang = i18n::detect_system_language();
+    let display: Box<dyn ProgressDisplay> = match mode {
+        DisplayMode::Bars => Box::new(SimpleProgressManager::new()),
+        DisplayMode::Dashboard => Box::new(Dashboard::new(tmpdir, &lang)),
+        DisplayMode::Compact => Box::new(CompactProgress::new(&lang)),
+        DisplayMode::Accessible => {
+            let tmpdir = tmpdir.to_path_buf();
+            Box::new(AccessibleProgress::new(&lang, move |tool| {
+                read_upgrade_details(&tmpdir, tool).len()
+            }))
+        }
+    };
+    Box::new(LiveStatus::new(display, tmpdir))
+}
+
 /// Execute tool updates in parallel
 async fn execute_parallel_updates(
     tools: Vec<Tool>,
     jobs: usize,
-    dry_run: bool,
-    verbose: bool,
-    keep_logs: bool,
+    options: UpdateOptions,
     tmpdir: std::path::PathBuf,
-    _localized: &LocalizedStrings,
+    mut progress_manager: Box<dyn ProgressDisplay>,
 ) -> Result<Vec<TaskResult>> {
     let scheduler = ParallelScheduler::new(jobs);
 
-    // 创建简化的进度条管理器
-    let mut progress_manager = SimpleProgressManager::new();
     progress_manager.create_progress_bars(&tools);
 
-    // 更新所有工具状态为执行中
-    for tool in &tools {
-        progress_manager.update_state(tool, SimpleProgressState::Executing);
-    }
-
     // 添加短暂延迟确保进度条显示
     tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
-    // 使用 Arc<Mutex<>> 来共享进度条管理器
+    // 使用 Arc<Mutex<>> 来共享进度显示
     let progress_manager = Arc::new(Mutex::new(progress_manager));
     let progress_manager_for_finalize = progress_manager.clone();
 
     let update_fn = move |tool: Tool| {
         let tool_clone = tool.clone();
         let tmpdir_path = tmpdir.clone();
-        let progress_manager = progress_manager.clone(); // 共享进度条管理器
+        let options = options.clone();
+        let progress_manager = progress_manager.clone(); // 共享进度显示
+
+        // 更新命令会阻塞线程，放到阻塞线程池执行，避免占用异步运行时（例如 serve 的 HTTP 处理）
+        tokio::task::spawn_blocking(move || {
+            // 调度器按 --jobs 限制同时运行的任务，任务真正开始时才标记为执行中，排队的工具保持等待状态
+            if let Ok(mut manager) = progress_manager.lock() {
+                manager.update_state(&tool_clone, SimpleProgressState::Executing);
+            }
 
-        tokio::spawn(async move {
             // 执行工具更新
-            let result = execute_tool_update(
-                tool_clone.clone(),
-                dry_run,
-                verbose,
-                keep_logs,
-                &tmpdir_path,
-            )
-            .await;
+            let result = execute_tool_update(tool_clone.clone(), &options, &tmpdir_path);
 
-            // 立即根据结果更新进度条状态
+            // 立即根据结果更新进度状态
             if let Ok(mut manager) = progress_manager.lock() {
-                // 检查工具是否已有进度条
-                if manager.has_progress_bar(&tool_clone) {
-                    match &result {
-                        Ok(task_result) => {
-                            if task_result.success {
-                                manager.update_state(&tool_clone, SimpleProgressState::Completed);
-                            } else {
-                                manager.update_state(&tool_clone, SimpleProgressState::Failed);
-                            }
-                        }
-                        Err(_) => {
-                            manager.update_state(&tool_clone, SimpleProgressState::Failed);
-                        }
+                match &result {
+                    Ok(task_result) if task_result.success => {
+                        manager.update_state(&tool_clone, SimpleProgressState::Completed);
+                    }
+                    _ => {
+                        manager.update_state(&tool_clone, SimpleProgressState::Failed);
                     }
                 }
             }
@@ -143,7 +250,7 @@ async fn execute_parallel_updates(
     // 延迟显示完成状态，确保用户能看到结果
     tokio::time::sleep(tokio::time::Duration::from_millis(2000)).await;
 
-    // 完成所有进度条
+    // 完成所有进度显示
     if let Ok(mut manager) = progress_manager_for_finalize.lock() {
         manager.finalize_all();
     }
@@ -151,86 +258,45 @@ async fn execute_parallel_updates(
     Ok(results)
 }
 
-/// 保存所有日志文件到缓存目录
-fn save_debug_logs(tmpdir: &Path, tool: &Tool) -> Result<()> {
-    use std::fs;
-
-    let cache_dir = ensure_cache_dir()?;
-
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map_err(|_| anyhow::anyhow!("Failed to get system time"))?
-        .as_secs();
-
-    let tool_name = match tool {
-        Tool::Homebrew => "homebrew",
-        Tool::Rustup => "rustup",
-        Tool::Mise => "mise",
+/// 按配置的保留策略删除旧的运行日志，配置有误时输出警告
+fn prune_run_logs(config: &LogsConfig, verbose: bool) {
+    let lang = i18n::detect_system_language();
+    let retention = match logs::Retention::from_config(config) {
+        Ok(retention) => retention,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                t!(&lang, "logs-retention-invalid", error = format!("{:#}", e))
+            );
+            return;
+        }
     };
-
-    // 创建工具特定的子目录
-    let tool_dir = cache_dir.join(tool_name);
-    fs::create_dir_all(&tool_dir)?;
-
-    // 创建时间戳子目录
-    let timestamp_dir = tool_dir.join(format!("{}", timestamp));
-    fs::create_dir_all(&timestamp_dir)?;
-
-    // 获取所有日志文件
-    let log_files = get_log_files_for_tool(tool);
-
-    // 复制所有日志文件
-    for log_file in &log_files {
-        let src_path = tmpdir.join(log_file);
-        if src_path.exists() {
-            let dst_path = timestamp_dir.join(log_file);
-            if let Err(e) = fs::copy(&src_path, &dst_path) {
-                eprintln!("Warning: Failed to copy {}: {}", log_file, e);
-            }
+    match retention.prune(&get_cache_dir(), chrono::Utc::now()) {
+        Ok(removed) if verbose && !removed.is_empty() => {
+            println!("Debug: removed {} old run log directories", removed.len());
         }
-    }
-
-    // 创建 latest 符号链接
-    let latest_link = tool_dir.join("latest");
-    if latest_link.exists() {
-        fs::remove_file(&latest_link).ok();
-    }
-    std::os::unix::fs::symlink(&timestamp_dir, &latest_link).ok();
-
-    Ok(())
-}
-
-/// 获取工具相关的日志文件列表
-fn get_log_files_for_tool(tool: &Tool) -> Vec<&'static str> {
-    match tool {
-        Tool::Homebrew => vec![
-            "brew_detailed_debug.log",
-            "brew_outdated.log",
-            "brew_update.log",
-            "brew_upgrade.log",
-            "brew_cleanup.log",
-            "brew_errors.log",
-            "outdated_packages.json",
-        ],
-        Tool::Rustup => vec!["rustup_update.log"],
-        Tool::Mise => vec!["mise_up.log"],
+        Ok(_) => {}
+        Err(e) => eprintln!(
+            "{}",
+            t!(&lang, "logs-prune-failed", error = format!("{:#}", e))
+        ),
     }
 }
 
 /// Execute a single tool update
-async fn execute_tool_update(
+fn execute_tool_update(
     tool: Tool,
-    dry_run: bool,
-    verbose: bool,
-    keep_logs: bool,
+    options: &UpdateOptions,
     tmpdir: &std::path::Path,
 ) -> Result<TaskResult> {
     let runner = ShellRunner;
+    let verbose = options.verbose;
+    let quarantine = &options.quarantine;
 
     // 启用输出抑制，防止命令输出干扰进度条显示
     enable_output_suppression();
 
-    let result = if dry_run {
+    let result = if options.dry_run {
         TaskResult {
             tool: tool.clone(),
             success: true,
@@ -241,7 +307,7 @@ async fn execute_tool_update(
             Tool::Homebrew => {
                 // Execute homebrew update sequence with progress bar isolation
                 let update_result = brew_update(&runner, tmpdir, verbose)?;
-                let upgrade_result = brew_upgrade(&runner, tmpdir, verbose)?;
+                let upgrade_result = brew_upgrade(&runner, tmpdir, verbose, quarantine)?;
                 let cleanup_result = brew_cleanup(&runner, tmpdir, verbose)?;
 
                 // Check if any step had changes
@@ -266,7 +332,7 @@ async fn execute_tool_update(
                 }
             }
             Tool::Rustup => {
-                let result = rustup_update(&runner, tmpdir, verbose)?;
+                let result = rustup_update(&runner, tmpdir, verbose, quarantine)?;
                 let has_changes = result.0 == "changed";
                 let output = if has_changes {
                     "Rustup updated".to_string()
@@ -281,7 +347,7 @@ async fn execute_tool_update(
                 }
             }
             Tool::Mise => {
-                let result = mise_up(&runner, tmpdir, verbose)?;
+                let result = mise_up(&runner, tmpdir, verbose, quarantine)?;
                 let has_changes = result.0 == "changed";
                 let output = if has_changes {
                     "Mise updated".to_string()
@@ -289,32 +355,2102 @@ async fn execute_tool_update(
                     "Mise already latest".to_string()
                 };
 
-                TaskResult {
-                    tool,
-                    success: result.0 == "changed" || result.0 == "unchanged",
-                    output,
-                }
-            }
+                TaskResult {
+                    tool,
+                    success: result.0 == "changed" || result.0 == "unchanged",
+                    output,
+                }
+            }
+        }
+    };
+
+    // 禁用输出抑制，恢复正常输出
+    disable_output_suppression();
+
+    Ok(result)
+}
+
+/// 输出界面语言的协商过程（`--print-locale`）
+fn print_locale_report(report: &i18n::LocaleReport) {
+    for (name, value) in &report.variables {
+        match value {
+            Some(value) => println!("{}={}", name, value),
+            None => println!("{} (unset)", name),
+        }
+    }
+    if let Some(setting) = &report.setting {
+        println!("setting: {}", setting);
+    }
+    println!("requested: {}", report.requested.join(", "));
+    println!("available: {}", i18n::available_languages().join(", "));
+    println!("selected: {}", report.selected);
+}
+
+/// 因维护窗口或冻结期拒绝更新时的退出码
+const EXIT_POLICY_BLOCKED: i32 = 3;
+
+/// 另一个 devtool 运行持有锁时的退出码
+const EXIT_LOCKED: i32 = 4;
+
+/// 描述持有锁的运行
+fn describe_lock_holder(info: Option<&LockInfo>, lang: &str) -> String {
+    let Some(info) = info else {
+        return t!(lang, "lock-held-unknown");
+    };
+    let started = info
+        .started_at
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M:%S");
+    t!(
+        lang,
+        "lock-held",
+        pid = info.pid,
+        started = started.to_string(),
+        command = info.command.as_str()
+    )
+}
+
+/// 获取单实例锁；已被占用时等待（`--wait`）或报告持有者并以 EXIT_LOCKED 退出
+fn acquire_run_lock(wait: bool, lang: &str) -> Result<RunLock> {
+    let icons = get_icon_manager();
+    let path = lock::lock_path();
+    if wait {
+        return lock::acquire_wait(&path, |info| {
+            print_info(&format!(
+                "{} {}",
+                icons.pause(),
+                describe_lock_holder(info, lang)
+            ));
+            println!("   {}", t!(lang, "lock-waiting"));
+        });
+    }
+    match lock::try_acquire(&path)? {
+        Acquire::Acquired(run_lock) => Ok(run_lock),
+        Acquire::Held(info) => {
+            print_error(&format!(
+                "{} {}",
+                icons.warning(),
+                describe_lock_holder(info.as_ref(), lang)
+            ));
+            println!(
+                "{}",
+                t!(lang, "lock-wait-hint", path = path.display().to_string())
+            );
+            std::process::exit(EXIT_LOCKED);
+        }
+    }
+}
+
+/// 描述策略判定结果
+fn describe_policy_decision(
+    decision: &PolicyDecision,
+    policy: &UpdatePolicy,
+    lang: &str,
+) -> String {
+    match decision {
+        PolicyDecision::Allowed => t!(lang, "policy-allowed"),
+        PolicyDecision::Frozen(freeze) => {
+            let reason = freeze
+                .reason
+                .as_ref()
+                .map(|r| format!(" ({})", r))
+                .unwrap_or_default();
+            t!(
+                lang,
+                "policy-frozen",
+                from = freeze.from.to_string(),
+                to = freeze.to.to_string(),
+                reason = reason
+            )
+        }
+        PolicyDecision::OutsideWindow => {
+            let windows: Vec<&str> = policy.windows().iter().map(|w| w.spec()).collect();
+            t!(lang, "policy-outside-window", windows = windows.join(", "))
+        }
+    }
+}
+
+/// 描述下一个允许更新的时间
+fn describe_next_allowed(policy: &UpdatePolicy, lang: &str) -> String {
+    let next = policy.next_allowed(chrono::Local::now().naive_local());
+    match next {
+        Some(at) => t!(
+            lang,
+            "policy-next-allowed",
+            time = at.format("%Y-%m-%d %H:%M (%a)").to_string()
+        ),
+        None => t!(lang, "policy-no-window"),
+    }
+}
+
+/// 打印正在运行的更新状态
+fn print_run_status(run: &RunStatus, lang: &str) {
+    let icons = get_icon_manager();
+    let elapsed = format_elapsed_secs(run.elapsed_secs);
+    let started = run
+        .started_at
+        .with_timezone(&chrono::Local)
+        .format("%H:%M:%S");
+    print_info(&format!(
+        "{} {}",
+        icons.rocket(),
+        t!(
+            lang,
+            "status-running",
+            pid = run.pid,
+            started = started.to_string(),
+            elapsed = elapsed
+        )
+    ));
+
+    for tool in &run.tools {
+        let (icon, state) = match tool.state {
+            StepState::Pending => (icons.pause(), t!(lang, "dashboard-pending")),
+            StepState::Running => (icons.rocket(), t!(lang, "dashboard-running")),
+            StepState::Done => (icons.success(), t!(lang, "dashboard-done")),
+            StepState::Failed => (icons.failure(), t!(lang, "dashboard-failed")),
+        };
+        let state = match (&tool.step, tool.step_index) {
+            (Some(step), Some(index)) => format!("{} {}/{}", step, index, tool.step_count),
+            _ => state,
+        };
+        let elapsed = tool
+            .elapsed_secs
+            .map(format_elapsed_secs)
+            .unwrap_or_default();
+        let line = tool.current_line.as_deref().unwrap_or("");
+        println!(
+            "   {} {:<9} {:<12} {:>5}  {}",
+            icon,
+            tool.tool.display_name(),
+            state,
+            elapsed,
+            line
+        )
+    }
+}
+
+/// 格式化秒数为 mm:ss
+fn format_elapsed_secs(secs: u64) -> String {
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+/// 持续输出正在运行的更新状态，状态变化时输出一行，直到被中断
+fn watch_run_status(path: &Path, format: OutputFormat) -> Result<()> {
+    let mut last = String::new();
+    loop {
+        // 读取超时或运行恰好结束时的不完整响应不中断监视，视为空闲并继续轮询
+        let run = live::query(path).unwrap_or(None);
+        let line = match format {
+            OutputFormat::Json => serde_json::to_string(&StatusReport::new(run))?,
+            OutputFormat::Text => run
+                .map(|r| r.summary())
+                .unwrap_or_else(|| "devtool: idle".to_string()),
+        };
+        if line != last {
+            println!("{}", line);
+            last = line;
+        }
+        std::thread::sleep(std::time::Duration::from_secs(1));
+    }
+}
+
+/// 处理 status 命令
+fn handle_status_command(watch: bool, format: OutputFormat) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+    let socket = live::socket_path();
+    if watch {
+        return watch_run_status(&socket, format);
+    }
+
+    let run = live::query(&socket)?;
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&StatusReport::new(run))?);
+        return Ok(());
+    }
+    match &run {
+        Some(run) => print_run_status(run, &lang),
+        None => print_info(&format!("{} {}", icons.info(), t!(&lang, "status-idle"))),
+    }
+    println!();
+
+    let config = Config::load()?;
+    let policy = UpdatePolicy::from_config(&config.policy)?;
+
+    if !policy.is_restricted() {
+        print_info(&format!(
+            "{} {}",
+            icons.info(),
+            t!(&lang, "policy-unrestricted")
+        ));
+        return Ok(());
+    }
+
+    let decision = policy.check_now();
+    let summary = describe_policy_decision(&decision, &policy, &lang);
+    if decision == PolicyDecision::Allowed {
+        print_success(&format!("{} {}", icons.success(), summary));
+    } else {
+        print_warning(&format!("{} {}", icons.warning(), summary));
+        println!("{}", describe_next_allowed(&policy, &lang));
+    }
+
+    if !policy.windows().is_empty() {
+        println!("\n{}", t!(&lang, "policy-windows-heading"));
+        for window in policy.windows() {
+            println!("   {}", window.spec());
+        }
+    }
+    if !policy.freezes().is_empty() {
+        println!("\n{}", t!(&lang, "policy-freezes-heading"));
+        for freeze in policy.freezes() {
+            let reason = freeze
+                .reason
+                .as_ref()
+                .map(|r| format!(" ({})", r))
+                .unwrap_or_default();
+            println!("   {} → {}{}", freeze.from, freeze.to, reason);
+        }
+    }
+
+    Ok(())
+}
+
+/// 处理 logs 子命令
+fn handle_logs_command(
+    tool: Option<&Tool>,
+    run: Option<u64>,
+    step: Option<&str>,
+    follow: bool,
+    action: Option<&LogsAction>,
+) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+
+    if let Some(LogsAction::Grep {
+        pattern,
+        tool,
+        run,
+        step,
+        ignore_case,
+    }) = action
+    {
+        return handle_logs_grep(pattern, tool.as_ref(), *run, step.as_deref(), *ignore_case);
+    }
+
+    if follow {
+        if !logs::follow(&live::socket_path(), tool, &lang)? {
+            print_info(&format!("{} {}", icons.info(), t!(&lang, "status-idle")));
+        }
+        return Ok(());
+    }
+
+    let runs = logs::list_runs(&get_cache_dir());
+    let matching: Vec<&logs::LogRun> = runs
+        .iter()
+        .filter(|r| tool.is_none_or(|tool| r.includes(tool)))
+        .collect();
+
+    if run.is_none() && step.is_none() {
+        if matching.is_empty() {
+            let message = match tool {
+                Some(tool) => t!(&lang, "logs-empty-tool", tool = tool.display_name()),
+                None => t!(&lang, "logs-empty"),
+            };
+            print_info(&format!("{} {}", icons.info(), message));
+        } else {
+            for run in matching {
+                print_log_run(run, &lang);
+            }
+            println!("\n{}", t!(&lang, "logs-hint"));
+        }
+        return Ok(());
+    }
+
+    let selected = match run {
+        Some(n) => logs::find_run(&runs, n),
+        None => matching.first().copied(),
+    };
+    let Some(selected) = selected else {
+        anyhow::bail!(
+            "{}",
+            t!(
+                &lang,
+                "logs-run-not-found",
+                run = run.unwrap_or(1),
+                count = runs.len()
+            )
+        );
+    };
+    let steps: Vec<logs::StepLog> = logs::step_logs(selected)
+        .into_iter()
+        .filter(|s| tool.is_none_or(|tool| s.tool.as_ref() == Some(tool)))
+        .collect();
+
+    let Some(step) = step else {
+        print_log_run(selected, &lang);
+        for log in &steps {
+            println!(
+                "      {:<32} {:>10}  {}",
+                log.label(),
+                logs::format_size(log.size),
+                log.path.display()
+            );
+        }
+        return Ok(());
+    };
+    match logs::find_step(&steps, step) {
+        Some(log) => logs::open_in_pager(&log.path),
+        None => anyhow::bail!(
+            "{}",
+            t!(
+                &lang,
+                "logs-step-not-found",
+                step = step,
+                run = selected.number,
+                steps = steps
+                    .iter()
+                    .map(|s| s.label())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        ),
+    }
+}
+
+/// 处理 logs grep 子命令，没有匹配时以非零状态退出
+fn handle_logs_grep(
+    pattern: &str,
+    tool: Option<&Tool>,
+    run: Option<u64>,
+    step: Option<&str>,
+    ignore_case: bool,
+) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let regex = regex::RegexBuilder::new(pattern)
+        .case_insensitive(ignore_case)
+        .build()
+        .with_context(|| format!("invalid pattern {:?}", pattern))?;
+
+    let runs = logs::list_runs(&get_cache_dir());
+    let runs: Vec<logs::LogRun> = match run {
+        Some(n) => logs::find_run(&runs, n).into_iter().cloned().collect(),
+        None => runs,
+    };
+    let matches = logs::grep(&runs, tool, step, &regex);
+
+    if matches.is_empty() {
+        eprintln!("{}", t!(&lang, "logs-grep-no-match", pattern = pattern));
+        std::process::exit(1);
+    }
+    for m in &matches {
+        println!("{}", m);
+    }
+    Ok(())
+}
+
+/// 输出一次运行：编号、开始时间、结果、包含的工具和日志总大小
+fn print_log_run(run: &logs::LogRun, lang: &str) {
+    let status = match &run.manifest {
+        Some(manifest) if manifest.success => t!(lang, "logs-status-ok"),
+        Some(_) => t!(lang, "logs-status-failed"),
+        None => t!(lang, "logs-status-incomplete"),
+    };
+    let tools: Vec<&str> = logs::TOOLS
+        .iter()
+        .filter(|tool| run.includes(tool))
+        .map(logs::tool_dir_name)
+        .collect();
+    println!(
+        "   #{:<3} {}  {:<10}  {}  ({})",
+        run.number,
+        run.started_at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S"),
+        status,
+        tools.join(", "),
+        logs::format_size(run.size())
+    );
+}
+
+/// 将执行结果转换为带升级详情的运行摘要，输出和升级详情已脱敏，供历史、通知和 manifest 使用
+fn tool_results(results: &[TaskResult], tmpdir: &Path) -> Vec<ToolResult> {
+    results
+        .iter()
+        .map(|result| ToolResult {
+            tool: result.tool.clone(),
+            success: result.success,
+            output: redact::current().redact(&result.output).into_owned(),
+            upgrades: read_upgrade_details(tmpdir, &result.tool)
+                .iter()
+                .map(|detail| redact::current().redact(detail).into_owned())
+                .collect(),
+        })
+        .collect()
+}
+
+/// 运行结束时发送通知，通知失败只输出警告，不影响运行结果
+fn send_notifications(notify: &[NotifyConfig], record: &RunRecord, tmpdir: &Path, lang: &str) {
+    let icons = get_icon_manager();
+    for (sink, e) in notify::notify_all(&ShellRunner, tmpdir, notify, record, lang) {
+        print_warning(&format!(
+            "{} {}",
+            icons.warning(),
+            t!(
+                lang,
+                "notify-send-failed",
+                sink = sink,
+                error = format!("{:#}", e)
+            )
+        ));
+    }
+}
+
+/// 每个工具的结果和升级详情
+fn summary_rows(results: &[TaskResult], tmpdir: &Path) -> Vec<SummaryRow> {
+    results
+        .iter()
+        .map(|result| SummaryRow {
+            tool: result.tool.clone(),
+            success: result.success,
+            upgrades: if result.success {
+                read_upgrade_details(tmpdir, &result.tool)
+            } else {
+                Vec::new()
+            },
+        })
+        .collect()
+}
+
+/// 因隔离期暂缓的升级，按工具分组
+fn deferred_upgrades(tmpdir: &Path) -> Vec<(Tool, Vec<DeferredUpgrade>)> {
+    [
+        (Tool::Homebrew, "brew"),
+        (Tool::Rustup, "rustup"),
+        (Tool::Mise, "mise"),
+    ]
+    .into_iter()
+    .map(|(tool, tool_key)| (tool, load_deferred(tmpdir, tool_key)))
+    .filter(|(_, items)| !items.is_empty())
+    .collect()
+}
+
+/// 启用隔离期时因无法列出候选版本而跳过升级的工具
+fn unchecked_tools(tmpdir: &Path) -> Vec<Tool> {
+    [Tool::Homebrew, Tool::Rustup, Tool::Mise]
+        .into_iter()
+        .filter(|tool| is_unchecked(tmpdir, deferred_key(tool)))
+        .collect()
+}
+
+/// CI 中的失败注解、暂缓升级注解和 GitHub Actions 任务摘要
+fn report_to_ci(provider: CiProvider, results: &[TaskResult], tmpdir: &Path, lang: &str) {
+    let failures: Vec<(Tool, String)> = results
+        .iter()
+        .filter(|result| !result.success)
+        .map(|result| (result.tool.clone(), result.output.clone()))
+        .collect();
+    let deferred = deferred_upgrades(tmpdir);
+    for line in ci::annotations(provider, &failures, &deferred, lang) {
+        println!("{}", line);
+    }
+    if provider == CiProvider::GitHub {
+        let summary = ci::job_summary(&summary_rows(results, tmpdir), &deferred, lang);
+        if let Err(e) = ci::write_job_summary(&summary) {
+            eprintln!(
+                "{}",
+                t!(lang, "ci-summary-write-failed", error = e.to_string())
+            );
+        }
+    }
+}
+
+/// 紧凑输出和无障碍输出共用的摘要附注：暂缓的升级、无法确认隔离期的工具和被 PATH 遮蔽的升级
+struct SummaryNotes {
+    deferred: Vec<(Tool, Vec<DeferredUpgrade>)>,
+    unchecked: Vec<Tool>,
+    shadowed: Vec<(String, Owner, ownership::Provision)>,
+}
+
+impl SummaryNotes {
+    fn collect(tmpdir: &Path) -> Self {
+        Self {
+            deferred: deferred_upgrades(tmpdir),
+            unchecked: unchecked_tools(tmpdir),
+            shadowed: shadowed_upgrades(tmpdir),
+        }
+    }
+}
+
+/// 紧凑输出的结果摘要：结果表、暂缓的升级和被 PATH 遮蔽的升级，不含表情符号
+fn print_compact_summary(
+    results: &[TaskResult],
+    tmpdir: &Path,
+    duration: chrono::Duration,
+    lang: &str,
+) {
+    let took = ui::compact::format_secs(duration.num_seconds().max(0) as u64);
+    log_line(&t!(lang, "compact-finished", duration = took));
+
+    for line in ui::compact::summary_lines(&summary_rows(results, tmpdir), lang) {
+        println!("{}", line);
+    }
+
+    let notes = SummaryNotes::collect(tmpdir);
+    for (tool, items) in &notes.deferred {
+        for item in items {
+            println!(
+                "{}",
+                t!(
+                    lang,
+                    "compact-deferred",
+                    tool = tool.display_name(),
+                    name = item.name.as_str(),
+                    version = item.version.as_str(),
+                    remaining = format_duration_short(item.remaining())
+                )
+            );
+        }
+    }
+    for tool in &notes.unchecked {
+        println!(
+            "{}",
+            t!(lang, "update-unchecked", tool = tool.display_name())
+        );
+    }
+    for (command, upgraded, winner) in &notes.shadowed {
+        println!(
+            "{}",
+            t!(
+                lang,
+                "update-shadowed",
+                command = command.as_str(),
+                via = upgraded.describe(),
+                path = winner.path.display().to_string(),
+                owner = winner.owner.describe()
+            )
+        );
+    }
+}
+
+/// 无障碍输出的结果摘要：逐句朗读，不含表格、图标和符号，时长使用完整单位
+fn print_accessible_summary(
+    results: &[TaskResult],
+    tmpdir: &Path,
+    duration: chrono::Duration,
+    lang: &str,
+) {
+    let took = i18n::spell_duration(lang, duration.num_seconds().max(0) as u64);
+    println!("{}", t!(lang, "accessible-complete", duration = took));
+
+    for line in ui::accessible::summary_lines(&summary_rows(results, tmpdir), lang) {
+        println!("{}", line);
+    }
+
+    let notes = SummaryNotes::collect(tmpdir);
+    for (tool, items) in &notes.deferred {
+        for item in items {
+            println!(
+                "{}",
+                t!(
+                    lang,
+                    "accessible-deferred",
+                    tool = tool.display_name(),
+                    name = item.name.as_str(),
+                    version = item.version.as_str(),
+                    remaining =
+                        i18n::spell_duration(lang, item.remaining().num_seconds().max(0) as u64)
+                )
+            );
+        }
+    }
+    for tool in &notes.unchecked {
+        println!(
+            "{}",
+            t!(lang, "accessible-unchecked", tool = tool.display_name())
+        );
+    }
+    for (command, upgraded, winner) in &notes.shadowed {
+        println!(
+            "{}",
+            t!(
+                lang,
+                "accessible-shadowed",
+                command = command.as_str(),
+                via = upgraded.describe(),
+                path = winner.path.display().to_string(),
+                owner = winner.owner.describe()
+            )
+        );
+    }
+}
+
+/// 执行一次通过 HTTP API 触发的更新，复用并行调度和更新摘要数据
+async fn execute_api_run(
+    request: RunRequest,
+    display: Box<dyn ProgressDisplay>,
+    lang: String,
+) -> Result<Vec<ToolResult>> {
+    let config = Config::load()?;
+    let policy = UpdatePolicy::from_config(&config.policy)?;
+    let decision = policy.check_now();
+    if decision != PolicyDecision::Allowed && !request.force && !request.dry_run {
+        anyhow::bail!("{}", describe_policy_decision(&decision, &policy, &lang));
+    }
+
+    let min_age = match request.min_age.clone().or(config.quarantine.min_age) {
+        Some(value) => Some(parse_duration(&value)?),
+        None => None,
+    };
+    let options = UpdateOptions {
+        dry_run: request.dry_run,
+        verbose: false,
+        quarantine: Quarantine::new(min_age),
+    };
+
+    let (available_tools, _) = detect_available_tools();
+    let tools: Vec<Tool> = match &request.tools {
+        Some(requested) => available_tools
+            .into_iter()
+            .filter(|tool| requested.contains(tool))
+            .collect(),
+        None => available_tools,
+    };
+
+    let _run_lock = if request.dry_run {
+        None
+    } else {
+        match lock::try_acquire(&lock::lock_path())? {
+            Acquire::Acquired(run_lock) => Some(run_lock),
+            Acquire::Held(info) => {
+                anyhow::bail!("{}", describe_lock_holder(info.as_ref(), &lang))
+            }
+        }
+    };
+
+    let started_at = chrono::Utc::now();
+    let run_dir = logs::RunDir::create(&ensure_cache_dir()?)?;
+    let tmpdir = run_dir.path().to_path_buf();
+    let display: Box<dyn ProgressDisplay> = Box::new(LiveStatus::new(display, &tmpdir));
+    let results = match execute_parallel_updates(
+        tools,
+        request.jobs.unwrap_or(3),
+        options,
+        tmpdir.clone(),
+        display,
+    )
+    .await
+    {
+        Ok(results) => tool_results(&results, &tmpdir),
+        Err(e) => {
+            // 运行中途出错也保留已写入的日志
+            if request.dry_run {
+                run_dir.discard();
+            } else {
+                let record = RunRecord {
+                    started_at,
+                    finished_at: chrono::Utc::now(),
+                    scheduled: false,
+                    tools: Vec::new(),
+                };
+                let _ = run_dir.finish(&record, true);
+            }
+            return Err(e);
+        }
+    };
+    if request.dry_run {
+        run_dir.discard();
+        return Ok(results);
+    }
+
+    let record = RunRecord {
+        started_at,
+        finished_at: chrono::Utc::now(),
+        scheduled: false,
+        tools: results.clone(),
+    };
+    if !config.notify.is_empty() {
+        let record = record.clone();
+        let tmpdir = tmpdir.clone();
+        let lang = lang.clone();
+        let _ = tokio::task::spawn_blocking(move || {
+            let failures =
+                notify::notify_all(&ShellRunner, &tmpdir, &config.notify, &record, &lang);
+            for (sink, e) in failures {
+                eprintln!(
+                    "{}",
+                    t!(
+                        &lang,
+                        "notify-send-failed",
+                        sink = sink,
+                        error = format!("{:#}", e)
+                    )
+                );
+            }
+        })
+        .await;
+    }
+    // 与命令行运行相同：失败的运行保留日志和 manifest.json
+    if let Err(e) = run_dir.finish(&record, false) {
+        eprintln!(
+            "{}",
+            t!(&lang, "log-save-failed", error = format!("{:#}", e))
+        );
+    }
+    Ok(results)
+}
+
+async fn handle_serve_command(
+    listen: &str,
+    socket: Option<&Path>,
+    token: Option<&str>,
+) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+    let listen = Listen::parse(listen, socket)?;
+
+    let env_token = std::env::var("DEVTOOL_SERVE_TOKEN").ok();
+    let (token, generated) = match token.map(str::to_string).or(env_token) {
+        Some(token) => (token, false),
+        None => (serve::generate_token()?, true),
+    };
+
+    let address = match &listen {
+        Listen::Tcp(addr) => format!("http://{}", addr),
+        Listen::Unix(path) => format!("unix:{}", path.display()),
+    };
+    print_info(&format!(
+        "{} {}",
+        icons.rocket(),
+        t!(&lang, "serve-listening", address = address)
+    ));
+    if generated {
+        println!("   {}", t!(&lang, "serve-token", token = token.as_str()));
+    }
+    println!("   {}", t!(&lang, "serve-stop-hint"));
+
+    // API 运行的错误信息使用服务端的界面语言
+    let executor: Executor =
+        Arc::new(move |request, display| Box::pin(execute_api_run(request, display, lang.clone())));
+    serve::serve(&listen, AppState::new(&token, executor)).await
+}
+
+fn handle_schedule_command(action: &ScheduleAction) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+    let runner = ShellRunner;
+    let tmp = tempdir()?;
+
+    match action {
+        ScheduleAction::Install {
+            daily,
+            days,
+            backend,
+            dry_run,
+        } => {
+            let schedule = Schedule::parse(daily, days.as_deref())?;
+            let job = Job::current()?;
+            let backend = match backend {
+                Some(ScheduleBackend::Systemd) => Backend::Systemd,
+                Some(ScheduleBackend::Cron) => Backend::Cron,
+                None => schedule::detect_backend(&runner, tmp.path())?,
+            };
+
+            if *dry_run {
+                match backend {
+                    Backend::Systemd => {
+                        println!("# {}.service", schedule::UNIT_NAME);
+                        println!("{}", schedule::systemd_service(&job));
+                        println!("# {}.timer", schedule::UNIT_NAME);
+                        print!("{}", schedule::systemd_timer(&schedule));
+                    }
+                    Backend::Cron => print!("{}", schedule::cron_entry(&schedule, &job)),
+                }
+                return Ok(());
+            }
+
+            let files = schedule::install(&runner, tmp.path(), backend, &schedule, &job)?;
+            print_success(&format!(
+                "{} {}",
+                icons.success(),
+                t!(
+                    &lang,
+                    "schedule-installed",
+                    backend = backend.name(),
+                    schedule = daily.as_str()
+                )
+            ));
+            for file in files {
+                println!("   {}", file.display());
+            }
+            println!(
+                "   {}",
+                t!(&lang, "schedule-log", path = job.log.display().to_string())
+            );
+        }
+        ScheduleAction::Status => {
+            match schedule::status(&runner, tmp.path())? {
+                Some(status) => {
+                    let state = if status.enabled {
+                        t!(&lang, "schedule-enabled")
+                    } else {
+                        t!(&lang, "schedule-disabled")
+                    };
+                    print_info(&format!(
+                        "{} {}",
+                        icons.info(),
+                        t!(
+                            &lang,
+                            "schedule-status",
+                            backend = status.backend.name(),
+                            state = state,
+                            schedule = status.schedule.as_str()
+                        )
+                    ));
+                    if let Some(next) = status.next_run {
+                        println!("   {}", t!(&lang, "schedule-next-run", time = next));
+                    }
+                }
+                None => print_info(&format!(
+                    "{} {}",
+                    icons.info(),
+                    t!(&lang, "schedule-not-installed")
+                )),
+            }
+
+            let last = history::load(&history::history_path())
+                .into_iter()
+                .rev()
+                .find(|record| record.scheduled);
+            if let Some(record) = last {
+                let when = record
+                    .started_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M");
+                let result = if record.success() {
+                    t!(&lang, "schedule-run-succeeded")
+                } else {
+                    t!(&lang, "schedule-run-failed")
+                };
+                println!(
+                    "   {}",
+                    t!(
+                        &lang,
+                        "schedule-last-run",
+                        time = when.to_string(),
+                        result = result
+                    )
+                );
+            }
+        }
+        ScheduleAction::Remove => {
+            let removed = schedule::remove(&runner, tmp.path())?;
+            if removed.is_empty() {
+                print_info(&format!(
+                    "{} {}",
+                    icons.info(),
+                    t!(&lang, "schedule-not-installed")
+                ));
+            }
+            for backend in removed {
+                print_success(&format!(
+                    "{} {}",
+                    icons.success(),
+                    t!(&lang, "schedule-removed", backend = backend.name())
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 加载团队清单，默认文件名时向上查找
+fn load_team_manifest(path: &Path) -> Result<(PathBuf, TeamManifest)> {
+    let path = manifest::resolve_manifest_path(path);
+    let manifest = TeamManifest::load(&path)?;
+    Ok((path, manifest))
+}
+
+/// 打印清单检查结果，返回偏差项数量
+fn print_check_report(items: &[CheckItem], lang: &str) -> usize {
+    let icons = get_icon_manager();
+    for item in items {
+        let installed = item.installed.as_deref().unwrap_or("-");
+        let line = format!(
+            "{}/{}: {} {} ({} {})",
+            item.tool.display_name().to_lowercase(),
+            item.name,
+            t!(lang, "check-requires"),
+            item.required,
+            t!(lang, "check-installed"),
+            installed
+        );
+        match item.status {
+            manifest::DriftStatus::Ok => print_success(&format!("{} {}", icons.success(), line)),
+            manifest::DriftStatus::Missing => print_error(&format!(
+                "{} {} [{}]",
+                icons.failure(),
+                line,
+                t!(lang, "check-missing")
+            )),
+            manifest::DriftStatus::Mismatch => print_error(&format!(
+                "{} {} [{}]",
+                icons.failure(),
+                line,
+                t!(lang, "check-mismatch")
+            )),
+        }
+    }
+
+    let drift = items.iter().filter(|item| item.is_drift()).count();
+    println!();
+    if drift == 0 {
+        print_success(&format!("{} {}", icons.success(), t!(lang, "check-clean")));
+    } else {
+        print_warning(&format!(
+            "{} {}",
+            icons.warning(),
+            t!(lang, "check-drift", count = drift)
+        ));
+    }
+    drift
+}
+
+/// 处理 check 子命令：存在偏差时以退出码 1 退出
+fn handle_check_command(manifest_path: &Path, format: OutputFormat) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let (path, team_manifest) = load_team_manifest(manifest_path)?;
+
+    let tmp = tempdir()?;
+    let installed = InstalledVersions::probe(&ShellRunner, tmp.path(), &team_manifest.tools());
+    let items = check(&team_manifest, &installed);
+
+    let drift = match format {
+        OutputFormat::Json => {
+            let drift = items.iter().filter(|item| item.is_drift()).count();
+            let report = serde_json::json!({
+                "manifest": path,
+                "drift": drift,
+                "items": items,
+            });
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            drift
+        }
+        OutputFormat::Text => {
+            let icons = get_icon_manager();
+            print_info(&format!("{} {}", icons.clipboard(), path.display()));
+            print_check_report(&items, &lang)
+        }
+    };
+
+    if drift > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// 处理 sync 子命令：按清单安装、升级或降级偏差项
+fn handle_sync_command(
+    manifest_path: &Path,
+    dry_run: bool,
+    verbose: bool,
+    wait: bool,
+) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+    let (path, team_manifest) = load_team_manifest(manifest_path)?;
+    print_info(&format!("{} {}", icons.clipboard(), path.display()));
+
+    // 与 update 互斥，避免同时修改同一个 Cellar 或工具链
+    let _run_lock = if dry_run {
+        None
+    } else {
+        Some(acquire_run_lock(wait, &lang)?)
+    };
+
+    let tmp = tempdir()?;
+    let runner = ShellRunner;
+    let tools = team_manifest.tools();
+    let items = check(
+        &team_manifest,
+        &InstalledVersions::probe(&runner, tmp.path(), &tools),
+    );
+
+    let drifted: Vec<&CheckItem> = items.iter().filter(|item| item.is_drift()).collect();
+    if drifted.is_empty() {
+        print_success(&format!("{} {}", icons.success(), t!(&lang, "check-clean")));
+        return Ok(());
+    }
+
+    for (index, item) in drifted.iter().enumerate() {
+        match plan_sync(item, &lang) {
+            SyncAction::Manual(note) => {
+                print_warning(&format!("{} {}", icons.warning(), note));
+            }
+            SyncAction::Run(cmd) if dry_run => {
+                println!("[dry-run] {}", cmd);
+            }
+            SyncAction::Run(cmd) => {
+                print_info(&format!("{} {}", icons.wrench(), cmd));
+                let logfile = tmp.path().join(format!("sync_{}.log", index));
+                let (rc, output) = runner.run(&cmd, &logfile, verbose)?;
+                if rc != 0 {
+                    print_error(&format!("{} {} (exit {})", icons.failure(), cmd, rc));
+                    if !verbose && !output.is_empty() {
+                        println!("{}", output);
+                    }
+                }
+            }
+        }
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+
+    // 重新探测并报告剩余偏差
+    println!();
+    let items = check(
+        &team_manifest,
+        &InstalledVersions::probe(&runner, tmp.path(), &tools),
+    );
+    if print_check_report(&items, &lang) > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// 顺序执行某个工具的初始化步骤，失败时停止该工具的后续步骤
+fn run_bootstrap_steps(
+    tool: &Tool,
+    steps: &[BootstrapStep],
+    progress: &Mutex<BootstrapProgress>,
+    tmpdir: &Path,
+    verbose: bool,
+) -> TaskResult {
+    let icons = get_icon_manager();
+    let runner = ShellRunner;
+    for (index, step) in steps.iter().enumerate() {
+        let logfile = tmpdir.join(format!(
+            "bootstrap_{}_{}.log",
+            tool.display_name().to_lowercase(),
+            index
+        ));
+        match runner.run(&step.command, &logfile, verbose) {
+            Ok((0, _)) => {
+                print_success(&format!("{} {}", icons.success(), step.command));
+                if let Ok(mut progress) = progress.lock() {
+                    if let Err(e) = progress.mark_completed(step) {
+                        print_warning(&format!("{} {}", icons.warning(), e));
+                    }
+                }
+            }
+            Ok((rc, output)) => {
+                print_error(&format!(
+                    "{} {} (exit {})",
+                    icons.failure(),
+                    step.command,
+                    rc
+                ));
+                return TaskResult {
+                    tool: tool.clone(),
+                    success: false,
+                    output,
+                };
+            }
+            Err(e) => {
+                print_error(&format!("{} {}: {}", icons.failure(), step.command, e));
+                return TaskResult {
+                    tool: tool.clone(),
+                    success: false,
+                    output: e.to_string(),
+                };
+            }
+        }
+    }
+    TaskResult {
+        tool: tool.clone(),
+        success: true,
+        output: String::new(),
+    }
+}
+
+/// 处理 bootstrap 子命令
+async fn handle_bootstrap_command(
+    state_file: &Path,
+    dry_run: bool,
+    verbose: bool,
+    wait: bool,
+) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+
+    // 与 update 互斥，避免同时修改同一个 Cellar 或工具链
+    let _run_lock = if dry_run {
+        None
+    } else {
+        Some(acquire_run_lock(wait, &lang)?)
+    };
+
+    let state = Snapshot::load(state_file)?;
+    let tmp = tempdir()?;
+    let live = Snapshot::capture(&ShellRunner, tmp.path())?;
+    let steps = bootstrap::plan(&state, &live)?;
+    let progress = BootstrapProgress::load(state_file);
+
+    if steps.is_empty() {
+        progress.clear();
+        print_success(&format!(
+            "{} {}",
+            icons.success(),
+            t!(&lang, "bootstrap-nothing-to-do")
+        ));
+        return Ok(());
+    }
+
+    // 先显示安装计划
+    print_info(&format!(
+        "{} {}",
+        icons.clipboard(),
+        t!(&lang, "bootstrap-plan", count = steps.len())
+    ));
+    for step in &steps {
+        let done = if progress.is_completed(step) {
+            format!(" {}", t!(&lang, "bootstrap-step-done"))
+        } else {
+            String::new()
+        };
+        println!("   [{}] {}{}", step.tool.display_name(), step.command, done);
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    let pending: Vec<BootstrapStep> = steps
+        .into_iter()
+        .filter(|step| !progress.is_completed(step))
+        .collect();
+    if progress.completed_count() > 0 {
+        print_info(&format!(
+            "{} {}",
+            icons.info(),
+            t!(&lang, "bootstrap-resuming", count = pending.len())
+        ));
+    }
+    println!();
+
+    // 按工具分组，工具之间交给并行调度器按依赖顺序执行
+    let mut groups: HashMap<Tool, Vec<BootstrapStep>> = HashMap::new();
+    for step in &pending {
+        groups
+            .entry(step.tool.clone())
+            .or_default()
+            .push(step.clone());
+    }
+    let tools: Vec<Tool> = groups.keys().cloned().collect();
+    let scheduler = ParallelScheduler::with_dependency_graph(bootstrap::dependency_graph(&pending));
+
+    let groups = Arc::new(groups);
+    let progress = Arc::new(Mutex::new(progress));
+    let progress_for_finalize = progress.clone();
+    let tmpdir = tmp.path().to_path_buf();
+    let run_fn = move |tool: Tool| {
+        let groups = groups.clone();
+        let progress = progress.clone();
+        let tmpdir = tmpdir.clone();
+        tokio::spawn(async move {
+            let steps = groups.get(&tool).cloned().unwrap_or_default();
+            Ok(run_bootstrap_steps(
+                &tool, &steps, &progress, &tmpdir, verbose,
+            ))
+        })
+    };
+    let results = scheduler.execute_parallel(tools, run_fn).await?;
+
+    println!();
+    if results.iter().all(|result| result.success) {
+        if let Ok(progress) = progress_for_finalize.lock() {
+            progress.clear();
+        }
+        print_success(&format!(
+            "{} {}",
+            icons.success(),
+            t!(&lang, "bootstrap-complete")
+        ));
+        return Ok(());
+    }
+
+    print_warning(&format!(
+        "{} {}",
+        icons.warning(),
+        t!(
+            &lang,
+            "bootstrap-failed",
+            state = state_file.display().to_string()
+        )
+    ));
+    std::process::exit(1);
+}
+
+/// 顺序升级某个工具下勾选的项目，单项失败不影响该工具的其他项目
+fn run_selected_upgrades(
+    tool: &Tool,
+    items: &[PendingUpgrade],
+    tmpdir: &Path,
+    verbose: bool,
+) -> TaskResult {
+    let icons = get_icon_manager();
+    let runner = ShellRunner;
+    let mut failed = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        let logfile = tmpdir.join(format!(
+            "{}upgrade_{}.log",
+            ui::dashboard::log_prefix(tool),
+            index
+        ));
+        match runner.run(&item.command(), &logfile, verbose) {
+            Ok((0, _)) => print_success(&format!(
+                "{} {} {} {} {}",
+                icons.success(),
+                item.name,
+                item.current,
+                icons.plain_arrow(),
+                item.latest
+            )),
+            Ok((rc, _)) => {
+                print_error(&format!("{} {} (exit {})", icons.failure(), item.name, rc));
+                failed.push(item.name.clone());
+            }
+            Err(e) => {
+                print_error(&format!("{} {}: {}", icons.failure(), item.name, e));
+                failed.push(item.name.clone());
+            }
+        }
+    }
+    TaskResult {
+        tool: tool.clone(),
+        success: failed.is_empty(),
+        output: failed.join(", "),
+    }
+}
+
+/// 工具在暂缓列表文件名中的名称
+fn deferred_key(tool: &Tool) -> &'static str {
+    match tool {
+        Tool::Homebrew => "brew",
+        Tool::Rustup => "rustup",
+        Tool::Mise => "mise",
+    }
+}
+
+/// 打印因隔离期暂缓的升级，以及无法确认隔离期而跳过的工具
+fn print_deferred_upgrades(tmpdir: &Path, lang: &str, color: bool) {
+    let icons = get_icon_manager();
+    for tool in [Tool::Homebrew, Tool::Rustup, Tool::Mise] {
+        let deferred = load_deferred(tmpdir, deferred_key(&tool));
+        if deferred.is_empty() {
+            continue;
+        }
+        let header = format!(
+            "{} {}",
+            icons.pause(),
+            t!(lang, "update-deferred-heading", tool = tool.display_name())
+        );
+        if ui::colors::supports_color() && color {
+            print_warning(&header);
+        } else {
+            println!("{}", header);
+        }
+        for item in &deferred {
+            println!(
+                "   {}",
+                t!(
+                    lang,
+                    "update-deferred-item",
+                    name = item.name.as_str(),
+                    version = item.version.as_str(),
+                    remaining = format_duration_short(item.remaining())
+                )
+            );
+        }
+    }
+    for tool in unchecked_tools(tmpdir) {
+        let line = format!(
+            "{} {}",
+            icons.pause(),
+            t!(lang, "update-unchecked", tool = tool.display_name())
+        );
+        if ui::colors::supports_color() && color {
+            print_warning(&line);
+        } else {
+            println!("{}", line);
+        }
+    }
+}
+
+/// 对选中的升级应用隔离期：未满隔离期的项目写入暂缓列表，返回可以立即升级的项目
+fn apply_quarantine(
+    items: Vec<PendingUpgrade>,
+    quarantine: &Quarantine,
+    tmpdir: &Path,
+) -> Vec<PendingUpgrade> {
+    let mut allowed_items = Vec::new();
+    for tool in [Tool::Homebrew, Tool::Rustup, Tool::Mise] {
+        let group: Vec<PendingUpgrade> = items
+            .iter()
+            .filter(|item| item.tool == tool)
+            .cloned()
+            .collect();
+        if group.is_empty() {
+            continue;
+        }
+        let candidates = group
+            .iter()
+            .map(|item| Candidate {
+                name: item.name.clone(),
+                candidate_version: item.latest.clone(),
+            })
+            .collect();
+        // 与各工具更新时使用相同的记录文件
+        let store = match tool {
+            Tool::Homebrew => "homebrew",
+            Tool::Rustup => "rustup",
+            Tool::Mise => "mise",
+        };
+        let (allowed, deferred) = quarantine.partition_selected(store, candidates);
+        if !deferred.is_empty() {
+            save_deferred(tmpdir, deferred_key(&tool), &deferred);
+        }
+        allowed_items.extend(
+            group
+                .into_iter()
+                .filter(|item| allowed.iter().any(|c| c.name == item.name)),
+        );
+    }
+    allowed_items
+}
+
+/// 处理 `update --interactive` 与 `update --plan`：只升级选定的项目
+async fn handle_selective_update(
+    plan: Option<&Path>,
+    save_plan: Option<&Path>,
+    dry_run: bool,
+    verbose: bool,
+    keep_logs: bool,
+    quarantine: &Quarantine,
+    run_lock: Option<RunLock>,
+) -> Result<()> {
+    use std::io::IsTerminal;
+
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+    let start_time = chrono::Utc::now();
+    if plan.is_none() && !std::io::stdout().is_terminal() {
+        anyhow::bail!("{}", t!(&lang, "select-requires-terminal"));
+    }
+    let run_dir = logs::RunDir::create(&ensure_cache_dir()?)?;
+
+    let items = if let Some(plan) = plan {
+        UpgradePlan::load(plan)?.items
+    } else {
+        let tools: Vec<Tool> = [
+            (Tool::Homebrew, "brew"),
+            (Tool::Rustup, "rustup"),
+            (Tool::Mise, "mise"),
+        ]
+        .into_iter()
+        .filter(|(_, bin)| which(bin).is_ok())
+        .map(|(tool, _)| tool)
+        .collect();
+
+        print_info(&format!(
+            "{} {}",
+            icons.info(),
+            t!(&lang, "select-checking")
+        ));
+        // 先刷新 Homebrew 的软件包索引，否则 brew outdated 可能遗漏新版本
+        if tools.contains(&Tool::Homebrew) && !dry_run {
+            brew_update(&ShellRunner, run_dir.path(), verbose)?;
+        }
+        let pending = interactive::collect_pending(&ShellRunner, run_dir.path(), &tools)?;
+        if pending.is_empty() {
+            print_success(&format!(
+                "{} {}",
+                icons.success(),
+                t!(&lang, "select-up-to-date")
+            ));
+            run_dir.discard();
+            return Ok(());
+        }
+
+        match interactive::tui::pick(pending, &lang)? {
+            Some(items) => items,
+            None => {
+                run_dir.discard();
+                return Ok(());
+            }
+        }
+    };
+
+    if items.is_empty() {
+        print_info(&format!(
+            "{} {}",
+            icons.info(),
+            t!(&lang, "select-nothing-selected")
+        ));
+        run_dir.discard();
+        return Ok(());
+    }
+
+    if let Some(path) = save_plan {
+        UpgradePlan::new(items).save(path)?;
+        print_success(&format!(
+            "{} {}",
+            icons.success(),
+            t!(
+                &lang,
+                "select-plan-saved",
+                path = path.display().to_string()
+            )
+        ));
+        run_dir.discard();
+        return Ok(());
+    }
+
+    // 选中的项目同样遵守隔离期，未满隔离期的项目本次不升级；与普通更新一样，模拟执行不记录
+    let items = if dry_run {
+        items
+    } else {
+        let items = apply_quarantine(items, quarantine, run_dir.path());
+        print_deferred_upgrades(run_dir.path(), &lang, true);
+        if items.is_empty() {
+            run_dir.discard();
+            return Ok(());
+        }
+        items
+    };
+
+    print_info(&format!(
+        "{} {}",
+        icons.clipboard(),
+        t!(&lang, "select-plan", count = items.len())
+    ));
+    for item in &items {
+        if dry_run {
+            println!("[dry-run] {}", item.command());
+        } else {
+            println!(
+                "   [{}] {} {} {} {}",
+                item.tool.display_name(),
+                item.name,
+                item.current,
+                icons.arrow(),
+                item.latest
+            );
+        }
+    }
+    if dry_run {
+        run_dir.discard();
+        return Ok(());
+    }
+    println!();
+
+    // 按工具分组，工具之间并行执行
+    let mut groups: HashMap<Tool, Vec<PendingUpgrade>> = HashMap::new();
+    for item in items {
+        groups.entry(item.tool.clone()).or_default().push(item);
+    }
+    let tools: Vec<Tool> = groups.keys().cloned().collect();
+    let scheduler = ParallelScheduler::new(tools.len().max(1));
+
+    let groups = Arc::new(groups);
+    let tmpdir = run_dir.path().to_path_buf();
+    let run_fn = move |tool: Tool| {
+        let groups = groups.clone();
+        let tmpdir = tmpdir.clone();
+        tokio::spawn(async move {
+            let items = groups.get(&tool).cloned().unwrap_or_default();
+            Ok(run_selected_upgrades(&tool, &items, &tmpdir, verbose))
+        })
+    };
+    let results = scheduler.execute_parallel(tools, run_fn).await?;
+
+    // 失败的运行总是保留日志，成功的运行在 --keep-logs 时保留
+    let record = RunRecord {
+        started_at: start_time,
+        finished_at: chrono::Utc::now(),
+        scheduled: false,
+        tools: tool_results(&results, run_dir.path()),
+    };
+    if let Err(e) = run_dir.finish(&record, keep_logs) {
+        eprintln!(
+            "{}",
+            t!(&lang, "log-save-failed", error = format!("{:#}", e))
+        );
+    }
+
+    let failed: Vec<String> = results
+        .iter()
+        .filter(|result| !result.success)
+        .map(|result| result.output.clone())
+        .collect();
+    println!();
+    if failed.is_empty() {
+        print_success(&format!(
+            "{} {}",
+            icons.success(),
+            t!(&lang, "select-all-upgraded")
+        ));
+        return Ok(());
+    }
+    print_error(&format!("{} {}", icons.failure(), failed.join(", ")));
+    drop(run_lock);
+    std::process::exit(1);
+}
+
+/// 处理 doctor 子命令：存在失败项时以退出码 1 退出
+fn handle_doctor_command(format: OutputFormat) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+    let tmp = tempdir()?;
+    let diagnostics = doctor::run_checks(&ShellRunner, tmp.path(), &lang);
+
+    let count = |status: CheckStatus| diagnostics.iter().filter(|d| d.status == status).count();
+    let failed = count(CheckStatus::Fail);
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&diagnostics)?),
+        OutputFormat::Text => {
+            for diagnostic in &diagnostics {
+                match diagnostic.status {
+                    CheckStatus::Ok => {
+                        print_success(&format!("{} {}", icons.success(), diagnostic.message))
+                    }
+                    CheckStatus::Warn => {
+                        print_warning(&format!("{} {}", icons.warning(), diagnostic.message))
+                    }
+                    CheckStatus::Fail => {
+                        print_error(&format!("{} {}", icons.failure(), diagnostic.message))
+                    }
+                }
+                if let Some(fix) = &diagnostic.fix {
+                    println!("   {} {}", icons.wrench(), fix);
+                }
+            }
+
+            println!();
+            print_info(&format!(
+                "{} {}",
+                icons.clipboard(),
+                t!(
+                    &lang,
+                    "doctor-summary",
+                    ok = count(CheckStatus::Ok),
+                    warnings = count(CheckStatus::Warn),
+                    failures = failed
+                )
+            ));
+        }
+    }
+
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// 扫描 PATH，建立命令归属表
+fn scan_ownership(tmpdir: &Path) -> OwnershipMap {
+    let ctx = OwnershipContext::detect(&ShellRunner, tmpdir);
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    OwnershipMap::scan(&path_var, &ctx)
+}
+
+fn handle_conflicts_command(format: OutputFormat) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+    let tmp = tempdir()?;
+    let conflicts = scan_ownership(tmp.path()).conflicts();
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&conflicts)?),
+        OutputFormat::Text => {
+            if conflicts.is_empty() {
+                print_success(&format!(
+                    "{} {}",
+                    icons.success(),
+                    t!(&lang, "conflicts-none")
+                ));
+                return Ok(());
+            }
+
+            for conflict in &conflicts {
+                print_warning(&format!("{} {}", icons.warning(), conflict.command));
+                println!(
+                    "   {} {}",
+                    icons.success(),
+                    t!(
+                        &lang,
+                        "conflicts-winner",
+                        path = conflict.winner.path.display().to_string(),
+                        owner = conflict.winner.owner.describe()
+                    )
+                );
+                for shadowed in &conflict.shadowed {
+                    println!(
+                        "   {} {}",
+                        icons.pause(),
+                        t!(
+                            &lang,
+                            "conflicts-shadowed",
+                            path = shadowed.path.display().to_string(),
+                            owner = shadowed.owner.describe()
+                        )
+                    );
+                }
+            }
+
+            println!();
+            print_info(&format!(
+                "{} {}",
+                icons.clipboard(),
+                t!(&lang, "conflicts-summary", count = conflicts.len())
+            ));
+        }
+    }
+
+    if !conflicts.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// 解析命令归属，找不到命令时报错
+fn resolve_command(command: &str, tmpdir: &Path, lang: &str) -> Result<ownership::Resolution> {
+    let map = scan_ownership(tmpdir);
+    ownership::resolve(&map, command, &ShellRunner, tmpdir)
+        .ok_or_else(|| anyhow::anyhow!("{}", t!(lang, "which-not-found", command = command)))
+}
+
+fn handle_which_command(command: &str, format: OutputFormat) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+    let tmp = tempdir()?;
+    let resolution = resolve_command(command, tmp.path(), &lang)?;
+
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&resolution)?);
+        return Ok(());
+    }
+
+    let mut rows = vec![
+        ("which-path", resolution.path.display().to_string()),
+        ("which-resolved", resolution.resolved.display().to_string()),
+        ("which-provider", resolution.owner.describe()),
+    ];
+    if let Some(toolchain) = &resolution.toolchain {
+        rows.push(("which-toolchain", toolchain.clone()));
+    }
+    if let Some(component) = &resolution.component {
+        rows.push(("which-component", component.clone()));
+    }
+    rows.push((
+        "which-version",
+        resolution
+            .version
+            .clone()
+            .unwrap_or_else(|| t!(&lang, "which-unknown")),
+    ));
+    rows.push((
+        "which-upgrade",
+        resolution
+            .upgrade
+            .clone()
+            .unwrap_or_else(|| t!(&lang, "which-unmanaged")),
+    ));
+
+    print_info(&format!("{} {}", icons.package(), resolution.command));
+    for (label, value) in rows {
+        println!("   {}: {}", t!(&lang, label), value);
+    }
+    for shadowed in &resolution.shadowed {
+        print_warning(&format!(
+            "{} {}",
+            icons.warning(),
+            t!(
+                &lang,
+                "conflicts-shadowed",
+                path = shadowed.path.display().to_string(),
+                owner = shadowed.owner.describe()
+            )
+        ));
+    }
+    Ok(())
+}
+
+/// 检查维护窗口与冻结期：不允许时，`--force` 或模拟执行只输出警告，否则提示下一个允许的时间并退出
+fn enforce_policy(policy: &UpdatePolicy, force: bool, dry_run: bool, lang: &str) {
+    let decision = policy.check_now();
+    if decision == PolicyDecision::Allowed {
+        return;
+    }
+    let icons = get_icon_manager();
+    let blocked_msg = format!(
+        "{} {}",
+        icons.warning(),
+        describe_policy_decision(&decision, policy, lang)
+    );
+    if force || dry_run {
+        let note = if force {
+            t!(lang, "policy-forced")
+        } else {
+            t!(lang, "policy-dry-run")
+        };
+        print_warning(&format!("{} {}", blocked_msg, note));
+    } else {
+        print_error(&blocked_msg);
+        println!("{}", describe_next_allowed(policy, lang));
+        println!("{}", t!(lang, "policy-force-hint"));
+        std::process::exit(EXIT_POLICY_BLOCKED);
+    }
+}
+
+/// 命令所属项目的待升级信息；不是 Homebrew、rustup、mise 管理的项目或已是最新版本时返回 `None`
+fn pending_for_owner(resolution: &ownership::Resolution, tmpdir: &Path) -> Option<PendingUpgrade> {
+    let runner = ShellRunner;
+    let (pending, name) = match &resolution.owner {
+        Owner::Homebrew { package, .. } => (
+            commands::homebrew::pending_upgrades(&runner, tmpdir),
+            package.as_str(),
+        ),
+        Owner::Rustup => (
+            commands::rustup::pending_upgrades(&runner, tmpdir),
+            resolution.toolchain.as_deref()?,
+        ),
+        Owner::Mise { tool, .. } => (
+            commands::mise::pending_upgrades(&runner, tmpdir),
+            tool.as_str(),
+        ),
+        Owner::Cargo { .. } | Owner::System => return None,
+    };
+    pending.ok()?.into_iter().find(|item| item.name == name)
+}
+
+/// 处理 upgrade 子命令：只升级提供指定命令的项目
+fn handle_upgrade_command(
+    command: &str,
+    dry_run: bool,
+    verbose: bool,
+    wait: bool,
+    min_age: Option<String>,
+    force: bool,
+) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+    let tmp = tempdir()?;
+    let resolution = resolve_command(command, tmp.path(), &lang)?;
+
+    let Some(cmd) = &resolution.upgrade else {
+        print_error(&format!(
+            "{} {}",
+            icons.failure(),
+            t!(
+                &lang,
+                "upgrade-unmanaged",
+                path = resolution.path.display().to_string(),
+                owner = resolution.owner.describe()
+            )
+        ));
+        std::process::exit(1);
+    };
+
+    // 与其他升级方式一致：遵守维护窗口、冻结期和新版本隔离期
+    let config = Config::load()?;
+    let policy = UpdatePolicy::from_config(&config.policy)?;
+    enforce_policy(&policy, force, dry_run, &lang);
+
+    if dry_run {
+        println!("[dry-run] {}", cmd);
+        return Ok(());
+    }
+
+    let run_lock = acquire_run_lock(wait, &lang)?;
+    let min_age = match min_age.or(config.quarantine.min_age) {
+        Some(value) => Some(parse_duration(&value)?),
+        None => None,
+    };
+    if let Some(pending) = pending_for_owner(&resolution, tmp.path()) {
+        let allowed = apply_quarantine(vec![pending], &Quarantine::new(min_age), tmp.path());
+        if allowed.is_empty() {
+            print_deferred_upgrades(tmp.path(), &lang, true);
+            return Ok(());
+        }
+    }
+    print_info(&format!("{} {}", icons.wrench(), cmd));
+    let logfile = tmp.path().join("upgrade.log");
+    let (rc, output) = ShellRunner.run(cmd, &logfile, verbose)?;
+    drop(run_lock);
+    if rc != 0 {
+        print_error(&format!("{} {} (exit {})", icons.failure(), cmd, rc));
+        if !verbose && !output.is_empty() {
+            println!("{}", output);
+        }
+        std::process::exit(1);
+    }
+
+    // 重新解析以显示升级后的版本
+    let upgraded = resolve_command(command, tmp.path(), &lang)?;
+    let version = upgraded.version.unwrap_or_else(|| "?".to_string());
+    print_success(&format!(
+        "{} {}",
+        icons.success(),
+        t!(&lang, "upgrade-done", command = command, version = version)
+    ));
+    Ok(())
+}
+
+/// 本次升级的项目中被 PATH 中其他副本遮蔽的命令：(命令, 被升级的副本, 生效的副本)
+fn shadowed_upgrades(tmpdir: &Path) -> Vec<(String, Owner, ownership::Provision)> {
+    let mut upgraded: Vec<(Tool, String)> = Vec::new();
+    for (tool, tool_key) in [
+        (Tool::Homebrew, "brew"),
+        (Tool::Rustup, "rustup"),
+        (Tool::Mise, "mise"),
+    ] {
+        if let Ok(Some(details)) =
+            commands::upgrade_details::UpgradeDetailsManager::load_upgrade_details(tmpdir, tool_key)
+        {
+            for detail in details.details {
+                upgraded.push((tool.clone(), detail.name));
+            }
+        }
+    }
+    if upgraded.is_empty() {
+        return Vec::new();
+    }
+
+    let targets_upgrade = |owner: &Owner| {
+        upgraded.iter().any(|(tool, name)| match (tool, owner) {
+            (Tool::Homebrew, Owner::Homebrew { package, .. }) => package == name,
+            (Tool::Rustup, Owner::Rustup) => true,
+            (Tool::Mise, Owner::Mise { tool, .. }) => tool == name,
+            _ => false,
+        })
+    };
+    scan_ownership(tmpdir)
+        .shadowed_copies(targets_upgrade)
+        .into_iter()
+        .map(|(conflict, shadowed)| (conflict.command, shadowed.owner, conflict.winner))
+        .collect()
+}
+
+/// 打印快照差异，按工具分组
+fn print_diff_report(entries: &[DiffEntry], lang: &str) {
+    let icons = get_icon_manager();
+    let mut current_tool = None;
+    for entry in entries {
+        if current_tool != Some(&entry.tool) {
+            println!();
+            print_header(entry.tool.display_name());
+            current_tool = Some(&entry.tool);
+        }
+        let version =
+            |v: &Option<String>| v.as_deref().map(|v| format!(" {}", v)).unwrap_or_default();
+        match entry.status {
+            DiffStatus::Missing => print_error(&format!(
+                "  - {} {}{}",
+                entry.category,
+                entry.name,
+                version(&entry.base)
+            )),
+            DiffStatus::Extra => print_success(&format!(
+                "  + {} {}{}",
+                entry.category,
+                entry.name,
+                version(&entry.other)
+            )),
+            DiffStatus::Changed => print_warning(&format!(
+                "  ~ {} {}: {} {} {}{}",
+                entry.category,
+                entry.name,
+                entry.base.as_deref().unwrap_or("-"),
+                icons.plain_arrow(),
+                entry.other.as_deref().unwrap_or("-"),
+                entry
+                    .distance
+                    .map(|d| format!(" [{}]", d))
+                    .unwrap_or_default()
+            )),
+        }
+    }
+
+    let count = |status: DiffStatus| entries.iter().filter(|e| e.status == status).count();
+    println!();
+    if entries.is_empty() {
+        print_success(&format!(
+            "{} {}",
+            icons.success(),
+            t!(lang, "diff-identical")
+        ));
+    } else {
+        print_info(&format!(
+            "{} {}",
+            icons.clipboard(),
+            t!(
+                lang,
+                "diff-summary",
+                missing = count(DiffStatus::Missing),
+                extra = count(DiffStatus::Extra),
+                changed = count(DiffStatus::Changed)
+            )
+        ));
+    }
+}
+
+/// 处理 diff 子命令：存在差异时以退出码 1 退出
+fn handle_diff_command(
+    base: Option<&Path>,
+    other: Option<&Path>,
+    against: Option<&Path>,
+    format: OutputFormat,
+) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let (base, other) = match (base, other, against) {
+        (Some(base), Some(other), _) => (Snapshot::load(base)?, Snapshot::load(other)?),
+        (_, _, Some(against)) => {
+            let tmp = tempdir()?;
+            (
+                Snapshot::load(against)?,
+                Snapshot::capture(&ShellRunner, tmp.path())?,
+            )
         }
+        // clap 已保证参数组合有效
+        _ => unreachable!("diff requires BASE OTHER or --against"),
     };
 
-    // 禁用输出抑制，恢复正常输出
-    disable_output_suppression();
+    let entries = diff(&base, &other);
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+        OutputFormat::Text => print_diff_report(&entries, &lang),
+    }
 
-    // 如果启用了 keep_logs，保存调试日志到缓存目录
-    if keep_logs {
-        if let Err(e) = save_debug_logs(tmpdir, &result.tool) {
-            eprintln!("Warning: Failed to save debug logs: {}", e);
-        }
+    if !entries.is_empty() {
+        std::process::exit(1);
     }
+    Ok(())
+}
 
-    Ok(result)
+/// 处理 snapshot 子命令
+fn handle_snapshot_command(output: Option<&Path>, format: Option<SnapshotFormat>) -> Result<()> {
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+    let format = format
+        .or_else(|| output.map(SnapshotFormat::from_path))
+        .unwrap_or(SnapshotFormat::Json);
+
+    let tmp = tempdir()?;
+    let snapshot = Snapshot::capture(&ShellRunner, tmp.path())?;
+
+    let (content, tool) = match format {
+        SnapshotFormat::Json => (Some(snapshot.to_json()? + "\n"), "devtool"),
+        SnapshotFormat::Brewfile => (snapshot.to_brewfile(), "brew"),
+        SnapshotFormat::RustToolchain => (snapshot.to_rust_toolchain(), "rustup"),
+        SnapshotFormat::MiseConfig => (snapshot.to_mise_config(), "mise"),
+    };
+    let Some(content) = content else {
+        print_error(&format!(
+            "{} {}",
+            icons.failure(),
+            t!(&lang, "snapshot-tool-missing", tool = tool)
+        ));
+        std::process::exit(1);
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            print_success(&format!(
+                "{} {}",
+                icons.success(),
+                t!(&lang, "snapshot-saved", path = path.display().to_string())
+            ));
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
 
+    // 配置文件有误时在这里先使用默认值，具体的错误由各命令加载配置时报告
+    let config = Config::load().unwrap_or_default();
+
+    // 界面语言：--lang 优先，其次是配置文件的 lang，最后根据系统环境检测
+    let lang_override = args.lang.clone().or(config.lang.clone());
+    if let Some(lang) = lang_override {
+        if !i18n::set_language(&lang) {
+            eprintln!(
+                "{}",
+                t!(
+                    &i18n::detect_system_language(),
+                    "lang-unsupported",
+                    lang = lang.as_str(),
+                    available = i18n::available_languages().join(", ")
+                )
+            );
+        }
+    }
+
+    // 图标和颜色主题；颜色开关统一由 NO_COLOR / CLICOLOR_FORCE 等环境变量决定
+    match Theme::from_config(&config.theme) {
+        Ok(theme) => ui::theme::set_theme(theme),
+        Err(e) => eprintln!(
+            "{}",
+            t!(
+                &i18n::detect_system_language(),
+                "theme-invalid",
+                error = format!("{:#}", e)
+            )
+        ),
+    }
+    colored::control::set_override(ui::colors::supports_color());
+    for (name, replacement) in ui::theme::deprecated_env_vars() {
+        eprintln!(
+            "{}",
+            t!(
+                &i18n::detect_system_language(),
+                "env-deprecated",
+                name = name,
+                replacement = replacement
+            )
+        );
+    }
+
+    // 日志和报告中的令牌脱敏；配置的模式无效时只使用内置规则
+    if args.no_redact {
+        redact::set_redactor(Redactor::disabled());
+    } else {
+        match Redactor::from_config(&config.redact) {
+            Ok(redactor) => redact::set_redactor(redactor),
+            Err(e) => eprintln!(
+                "{}",
+                t!(
+                    &i18n::detect_system_language(),
+                    "redact-invalid",
+                    error = format!("{:#}", e)
+                )
+            ),
+        }
+    }
+
+    if args.print_locale {
+        print_locale_report(&i18n::locale_report());
+        return Ok(());
+    }
+
     // 处理补全生成命令
     if let Some(Commands::Completion { shell }) = &args.command {
         let mut cmd = Args::command();
@@ -354,40 +2490,256 @@ async fn main() -> Result<()> {
         return handle_feedback_command(feedback_type, message, *verbose);
     }
 
+    // 处理 serve 子命令
+    if let Some(Commands::Serve {
+        listen,
+        socket,
+        token,
+    }) = &args.command
+    {
+        return handle_serve_command(listen, socket.as_deref(), token.as_deref()).await;
+    }
+
+    if let Some(Commands::Schedule { action }) = &args.command {
+        return handle_schedule_command(action);
+    }
+
+    if let Some(Commands::Status { watch, format }) = &args.command {
+        return handle_status_command(*watch, *format);
+    }
+
+    if let Some(Commands::Logs {
+        tool,
+        run,
+        step,
+        follow,
+        action,
+    }) = &args.command
+    {
+        return handle_logs_command(
+            tool.as_ref(),
+            *run,
+            step.as_deref(),
+            *follow,
+            action.as_ref(),
+        );
+    }
+
+    // 处理团队清单相关子命令
+    if let Some(Commands::Check { manifest, format }) = &args.command {
+        return handle_check_command(manifest, *format);
+    }
+    if let Some(Commands::Sync {
+        manifest,
+        dry_run,
+        verbose,
+        wait,
+    }) = &args.command
+    {
+        return handle_sync_command(manifest, *dry_run, *verbose, *wait);
+    }
+
+    // 处理 bootstrap 子命令
+    if let Some(Commands::Bootstrap {
+        state,
+        dry_run,
+        verbose,
+        wait,
+    }) = &args.command
+    {
+        return handle_bootstrap_command(state, *dry_run, *verbose, *wait).await;
+    }
+
+    // 处理 doctor 子命令
+    if let Some(Commands::Doctor { format }) = &args.command {
+        return handle_doctor_command(*format);
+    }
+
+    // 处理 conflicts 子命令
+    if let Some(Commands::Conflicts { format }) = &args.command {
+        return handle_conflicts_command(*format);
+    }
+
+    // 处理 which 子命令
+    if let Some(Commands::Which { command, format }) = &args.command {
+        return handle_which_command(command, *format);
+    }
+
+    // 处理 upgrade 子命令
+    if let Some(Commands::Upgrade {
+        command,
+        dry_run,
+        verbose,
+        wait,
+        min_age,
+        force,
+    }) = &args.command
+    {
+        return handle_upgrade_command(command, *dry_run, *verbose, *wait, min_age.clone(), *force);
+    }
+
+    // 处理 diff 子命令
+    if let Some(Commands::Diff {
+        base,
+        other,
+        against,
+        format,
+    }) = &args.command
+    {
+        return handle_diff_command(
+            base.as_deref(),
+            other.as_deref(),
+            against.as_deref(),
+            *format,
+        );
+    }
+
+    // 处理 snapshot 子命令
+    if let Some(Commands::Snapshot { output, format }) = &args.command {
+        return handle_snapshot_command(output.as_deref(), *format);
+    }
+
     // 获取 update 命令的参数，如果没有指定命令则使用默认值
-    let (dry_run, verbose, no_color, keep_logs, parallel, sequential, jobs, no_banner, _compact) =
-        match &args.command {
-            Some(Commands::Update {
+    let (
+        dry_run,
+        verbose,
+        no_color,
+        keep_logs,
+        parallel,
+        sequential,
+        jobs,
+        no_banner,
+        compact,
+        accessible,
+        min_age,
+        force,
+        scheduled,
+    ) = match &args.command {
+        Some(Commands::Update {
+            dry_run,
+            verbose,
+            no_color,
+            keep_logs,
+            parallel,
+            sequential,
+            jobs,
+            no_banner,
+            compact,
+            accessible,
+            min_age,
+            force,
+            scheduled,
+            ..
+        }) => (
+            *dry_run,
+            *verbose,
+            *no_color,
+            *keep_logs,
+            *parallel,
+            *sequential,
+            *jobs,
+            *no_banner,
+            *compact,
+            *accessible,
+            min_age.clone(),
+            *force,
+            *scheduled,
+        ),
+        None => (
+            false, false, false, false, true, false, 3, false, false, false, None, false, false,
+        ), // 默认值：并行执行，3个任务
+        _ => return Ok(()),
+    };
+
+    // 加载配置文件，命令行参数优先
+    let config = Config::load()?;
+
+    // 无障碍输出优先于其他显示方式，并且不使用颜色
+    let accessible = ui::accessible::enabled(accessible, config.accessible);
+    let no_color = no_color || accessible;
+    // 全屏仪表盘接管终端，命令输出只写入日志并显示在面板中
+    let dashboard = !compact
+        && !accessible
+        && use_dashboard(matches!(
+            &args.command,
+            Some(Commands::Update { tui: true, .. })
+        ));
+    // 非终端或 CI 环境中自动使用紧凑输出，避免进度条残留在日志中
+    let compact = !accessible && (compact || (!dashboard && ui::compact::auto_enabled()));
+    let display_mode = if accessible {
+        DisplayMode::Accessible
+    } else if dashboard {
+        DisplayMode::Dashboard
+    } else if compact {
+        DisplayMode::Compact
+    } else {
+        DisplayMode::Bars
+    };
+    let min_age = match min_age.or(config.quarantine.min_age) {
+        Some(value) => Some(parse_duration(&value)?),
+        None => None,
+    };
+    let options = UpdateOptions {
+        dry_run,
+        verbose: verbose && !dashboard,
+        quarantine: Quarantine::new(min_age),
+    };
+
+    // 检查维护窗口与冻结期
+    let policy = UpdatePolicy::from_config(&config.policy)?;
+    if scheduled && policy.check_now() != PolicyDecision::Allowed {
+        // 计划任务静默延后到下一个允许的时间
+        return Ok(());
+    }
+    enforce_policy(&policy, force, dry_run, &i18n::detect_system_language());
+
+    // 防止与其他 devtool 运行（如定时任务）同时修改工具链
+    let wait = matches!(&args.command, Some(Commands::Update { wait: true, .. }));
+    let saving_plan = matches!(
+        &args.command,
+        Some(Commands::Update {
+            save_plan: Some(_),
+            ..
+        })
+    );
+    let run_lock = if dry_run || saving_plan {
+        None
+    } else {
+        Some(acquire_run_lock(wait, &i18n::detect_system_language())?)
+    };
+
+    // 按保留策略清理旧的运行日志；持有运行锁时没有其他运行正在写入日志
+    if run_lock.is_some() {
+        prune_run_logs(&config.logs, verbose);
+    }
+
+    // 交互式选择或执行保存的升级计划
+    if let Some(Commands::Update {
+        interactive,
+        save_plan,
+        plan,
+        ..
+    }) = &args.command
+    {
+        if *interactive || plan.is_some() {
+            return handle_selective_update(
+                plan.as_deref(),
+                save_plan.as_deref(),
                 dry_run,
                 verbose,
-                no_color,
                 keep_logs,
-                parallel,
-                sequential,
-                jobs,
-                no_banner,
-                compact,
-            }) => (
-                *dry_run,
-                *verbose,
-                *no_color,
-                *keep_logs,
-                *parallel,
-                *sequential,
-                *jobs,
-                *no_banner,
-                *compact,
-            ),
-            None => (false, false, false, false, true, false, 3, false, false), // 默认值：并行执行，3个任务
-            _ => return Ok(()),
-        };
+                &options.quarantine,
+                run_lock,
+            )
+            .await;
+        }
+    }
 
     // 检测系统语言并初始化本地化
     let system_lang = i18n::detect_system_language();
     if verbose {
         println!("Debug: Detected language: {}", system_lang);
     }
-    let localized = LocalizedStrings::new(&system_lang);
 
     // 初始化颜色支持
     if no_color {
@@ -400,63 +2752,40 @@ async fn main() -> Result<()> {
     // 记录开始时间
     let start_time = chrono::Local::now();
 
-    if !no_banner {
+    if !no_banner && !compact && !accessible {
         if ui::colors::supports_color() && !no_color {
-            print_banner(&format!(
-                "{}{}",
-                localized.banner,
-                start_time.format("%Y-%m-%d %H:%M:%S")
+            print_banner(&t!(
+                &system_lang,
+                "update-banner",
+                time = start_time.format("%Y-%m-%d %H:%M:%S").to_string()
             ));
         } else {
             println!(
-                "{}{}",
-                localized.banner,
-                start_time.format("%Y-%m-%d %H:%M:%S")
+                "{}",
+                t!(
+                    &system_lang,
+                    "update-banner",
+                    time = start_time.format("%Y-%m-%d %H:%M:%S").to_string()
+                )
             );
         }
     }
 
     // 构建可用工具列表
-    let mut available_tools: Vec<Tool> = Vec::new();
-    let mut skipped: Vec<&str> = Vec::new();
-
-    // 检查并添加 Homebrew
-    if which("brew").is_ok() {
-        available_tools.push(Tool::Homebrew);
-    } else {
-        skipped.push("Homebrew");
-    }
-
-    // 检查并添加 Rustup
-    if which("rustup").is_ok() {
-        available_tools.push(Tool::Rustup);
-    } else {
-        skipped.push("Rust (rustup)");
-    }
-
-    // 检查并添加 Mise
-    if which("mise").is_ok() {
-        available_tools.push(Tool::Mise);
-    } else {
-        skipped.push("Mise");
-    }
+    let (available_tools, skipped) = detect_available_tools();
 
     let total = available_tools.len();
     if total == 0 {
         let icons = get_icon_manager();
-        let warning_msg = if system_lang == "zh" {
-            format!(
-                "{} 未检测到可执行步骤。跳过： {}",
-                icons.warning(),
-                skipped.join(", ")
+        let warning_msg = format!(
+            "{} {}",
+            icons.warning(),
+            t!(
+                &system_lang,
+                "update-no-tools",
+                skipped = skipped.join(", ")
             )
-        } else {
-            format!(
-                "{} No executable steps detected. Skipped: {}",
-                icons.warning(),
-                skipped.join(", ")
-            )
-        };
+        );
 
         if ui::colors::supports_color() && !no_color {
             print_warning(&warning_msg);
@@ -466,36 +2795,55 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
-    // 创建临时目录用于日志
-    let tmp = tempdir()?;
-    let _run_tmp = tmp.path().to_path_buf();
+    // 创建本次运行的日志目录，所有步骤的日志都写在这里
+    let run_dir = logs::RunDir::create(&ensure_cache_dir()?)?;
 
     // 不再使用自建进度条，完全使用 indicatif
     // let mut pb_opt = Some(Bar::new(total, "devtool"));
 
     // 打印工具列表
     let icons = get_icon_manager();
-    let tools_msg = format!(
-        "{} {}",
-        icons.clipboard(),
-        localized.steps_count.replace("{}", &total.to_string())
-    );
-    if ui::colors::supports_color() && !no_color {
-        print_info(&tools_msg);
+    if accessible {
+        let names: Vec<&str> = available_tools.iter().map(Tool::display_name).collect();
+        println!(
+            "{}",
+            t!(
+                &system_lang,
+                "accessible-steps",
+                count = total,
+                tools = names.join(&t!(&system_lang, "list-separator"))
+            )
+        );
+    } else if compact {
+        let names: Vec<&str> = available_tools.iter().map(Tool::display_name).collect();
+        log_line(&t!(
+            &system_lang,
+            "compact-steps",
+            count = total,
+            tools = names.join(", ")
+        ));
     } else {
-        println!("{}", tools_msg);
-    }
-    for (i, tool) in available_tools.iter().enumerate() {
-        let tool_description = get_tool_description(tool);
-        println!("  {}) {}", i + 1, tool_description);
+        let tools_msg = format!(
+            "{} {}",
+            icons.clipboard(),
+            t!(&system_lang, "steps-count", count = total)
+        );
+        if ui::colors::supports_color() && !no_color {
+            print_info(&tools_msg);
+        } else {
+            println!("{}", tools_msg);
+        }
+        for (i, tool) in available_tools.iter().enumerate() {
+            let tool_description = get_tool_description(tool, &system_lang);
+            println!("  {}) {}", i + 1, tool_description);
+        }
     }
 
     // 完全使用 indicatif 进度条，不再使用自建进度条
 
     // 执行工具更新
     let mut results: Vec<TaskResult> = Vec::new();
-    let mut short_updates: std::collections::HashMap<String, Vec<String>> =
-        std::collections::HashMap::new();
+    let mut short_updates: HashMap<Tool, Vec<String>> = HashMap::new();
 
     // 确定执行模式：如果指定了 sequential，则顺序执行；否则并行执行
     let use_parallel = parallel && !sequential;
@@ -503,16 +2851,18 @@ async fn main() -> Result<()> {
     if use_parallel {
         // 并行执行
         if verbose {
-            println!("{} 并行执行模式 (最大并发数: {})", icons.rocket(), jobs);
+            println!(
+                "{} {}",
+                icons.rocket(),
+                t!(&system_lang, "update-parallel-mode", jobs = jobs)
+            );
         }
         results = execute_parallel_updates(
             available_tools,
             jobs,
-            dry_run,
-            verbose,
-            keep_logs,
-            _run_tmp.clone(),
-            &localized,
+            options.clone(),
+            run_dir.path().to_path_buf(),
+            new_progress_display(display_mode, run_dir.path()),
         )
         .await?;
 
@@ -520,37 +2870,32 @@ async fn main() -> Result<()> {
         for result in &results {
             if result.success {
                 // 检查是否有升级详情文件存在
-                let details = read_upgrade_details(&_run_tmp, &result.tool);
+                let details = read_upgrade_details(run_dir.path(), &result.tool);
                 if !details.is_empty() {
-                    let key = match result.tool {
-                        Tool::Homebrew => "Homebrew：升级软件包".to_string(),
-                        Tool::Rustup => "Rust：更新工具链".to_string(),
-                        Tool::Mise => "Mise：更新托管工具".to_string(),
-                    };
-                    short_updates.insert(key, details);
+                    short_updates.insert(result.tool.clone(), details);
                 }
             }
         }
     } else {
         // 顺序执行 - 使用简化的进度条管理器
         if verbose {
-            println!("🔄 顺序执行模式");
+            println!(
+                "{} {}",
+                icons.refresh(),
+                t!(&system_lang, "update-sequential-mode")
+            );
         }
 
-        // 创建简化的进度条管理器
-        let mut progress_manager = SimpleProgressManager::new();
+        // 创建进度显示（进度条或全屏仪表盘）
+        let mut progress_manager = new_progress_display(display_mode, run_dir.path());
         progress_manager.create_progress_bars(&available_tools);
 
-        // 更新所有工具状态为执行中
-        for tool in &available_tools {
-            progress_manager.update_state(tool, SimpleProgressState::Executing);
-        }
-
         // 添加短暂延迟确保进度条显示
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
         // 顺序执行每个工具
         for tool in available_tools.iter() {
+            progress_manager.update_state(tool, SimpleProgressState::Executing);
             let result = if dry_run {
                 TaskResult {
                     tool: tool.clone(),
@@ -558,13 +2903,19 @@ async fn main() -> Result<()> {
                     output: format!("{} (dry run)", tool.display_name()),
                 }
             } else {
-                match execute_tool_update(tool.clone(), dry_run, verbose, keep_logs, &_run_tmp)
-                    .await
-                {
+                match execute_tool_update(tool.clone(), &options, run_dir.path()) {
                     Ok(result) => result,
                     Err(e) => {
                         if verbose {
-                            eprintln!("Error executing {}: {}", tool.display_name(), e);
+                            eprintln!(
+                                "{}",
+                                t!(
+                                    &system_lang,
+                                    "update-tool-error",
+                                    tool = tool.display_name(),
+                                    error = e.to_string()
+                                )
+                            );
                         }
                         TaskResult {
                             tool: tool.clone(),
@@ -587,14 +2938,9 @@ async fn main() -> Result<()> {
 
             // 收集升级详情
             if result.success {
-                let details = read_upgrade_details(&_run_tmp, tool);
+                let details = read_upgrade_details(run_dir.path(), tool);
                 if !details.is_empty() {
-                    let key = match tool {
-                        Tool::Homebrew => "Homebrew：升级软件包".to_string(),
-                        Tool::Rustup => "Rust：更新工具链".to_string(),
-                        Tool::Mise => "Mise：更新托管工具".to_string(),
-                    };
-                    short_updates.insert(key, details);
+                    short_updates.insert(tool.clone(), details);
                 }
             }
 
@@ -605,6 +2951,13 @@ async fn main() -> Result<()> {
         progress_manager.finalize_all();
     }
 
+    // CI 中每个工具的日志折叠为一个分组
+    let ci_provider = CiProvider::detect();
+    if let Some(provider) = ci_provider {
+        let tools: Vec<Tool> = results.iter().map(|result| result.tool.clone()).collect();
+        ci::print_log_groups(provider, &tools, run_dir.path());
+    }
+
     // 处理执行结果
     let mut succ: Vec<String> = Vec::new();
     let mut fail: Vec<String> = Vec::new();
@@ -617,7 +2970,8 @@ async fn main() -> Result<()> {
             succ.push(result.tool.display_name().to_string());
 
             // 检查是否有升级详情文件来判断是否有真正的升级
-            let has_upgrade_details = !read_upgrade_details(&_run_tmp, &result.tool).is_empty();
+            let has_upgrade_details =
+                !read_upgrade_details(run_dir.path(), &result.tool).is_empty();
 
             if result.output.contains("updated") && has_upgrade_details {
                 updated.push(result.tool.display_name().to_string());
@@ -636,146 +2990,214 @@ async fn main() -> Result<()> {
     }
 
     // 使用 indicatif 进度条，不需要旧进度条
-    println!(); // 换行
-                // 使用 indicatif 进度条，不需要 progress_finish
-                // if !dry_run {
-                //     progress_finish();
-                // }
+    if !compact && !accessible {
+        println!(); // 换行
+    }
+    // 使用 indicatif 进度条，不需要 progress_finish
+    // if !dry_run {
+    //     progress_finish();
+    // }
 
     // 计算总耗时
     let end_time = chrono::Local::now();
     let duration = end_time.signed_duration_since(start_time);
-    let duration_str = match (
-        duration.num_hours(),
-        duration.num_minutes(),
-        duration.num_seconds(),
-    ) {
-        (h, _, _) if h > 0 => format!(
-            "{}小时{}分{}秒",
-            h,
-            duration.num_minutes() % 60,
-            duration.num_seconds() % 60
-        ),
-        (_, m, _) if m > 0 => format!("{}分{}秒", m, duration.num_seconds() % 60),
-        (_, _, s) => format!("{}秒", s),
-    };
+    let duration_str = i18n::format_duration(&system_lang, duration.num_seconds().max(0) as u64);
 
     let update_complete_msg = format!(
-        "\n{} {} ({}: {})",
-        localized.update_complete,
-        end_time.format("%Y-%m-%d %H:%M:%S"),
-        localized.time_taken,
-        duration_str
+        "\n{}",
+        t!(
+            &system_lang,
+            "update-complete",
+            time = end_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            duration = duration_str
+        )
     );
 
-    if ui::colors::supports_color() && !no_color {
-        print_success(&update_complete_msg);
-        if !updated.is_empty() {
-            let updated_msg = if system_lang == "zh" {
-                format!("{} 已更新：{}", icons.success(), updated.join(", "))
-            } else {
-                format!("{} Updated: {}", icons.success(), updated.join(", "))
-            };
-            print_success(&updated_msg);
-        } else {
-            print_info(&format!("{} {}", icons.info(), localized.no_updates));
-        }
-        if !actions.is_empty() {
-            let actions_msg = format!(
-                "{}{}{}",
-                icons.tools(),
-                localized.actions_executed,
-                actions.join(", ")
-            );
-            print_info(&actions_msg);
-        }
-        if !unchanged.is_empty() {
-            let unchanged_msg = format!(
-                "{}{}{}",
-                icons.warning(),
-                localized.already_latest,
-                unchanged.join(", ")
-            );
-            print_warning(&unchanged_msg);
-        }
+    if accessible {
+        print_accessible_summary(&results, run_dir.path(), duration, &system_lang);
+    } else if compact {
+        print_compact_summary(&results, run_dir.path(), duration, &system_lang);
     } else {
-        println!("{}", update_complete_msg);
-        if !updated.is_empty() {
-            let updated_msg = if system_lang == "zh" {
-                format!("{} 已更新：{}", icons.success(), updated.join(", "))
+        if ui::colors::supports_color() && !no_color {
+            print_success(&update_complete_msg);
+            if !updated.is_empty() {
+                let updated_msg = format!(
+                    "{} {}",
+                    icons.success(),
+                    t!(&system_lang, "update-updated", tools = updated.join(", "))
+                );
+                print_success(&updated_msg);
             } else {
-                format!("{} Updated: {}", icons.success(), updated.join(", "))
-            };
-            println!("{}", updated_msg);
+                print_info(&format!(
+                    "{} {}",
+                    icons.info(),
+                    t!(&system_lang, "update-no-updates")
+                ));
+            }
+            if !actions.is_empty() {
+                let actions_msg = format!(
+                    "{}{}",
+                    icons.tools(),
+                    t!(&system_lang, "update-actions", actions = actions.join(", "))
+                );
+                print_info(&actions_msg);
+            }
+            if !unchanged.is_empty() {
+                let unchanged_msg = format!(
+                    "{}{}",
+                    icons.warning(),
+                    t!(
+                        &system_lang,
+                        "update-unchanged",
+                        tools = unchanged.join(", ")
+                    )
+                );
+                print_warning(&unchanged_msg);
+            }
         } else {
-            println!("{} {}", icons.info(), localized.no_updates);
-        }
-        if !actions.is_empty() {
-            let actions_msg = format!(
-                "{}{}{}",
-                icons.tools(),
-                localized.actions_executed,
-                actions.join(", ")
-            );
-            println!("{}", actions_msg);
-        }
-        if !unchanged.is_empty() {
-            let unchanged_msg = format!(
-                "{}{}{}",
-                icons.warning(),
-                localized.already_latest,
-                unchanged.join(", ")
-            );
-            println!("{}", unchanged_msg);
-        }
-    }
-
-    // 打印详细更新信息
-    if let Some(vals) = short_updates.get("Homebrew：升级软件包") {
-        if !vals.is_empty() {
-            if ui::colors::supports_color() && !no_color {
-                print_info(&format!("{} Homebrew 升级详情：", icons.package()));
+            println!("{}", update_complete_msg);
+            if !updated.is_empty() {
+                let updated_msg = format!(
+                    "{} {}",
+                    icons.success(),
+                    t!(&system_lang, "update-updated", tools = updated.join(", "))
+                );
+                println!("{}", updated_msg);
             } else {
-                println!("{} Homebrew 升级详情：", icons.package());
+                println!("{} {}", icons.info(), t!(&system_lang, "update-no-updates"));
             }
-            for detail in vals {
-                println!("   {}", detail);
+            if !actions.is_empty() {
+                let actions_msg = format!(
+                    "{}{}",
+                    icons.tools(),
+                    t!(&system_lang, "update-actions", actions = actions.join(", "))
+                );
+                println!("{}", actions_msg);
+            }
+            if !unchanged.is_empty() {
+                let unchanged_msg = format!(
+                    "{}{}",
+                    icons.warning(),
+                    t!(
+                        &system_lang,
+                        "update-unchanged",
+                        tools = unchanged.join(", ")
+                    )
+                );
+                println!("{}", unchanged_msg);
             }
         }
-    }
 
-    if let Some(vals) = short_updates.get("Rust：更新工具链") {
-        if !vals.is_empty() {
+        // 打印详细更新信息
+        for (tool, icon) in [
+            (Tool::Homebrew, icons.package()),
+            (Tool::Rustup, icons.rust()),
+            (Tool::Mise, icons.wrench()),
+        ] {
+            let Some(vals) = short_updates.get(&tool) else {
+                continue;
+            };
+            let heading = format!(
+                "{} {}",
+                icon,
+                t!(
+                    &system_lang,
+                    "update-details-heading",
+                    tool = tool.display_name()
+                )
+            );
             if ui::colors::supports_color() && !no_color {
-                print_info(&format!("{} Rust 升级详情：", icons.rust()));
+                print_header(&heading);
             } else {
-                println!("{} Rust 升级详情：", icons.rust());
+                println!("{}", heading);
             }
+            let arrow = format!(" {} ", icons.arrow());
             for detail in vals {
-                println!("   {}", detail);
+                println!("   {}", detail.replace(" → ", &arrow));
             }
         }
-    }
 
-    if let Some(vals) = short_updates.get("Mise：更新托管工具") {
-        if !vals.is_empty() {
+        // 打印因隔离期暂缓的升级
+        print_deferred_upgrades(run_dir.path(), &system_lang, !no_color);
+
+        // 提示升级的副本被 PATH 中其他工具的副本遮蔽
+        for (command, upgraded, winner) in shadowed_upgrades(run_dir.path()) {
+            let message = format!(
+                "{} {}",
+                icons.warning(),
+                t!(
+                    &system_lang,
+                    "update-shadowed",
+                    command = command.as_str(),
+                    via = upgraded.describe(),
+                    path = winner.path.display().to_string(),
+                    owner = winner.owner.describe()
+                )
+            );
             if ui::colors::supports_color() && !no_color {
-                print_info(&format!("{} Mise 升级详情：", icons.wrench()));
+                print_warning(&message);
             } else {
-                println!("{} Mise 升级详情：", icons.wrench());
+                println!("{}", message);
             }
-            for detail in vals {
-                println!("   {}", detail);
+        }
+    }
+
+    if let Some(provider) = ci_provider {
+        report_to_ci(provider, &results, run_dir.path(), &system_lang);
+    }
+
+    // 记录运行历史
+    let mut kept_logs = None;
+    if !dry_run {
+        let record = RunRecord {
+            started_at: start_time.with_timezone(&chrono::Utc),
+            finished_at: end_time.with_timezone(&chrono::Utc),
+            scheduled,
+            tools: tool_results(&results, run_dir.path()),
+        };
+        if let Err(e) = history::append(&history::history_path(), &record) {
+            if verbose {
+                eprintln!("Debug: failed to write history: {:#}", e);
             }
         }
+        send_notifications(&config.notify, &record, run_dir.path(), &system_lang);
+
+        // 失败的运行总是保留日志，成功的运行在 --keep-logs 或定时任务时保留
+        match run_dir.finish(&record, keep_logs || scheduled) {
+            Ok(path) => kept_logs = path,
+            Err(e) => eprintln!(
+                "{}",
+                t!(&system_lang, "log-save-failed", error = format!("{:#}", e))
+            ),
+        }
+    } else {
+        run_dir.discard();
     }
 
     if !fail.is_empty() {
-        if ui::colors::supports_color() && !no_color {
-            print_error(&format!("{} 失败：{}", icons.failure(), fail.join(", ")));
+        let failed_msg = t!(&system_lang, "update-failed", tools = fail.join(", "));
+        if compact {
+            log_line(&failed_msg);
+        } else if accessible {
+            println!("{}", failed_msg);
+        } else if ui::colors::supports_color() && !no_color {
+            print_error(&format!("{} {}", icons.failure(), failed_msg));
         } else {
-            println!("{} 失败：{}", icons.failure(), fail.join(", "));
+            println!("{} {}", icons.failure(), failed_msg);
+        }
+        if let Some(path) = &kept_logs {
+            let hint = t!(
+                &system_lang,
+                "logs-run-kept",
+                path = path.display().to_string()
+            );
+            if compact {
+                log_line(&hint);
+            } else {
+                println!("{}", hint);
+            }
         }
+        drop(run_lock);
         std::process::exit(1);
     }
 
@@ -791,11 +3213,15 @@ fn handle_feedback_command(
     use std::io::{self, Write};
     use std::time::{SystemTime, UNIX_EPOCH};
 
+    let lang = i18n::detect_system_language();
+    let icons = get_icon_manager();
+
     // 显示反馈收集界面
+    let title = format!("{} {}", icons.memo(), t!(&lang, "feedback-title"));
     if ui::colors::supports_color() {
-        print_info("📝 devtool User Feedback Collection");
+        print_info(&title);
     } else {
-        println!("📝 devtool User Feedback Collection");
+        println!("{}", title);
     }
 
     // 收集系统信息
@@ -805,14 +3231,21 @@ fn handle_feedback_command(
     let feedback_type = match feedback_type {
         Some(ft) => ft.clone(),
         None => {
-            println!("\nPlease select feedback type:");
-            println!("1. Bug Report");
-            println!("2. Feature Request");
-            println!("3. User Experience Issue");
-            println!("4. Performance Issue");
-            println!("5. Documentation Issue");
-            println!("6. Other");
-            print!("Please enter your choice (1-6): ");
+            println!("\n{}", t!(&lang, "feedback-select-type"));
+            for (index, id) in [
+                "feedback-type-bug",
+                "feedback-type-feature",
+                "feedback-type-ux",
+                "feedback-type-performance",
+                "feedback-type-documentation",
+                "feedback-type-other",
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                println!("{}. {}", index + 1, t!(&lang, id));
+            }
+            print!("{} ", t!(&lang, "feedback-choice-prompt"));
             io::stdout().flush()?;
 
             let mut input = String::new();
@@ -834,7 +3267,7 @@ fn handle_feedback_command(
     let feedback_message = match message {
         Some(msg) => msg.clone(),
         None => {
-            println!("\nPlease describe your feedback:");
+            println!("\n{}", t!(&lang, "feedback-describe"));
             let mut input = String::new();
             io::stdin().read_line(&mut input)?;
             input.trim().to_string()
@@ -842,7 +3275,7 @@ fn handle_feedback_command(
     };
 
     if feedback_message.is_empty() {
-        println!("Feedback content cannot be empty!");
+        println!("{}", t!(&lang, "feedback-empty"));
         return Ok(());
     }
 
@@ -859,34 +3292,51 @@ fn handle_feedback_command(
     let feedback_dir = ensure_cache_dir()?;
 
     let feedback_file = feedback_dir.join(&filename);
-    std::fs::write(&feedback_file, &feedback_report)?;
+    std::fs::write(
+        &feedback_file,
+        redact::current().redact(&feedback_report).as_ref(),
+    )?;
 
     // 显示反馈信息
-    let icons = get_icon_manager();
+    let saved_msg = format!(
+        "{} {}",
+        icons.success(),
+        t!(
+            &lang,
+            "feedback-saved",
+            path = feedback_file.display().to_string()
+        )
+    );
     if ui::colors::supports_color() {
-        print_success(&format!(
-            "{} Feedback saved to: {}",
-            icons.success(),
-            feedback_file.display()
-        ));
+        print_success(&saved_msg);
     } else {
-        println!(
-            "{} Feedback saved to: {}",
-            icons.success(),
-            feedback_file.display()
-        );
+        println!("{}", saved_msg);
     }
 
-    println!("\n{} Feedback Summary:", icons.clipboard());
-    println!("Type: {:?}", feedback_type);
-    println!("Content: {}", feedback_message);
+    println!("\n{} {}", icons.clipboard(), t!(&lang, "feedback-summary"));
+    println!(
+        "{}",
+        t!(
+            &lang,
+            "feedback-summary-type",
+            kind = format!("{:?}", feedback_type)
+        )
+    );
+    println!(
+        "{}",
+        t!(
+            &lang,
+            "feedback-summary-content",
+            content = feedback_message.as_str()
+        )
+    );
 
     if verbose {
-        println!("\n{} System Information:", icons.tools());
+        println!("\n{} {}", icons.tools(), t!(&lang, "feedback-system-info"));
         println!("{}", system_info);
     }
 
-    println!("\n💡 You can also submit feedback through:");
+    println!("\n{} {}", icons.bulb(), t!(&lang, "feedback-channels"));
     println!("- GitHub Issues: https://github.com/jenkinpan/devtool-rs/issues");
     println!("- GitHub Discussions: https://github.com/jenkinpan/devtool-rs/discussions");
 
@@ -899,17 +3349,17 @@ fn collect_system_info() -> String {
 
     // 操作系统信息
     if let Ok(os) = std::env::var("OS") {
-        info.push_str(&format!("操作系统: {}\n", os));
+        info.push_str(&format!("OS: {}\n", os));
     } else if cfg!(target_os = "macos") {
-        info.push_str("操作系统: macOS\n");
+        info.push_str("OS: macOS\n");
     } else if cfg!(target_os = "linux") {
-        info.push_str("操作系统: Linux\n");
+        info.push_str("OS: Linux\n");
     } else if cfg!(target_os = "windows") {
-        info.push_str("操作系统: Windows\n");
+        info.push_str("OS: Windows\n");
     }
 
     // devtool 版本
-    info.push_str(&format!("devtool 版本: {}\n", env!("CARGO_PKG_VERSION")));
+    info.push_str(&format!("devtool version: {}\n", env!("CARGO_PKG_VERSION")));
 
     // Rust 版本
     if let Ok(rustc_version) = std::process::Command::new("rustc")
@@ -917,7 +3367,7 @@ fn collect_system_info() -> String {
         .output()
     {
         if let Ok(version) = String::from_utf8(rustc_version.stdout) {
-            info.push_str(&format!("Rust 版本: {}", version.trim()));
+            info.push_str(&format!("Rust version: {}", version.trim()));
         }
     }
 
@@ -0,0 +1,465 @@
+// 团队工具链清单模块
+// 读取 devtool.team.toml，将已安装版本与清单要求对比，并生成同步操作
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use which::which;
+
+use crate::commands::{homebrew, mise, rustup};
+use crate::parallel::Tool;
+use crate::runner::Runner;
+use crate::t;
+use crate::utils::{is_valid_name, shell_quote};
+use crate::version::{Version, VersionReq};
+
+/// 默认清单文件名
+pub const DEFAULT_MANIFEST_FILE: &str = "devtool.team.toml";
+
+/// 清单文件结构
+///
+/// ```toml
+/// [rustup]
+/// rustc = "1.80.0"
+///
+/// [mise]
+/// node = "20"
+///
+/// [homebrew]
+/// git = ">=2.40"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ManifestFile {
+    homebrew: BTreeMap<String, String>,
+    mise: BTreeMap<String, String>,
+    rustup: RustupSection,
+}
+
+/// `[rustup]` 段：约束默认工具链的 rustc 版本
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RustupSection {
+    rustc: Option<String>,
+}
+
+/// 单个版本要求
+#[derive(Debug, Clone)]
+pub struct Requirement {
+    pub tool: Tool,
+    pub name: String,
+    pub req: VersionReq,
+}
+
+/// 团队工具链清单
+#[derive(Debug, Clone, Default)]
+pub struct TeamManifest {
+    requirements: Vec<Requirement>,
+}
+
+impl TeamManifest {
+    /// 从文件加载清单
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            std::fs::read_to_string(path).with_context(|| format!("read manifest {:?}", path))?;
+        Self::parse(&content).with_context(|| format!("parse manifest {:?}", path))
+    }
+
+    /// 从 TOML 文本解析清单
+    pub fn parse(content: &str) -> Result<Self> {
+        let file: ManifestFile = toml::from_str(content)?;
+        let mut requirements = Vec::new();
+
+        let parse_req = |tool: Tool, name: &str, spec: &str| -> Result<Requirement> {
+            if !is_valid_name(name) {
+                bail!("invalid name {:?}", name);
+            }
+            Ok(Requirement {
+                tool,
+                name: name.to_string(),
+                req: VersionReq::parse(spec)
+                    .with_context(|| format!("invalid version for {}: {:?}", name, spec))?,
+            })
+        };
+
+        if let Some(spec) = &file.rustup.rustc {
+            requirements.push(parse_req(Tool::Rustup, "rustc", spec)?);
+        }
+        for (name, spec) in &file.mise {
+            requirements.push(parse_req(Tool::Mise, name, spec)?);
+        }
+        for (name, spec) in &file.homebrew {
+            requirements.push(parse_req(Tool::Homebrew, name, spec)?);
+        }
+
+        Ok(Self { requirements })
+    }
+
+    /// 清单中的全部版本要求
+    pub fn requirements(&self) -> &[Requirement] {
+        &self.requirements
+    }
+
+    /// 清单涉及的工具
+    pub fn tools(&self) -> Vec<Tool> {
+        let mut tools: Vec<Tool> = Vec::new();
+        for requirement in &self.requirements {
+            if !tools.contains(&requirement.tool) {
+                tools.push(requirement.tool.clone());
+            }
+        }
+        tools
+    }
+}
+
+/// 解析清单路径
+///
+/// 使用默认文件名时从当前目录向上查找，便于在仓库子目录中运行
+pub fn resolve_manifest_path(path: &Path) -> PathBuf {
+    if path.exists() || path != Path::new(DEFAULT_MANIFEST_FILE) {
+        return path.to_path_buf();
+    }
+    std::env::current_dir()
+        .ok()
+        .and_then(|cwd| {
+            cwd.ancestors()
+                .map(|dir| dir.join(DEFAULT_MANIFEST_FILE))
+                .find(|candidate| candidate.exists())
+        })
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// 已安装的版本
+#[derive(Debug, Clone, Default)]
+pub struct InstalledVersions {
+    pub homebrew: HashMap<String, String>,
+    pub mise: HashMap<String, String>,
+    /// 默认工具链的 rustc 版本
+    pub rustc: Option<String>,
+}
+
+impl InstalledVersions {
+    /// 探测指定工具的已安装版本，工具未安装或探测失败时视为空
+    pub fn probe(runner: &dyn Runner, tmpdir: &Path, tools: &[Tool]) -> Self {
+        let mut installed = Self::default();
+        for tool in tools {
+            match tool {
+                Tool::Homebrew if which("brew").is_ok() => {
+                    if let Ok(packages) = homebrew::installed_packages(runner, tmpdir) {
+                        installed.homebrew = packages
+                            .into_iter()
+                            .map(|package| (package.name, package.version))
+                            .collect();
+                    }
+                }
+                Tool::Mise if which("mise").is_ok() => {
+                    if let Ok(tools) = mise::installed_tools(runner, tmpdir) {
+                        installed.mise = tools.into_iter().collect();
+                    }
+                }
+                Tool::Rustup if which("rustup").is_ok() => {
+                    if let Ok(Some((_, version))) =
+                        rustup::default_toolchain_version(runner, tmpdir)
+                    {
+                        installed.rustc = Some(version);
+                    }
+                }
+                _ => {}
+            }
+        }
+        installed
+    }
+
+    fn get(&self, tool: &Tool, name: &str) -> Option<&String> {
+        match tool {
+            Tool::Homebrew => self.homebrew.get(name),
+            Tool::Mise => self.mise.get(name),
+            Tool::Rustup => self.rustc.as_ref(),
+        }
+    }
+}
+
+/// 检查状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DriftStatus {
+    /// 已满足要求
+    Ok,
+    /// 未安装
+    Missing,
+    /// 已安装但版本不满足要求
+    Mismatch,
+}
+
+/// 单项检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckItem {
+    pub tool: Tool,
+    pub name: String,
+    pub required: String,
+    pub installed: Option<String>,
+    pub status: DriftStatus,
+    #[serde(skip)]
+    req: VersionReq,
+}
+
+impl CheckItem {
+    /// 是否偏离清单
+    pub fn is_drift(&self) -> bool {
+        self.status != DriftStatus::Ok
+    }
+}
+
+/// 将已安装版本与清单逐项对比
+pub fn check(manifest: &TeamManifest, installed: &InstalledVersions) -> Vec<CheckItem> {
+    manifest
+        .requirements()
+        .iter()
+        .map(|requirement| {
+            let version = installed.get(&requirement.tool, &requirement.name).cloned();
+            let status = match &version {
+                None => DriftStatus::Missing,
+                Some(v) if requirement.req.matches(&Version::parse(v)) => DriftStatus::Ok,
+                Some(_) => DriftStatus::Mismatch,
+            };
+            CheckItem {
+                tool: requirement.tool.clone(),
+                name: requirement.name.clone(),
+                required: requirement.req.to_string(),
+                installed: version,
+                status,
+                req: requirement.req.clone(),
+            }
+        })
+        .collect()
+}
+
+/// 同步操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncAction {
+    /// 执行命令
+    Run(String),
+    /// 无法自动处理，需要手动操作
+    Manual(String),
+}
+
+/// 为偏离清单的项目生成同步操作
+pub fn plan_sync(item: &CheckItem, lang: &str) -> SyncAction {
+    let hint = item.req.install_hint();
+    let installed = item.installed.as_deref().map(Version::parse);
+    // 名称来自提交到仓库的清单，拼接命令时必须加引号
+    let name = shell_quote(&item.name);
+    let manual = || {
+        SyncAction::Manual(t!(
+            lang,
+            "sync-manual",
+            name = item.name.as_str(),
+            req = item.required.as_str()
+        ))
+    };
+    match item.tool {
+        Tool::Homebrew => match installed {
+            None => SyncAction::Run(format!("brew install {}", name)),
+            // 已安装版本高于上界时升级只会离约束更远，Homebrew 也无法降级
+            Some(installed) if item.req.exceeded_by(&installed) => {
+                SyncAction::Manual(match &hint {
+                    Some(hint) => t!(
+                        lang,
+                        "sync-brew-downgrade",
+                        name = item.name.as_str(),
+                        version = item.required.as_str(),
+                        hint = hint.as_str()
+                    ),
+                    None => t!(
+                        lang,
+                        "sync-brew-downgrade-range",
+                        name = item.name.as_str(),
+                        installed = installed.as_str(),
+                        req = item.required.as_str()
+                    ),
+                })
+            }
+            // brew upgrade 总是升级到最新版本，会越过固定的版本
+            Some(installed) if item.req.exact().is_some() => SyncAction::Manual(t!(
+                lang,
+                "sync-brew-pinned",
+                name = item.name.as_str(),
+                installed = installed.as_str(),
+                version = item.required.as_str()
+            )),
+            Some(_) => SyncAction::Run(format!("brew upgrade {}", name)),
+        },
+        Tool::Mise => match hint {
+            Some(hint) => SyncAction::Run(format!(
+                "mise use -g {}",
+                shell_quote(&format!("{}@{}", item.name, hint))
+            )),
+            // 没有上界时最新版本总能满足约束
+            None if installed.is_none_or(|installed| !item.req.exceeded_by(&installed)) => {
+                SyncAction::Run(format!(
+                    "mise use -g {}",
+                    shell_quote(&format!("{}@latest", item.name))
+                ))
+            }
+            None => manual(),
+        },
+        // 只有精确版本或完整的 主.次.修订 版本才是有效的工具链名
+        Tool::Rustup => {
+            let toolchain = item
+                .req
+                .exact()
+                .map(|version| version.as_str().trim_start_matches('v').to_string())
+                .or(hint.filter(|hint| {
+                    hint.split('.').count() == 3
+                        && hint.split('.').all(|part| part.parse::<u64>().is_ok())
+                }));
+            match toolchain {
+                Some(toolchain) => {
+                    let toolchain = shell_quote(&toolchain);
+                    SyncAction::Run(format!(
+                        "rustup toolchain install {} && rustup default {}",
+                        toolchain, toolchain
+                    ))
+                }
+                None => manual(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[rustup]
+rustc = "1.80.0"
+
+[mise]
+node = "20"
+python = "^3.11"
+
+[homebrew]
+git = ">=2.40"
+jq = "1.7.1"
+"#;
+
+    fn installed() -> InstalledVersions {
+        InstalledVersions {
+            homebrew: HashMap::from([
+                ("git".to_string(), "2.45.0".to_string()),
+                ("jq".to_string(), "1.8.0".to_string()),
+            ]),
+            mise: HashMap::from([("node".to_string(), "18.19.0".to_string())]),
+            rustc: Some("1.80.0".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = TeamManifest::parse(MANIFEST).unwrap();
+        assert_eq!(manifest.requirements().len(), 5);
+        assert_eq!(
+            manifest.tools(),
+            vec![Tool::Rustup, Tool::Mise, Tool::Homebrew]
+        );
+        assert!(TeamManifest::parse("[rustup]\ncargo = \"1.80\"\n").is_err());
+        assert!(TeamManifest::parse("[mise]\nnode = \">=\"\n").is_err());
+        assert!(TeamManifest::parse("[homebrew]\n\"jq; id\" = \"1.7\"\n").is_err());
+    }
+
+    #[test]
+    fn test_check_reports_drift() {
+        let manifest = TeamManifest::parse(MANIFEST).unwrap();
+        let items = check(&manifest, &installed());
+        let status = |name: &str| items.iter().find(|i| i.name == name).unwrap().status;
+
+        assert_eq!(status("rustc"), DriftStatus::Ok);
+        assert_eq!(status("git"), DriftStatus::Ok);
+        assert_eq!(status("node"), DriftStatus::Mismatch);
+        assert_eq!(status("python"), DriftStatus::Missing);
+        assert_eq!(status("jq"), DriftStatus::Mismatch);
+        assert_eq!(items.iter().filter(|i| i.is_drift()).count(), 3);
+    }
+
+    #[test]
+    fn test_plan_sync() {
+        let manifest = TeamManifest::parse(MANIFEST).unwrap();
+        let items = check(&manifest, &installed());
+        let action = |name: &str| plan_sync(items.iter().find(|i| i.name == name).unwrap(), "en");
+
+        assert_eq!(
+            action("node"),
+            SyncAction::Run("mise use -g 'node@20'".to_string())
+        );
+        assert_eq!(
+            action("python"),
+            SyncAction::Run("mise use -g 'python@3'".to_string())
+        );
+        assert!(matches!(action("jq"), SyncAction::Manual(_)));
+        assert_eq!(
+            action("rustc"),
+            SyncAction::Run(
+                "rustup toolchain install '1.80.0' && rustup default '1.80.0'".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_plan_sync_upper_bounds_and_caret() {
+        let manifest = TeamManifest::parse(
+            r#"
+[rustup]
+rustc = "^1.80"
+
+[mise]
+node = "<20"
+deno = "^0.2.3"
+
+[homebrew]
+git = "<2.40"
+wget = ">=1.21"
+"#,
+        )
+        .unwrap();
+        let installed = InstalledVersions {
+            homebrew: HashMap::from([
+                ("git".to_string(), "2.45.0".to_string()),
+                ("wget".to_string(), "1.20.0".to_string()),
+            ]),
+            mise: HashMap::from([
+                ("node".to_string(), "22.1.0".to_string()),
+                ("deno".to_string(), "0.3.0".to_string()),
+            ]),
+            rustc: Some("1.79.0".to_string()),
+        };
+        let items = check(&manifest, &installed);
+        let action = |name: &str| plan_sync(items.iter().find(|i| i.name == name).unwrap(), "en");
+
+        // 高于上界时升级无济于事
+        assert!(matches!(action("git"), SyncAction::Manual(_)));
+        assert_eq!(
+            action("wget"),
+            SyncAction::Run("brew upgrade 'wget'".to_string())
+        );
+        assert!(matches!(action("node"), SyncAction::Manual(_)));
+        // 0.x 的 "^" 只允许同一个次版本
+        assert_eq!(
+            action("deno"),
+            SyncAction::Run("mise use -g 'deno@0.2'".to_string())
+        );
+        // 固定的精确版本不能用 brew upgrade 追到最新
+        let pinned = TeamManifest::parse("[homebrew]\njq = \"1.7.1\"\n").unwrap();
+        let older = InstalledVersions {
+            homebrew: HashMap::from([("jq".to_string(), "1.6".to_string())]),
+            ..installed.clone()
+        };
+        assert!(matches!(
+            plan_sync(&check(&pinned, &older)[0], "en"),
+            SyncAction::Manual(_)
+        ));
+        // "1" 不是有效的工具链名
+        assert!(matches!(action("rustc"), SyncAction::Manual(_)));
+    }
+}
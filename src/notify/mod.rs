@@ -0,0 +1,574 @@
+// 运行结束通知
+// 根据配置文件中的 [[notify]] 在更新结束时发送通知：通用 JSON webhook（支持 Slack、Discord、飞书格式和自定义模板）、
+// SMTP 邮件、notify-send 桌面通知以及 ntfy/gotify 推送。HTTP 和 SMTP 请求通过 curl 发送
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::config::{NotifyConfig, NotifyOn, SinkConfig, WebhookFormat};
+use crate::history::RunRecord;
+use crate::runner::Runner;
+use crate::t;
+use crate::utils::{format_duration_short, shell_quote};
+
+/// 单次请求的超时（秒）
+const TIMEOUT_SECS: u32 = 30;
+
+/// 发送给各通知方式的运行摘要
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub title: String,
+    pub text: String,
+    pub host: String,
+    pub record: RunRecord,
+}
+
+impl Notification {
+    pub fn new(record: &RunRecord, host: &str, lang: &str) -> Self {
+        let title = if record.success() {
+            t!(lang, "notify-title-success", host = host)
+        } else {
+            t!(lang, "notify-title-failure", host = host)
+        };
+
+        let mut lines = Vec::new();
+        for tool in &record.tools {
+            let status = if tool.success {
+                t!(lang, "notify-tool-upgraded", count = tool.upgrades.len())
+            } else {
+                t!(lang, "notify-tool-failed")
+            };
+            lines.push(t!(
+                lang,
+                "notify-tool-line",
+                tool = tool.tool.display_name(),
+                status = status
+            ));
+            lines.extend(tool.upgrades.iter().map(|detail| format!("  - {}", detail)));
+        }
+        lines.push(t!(
+            lang,
+            "notify-took",
+            duration = format_duration_short(record.finished_at - record.started_at)
+        ));
+
+        Self {
+            title,
+            text: lines.join("\n"),
+            host: host.to_string(),
+            record: record.clone(),
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        if self.record.success() {
+            "success"
+        } else {
+            "failure"
+        }
+    }
+
+    fn upgrade_count(&self) -> usize {
+        self.record.tools.iter().map(|t| t.upgrades.len()).sum()
+    }
+
+    /// 通用 webhook 的请求体：结构化的运行摘要
+    pub fn payload(&self) -> Value {
+        json!({
+            "event": "run_finished",
+            "status": self.status(),
+            "title": self.title,
+            "text": self.text,
+            "host": self.host,
+            "upgrades": self.upgrade_count(),
+            "run": self.record,
+        })
+    }
+}
+
+/// 本次运行是否满足触发条件
+pub fn should_notify(on: NotifyOn, record: &RunRecord) -> bool {
+    match on {
+        NotifyOn::Always => true,
+        NotifyOn::Failure => !record.success(),
+        NotifyOn::Upgrades => record.tools.iter().any(|t| !t.upgrades.is_empty()),
+    }
+}
+
+/// 通知方式名称，用于错误提示
+pub fn sink_name(sink: &SinkConfig) -> &'static str {
+    match sink {
+        SinkConfig::Webhook { .. } => "webhook",
+        SinkConfig::Email { .. } => "email",
+        SinkConfig::Desktop => "desktop",
+        SinkConfig::Ntfy { .. } => "ntfy",
+        SinkConfig::Gotify { .. } => "gotify",
+    }
+}
+
+/// 不带引号的 JSON 字符串转义
+fn json_escape(value: &str) -> String {
+    let quoted = Value::String(value.to_string()).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// 替换模板中的占位符：{{title}}、{{text}}、{{status}}、{{host}}、{{upgrades}} 和 {{summary}}（完整摘要 JSON）
+fn render_template(template: &str, notification: &Notification) -> String {
+    template
+        .replace("{{title}}", &json_escape(&notification.title))
+        .replace("{{text}}", &json_escape(&notification.text))
+        .replace("{{status}}", notification.status())
+        .replace("{{host}}", &json_escape(&notification.host))
+        .replace("{{upgrades}}", &notification.upgrade_count().to_string())
+        .replace("{{summary}}", &notification.payload().to_string())
+}
+
+/// webhook 请求体
+pub fn webhook_body(
+    notification: &Notification,
+    format: WebhookFormat,
+    template: Option<&str>,
+) -> String {
+    if let Some(template) = template {
+        return render_template(template, notification);
+    }
+    let message = format!("{}\n{}", notification.title, notification.text);
+    match format {
+        WebhookFormat::Json => notification.payload(),
+        WebhookFormat::Slack => json!({
+            "text": format!("*{}*\n{}", notification.title, notification.text),
+        }),
+        WebhookFormat::Discord => json!({ "content": message }),
+        WebhookFormat::Feishu => json!({
+            "msg_type": "text",
+            "content": { "text": message },
+        }),
+    }
+    .to_string()
+}
+
+/// Base64 编码（用于 MIME 邮件头和正文）
+fn base64(data: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(TABLE[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// 非 ASCII 的头部按 RFC 2047 编码
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", base64(value.as_bytes()))
+    }
+}
+
+/// 邮件内容（RFC 5322）
+pub fn email_message(notification: &Notification, from: &str, to: &[String]) -> String {
+    let body = base64(notification.text.as_bytes());
+    let wrapped: Vec<&str> = body
+        .as_bytes()
+        .chunks(76)
+        .map(|line| std::str::from_utf8(line).unwrap_or_default())
+        .collect();
+    format!(
+        "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: base64\r\n\r\n{}\r\n",
+        from,
+        to.join(", "),
+        encode_header(&notification.title),
+        notification.record.finished_at.to_rfc2822(),
+        wrapped.join("\r\n")
+    )
+}
+
+/// 执行命令，失败时返回包含输出的错误
+fn run(runner: &dyn Runner, tmpdir: &Path, name: &str, cmd: &str) -> Result<()> {
+    let logfile = tmpdir.join(format!("notify_{}.log", name));
+    let (rc, _) = runner.run(cmd, &logfile, false)?;
+    if rc != 0 {
+        let output = std::fs::read_to_string(&logfile).unwrap_or_default();
+        bail!("exit {}: {}", rc, output.trim());
+    }
+    Ok(())
+}
+
+/// 将请求体和请求头写入临时文件，避免令牌出现在命令行中
+fn write_file(tmpdir: &Path, name: &str, content: &str) -> Result<String> {
+    let path = tmpdir.join(name);
+    std::fs::write(&path, content).with_context(|| format!("write {:?}", path))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(shell_quote(&path.to_string_lossy()))
+}
+
+/// 用 curl POST 请求体，请求体和请求头写入 `files`
+fn post(
+    runner: &dyn Runner,
+    tmpdir: &Path,
+    files: &Path,
+    name: &str,
+    url: &str,
+    headers: &[String],
+    body: &str,
+) -> Result<()> {
+    let body_file = write_file(files, &format!("notify_{}.body", name), body)?;
+    let headers_file = write_file(
+        files,
+        &format!("notify_{}.headers", name),
+        &headers.join("\n"),
+    )?;
+    let cmd = format!(
+        "curl -fsS --max-time {} -X POST -H @{} --data-binary @{} {}",
+        TIMEOUT_SECS,
+        headers_file,
+        body_file,
+        shell_quote(url)
+    );
+    run(runner, tmpdir, name, &cmd)
+}
+
+/// 通过一种方式发送通知
+///
+/// 命令日志写入 `tmpdir`（运行目录）；请求体、请求头和凭据写入单独的临时目录，发送后删除，
+/// 不会留在保留的运行日志中
+pub fn send(
+    runner: &dyn Runner,
+    tmpdir: &Path,
+    sink: &SinkConfig,
+    notification: &Notification,
+) -> Result<()> {
+    let name = sink_name(sink);
+    let failed = !notification.record.success();
+    let files = tempfile::tempdir()?;
+    let files = files.path();
+    match sink {
+        SinkConfig::Webhook {
+            url,
+            format,
+            template,
+        } => post(
+            runner,
+            tmpdir,
+            files,
+            name,
+            url,
+            &["Content-Type: application/json".to_string()],
+            &webhook_body(notification, *format, template.as_deref()),
+        ),
+        SinkConfig::Email {
+            smtp_url,
+            from,
+            to,
+            username,
+            password_env,
+        } => {
+            let message = write_file(
+                files,
+                "notify_email.eml",
+                &email_message(notification, from, to),
+            )?;
+            let mut cmd = format!(
+                "curl -fsS --max-time {} --ssl --url {} --mail-from {} --upload-file {}",
+                TIMEOUT_SECS,
+                shell_quote(smtp_url),
+                shell_quote(from),
+                message
+            );
+            for rcpt in to {
+                cmd.push_str(&format!(" --mail-rcpt {}", shell_quote(rcpt)));
+            }
+            if let Some(username) = username {
+                let password = match password_env {
+                    Some(var) => std::env::var(var)
+                        .with_context(|| format!("environment variable {} is not set", var))?,
+                    None => String::new(),
+                };
+                let credentials = format!("{}:{}", username, password);
+                let config = write_file(
+                    files,
+                    "notify_email.curlrc",
+                    &format!("user = {}\n", Value::String(credentials)),
+                )?;
+                cmd.push_str(&format!(" -K {}", config));
+            }
+            run(runner, tmpdir, name, &cmd)
+        }
+        SinkConfig::Desktop => {
+            let cmd = format!(
+                "notify-send -a devtool -u {} {} {}",
+                if failed { "critical" } else { "normal" },
+                shell_quote(&notification.title),
+                shell_quote(&notification.text)
+            );
+            run(runner, tmpdir, name, &cmd)
+        }
+        SinkConfig::Ntfy { url, token } => {
+            let mut headers = vec![
+                format!("Title: {}", encode_header(&notification.title)),
+                format!("Tags: {}", if failed { "x" } else { "white_check_mark" }),
+                format!("Priority: {}", if failed { "high" } else { "default" }),
+            ];
+            if let Some(token) = token {
+                headers.push(format!("Authorization: Bearer {}", token));
+            }
+            post(
+                runner,
+                tmpdir,
+                files,
+                name,
+                url,
+                &headers,
+                &notification.text,
+            )
+        }
+        SinkConfig::Gotify { url, token } => {
+            let body = json!({
+                "title": notification.title,
+                "message": notification.text,
+                "priority": if failed { 8 } else { 4 },
+            });
+            post(
+                runner,
+                tmpdir,
+                files,
+                name,
+                &format!("{}/message", url.trim_end_matches('/')),
+                &[
+                    "Content-Type: application/json".to_string(),
+                    format!("X-Gotify-Key: {}", token),
+                ],
+                &body.to_string(),
+            )
+        }
+    }
+}
+
+/// 主机名，用于通知标题
+pub fn hostname() -> String {
+    std::process::Command::new("hostname")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+/// 按配置发送所有满足触发条件的通知，返回失败的通知方式及原因；单个通知失败不影响其他通知
+pub fn notify_all(
+    runner: &dyn Runner,
+    tmpdir: &Path,
+    configs: &[NotifyConfig],
+    record: &RunRecord,
+    lang: &str,
+) -> Vec<(&'static str, anyhow::Error)> {
+    let targets: Vec<&NotifyConfig> = configs
+        .iter()
+        .filter(|config| should_notify(config.on, record))
+        .collect();
+    if targets.is_empty() {
+        return Vec::new();
+    }
+    let notification = Notification::new(record, &hostname(), lang);
+    targets
+        .into_iter()
+        .filter_map(|config| {
+            send(runner, tmpdir, &config.sink, &notification)
+                .err()
+                .map(|e| (sink_name(&config.sink), e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::ToolResult;
+    use crate::parallel::Tool;
+    use chrono::{TimeZone, Utc};
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    /// 记录命令及其 `@文件` 参数内容的执行器，`curl` 命令返回 `curl_rc`
+    struct RecordingRunner {
+        commands: Mutex<Vec<String>>,
+        files: Mutex<Vec<String>>,
+        curl_rc: i32,
+    }
+
+    impl Runner for RecordingRunner {
+        fn run(&self, cmd: &str, logfile: &Path, _verbose: bool) -> Result<(i32, String)> {
+            self.commands.lock().unwrap().push(cmd.to_string());
+            for arg in cmd.split_whitespace() {
+                if let Some(path) = arg.strip_prefix('@') {
+                    let content = std::fs::read_to_string(path.trim_matches('\''))?;
+                    self.files.lock().unwrap().push(content);
+                }
+            }
+            std::fs::write(logfile, "curl: (22) 500")?;
+            let rc = if cmd.starts_with("curl") {
+                self.curl_rc
+            } else {
+                0
+            };
+            Ok((rc, String::new()))
+        }
+    }
+
+    fn record(success: bool, upgrades: &[&str]) -> RunRecord {
+        RunRecord {
+            started_at: Utc.with_ymd_and_hms(2026, 10, 18, 7, 0, 0).unwrap(),
+            finished_at: Utc.with_ymd_and_hms(2026, 10, 18, 7, 3, 0).unwrap(),
+            scheduled: true,
+            tools: vec![
+                ToolResult {
+                    tool: Tool::Homebrew,
+                    success: true,
+                    output: "Homebrew completed".to_string(),
+                    upgrades: upgrades.iter().map(|s| s.to_string()).collect(),
+                },
+                ToolResult {
+                    tool: Tool::Mise,
+                    success,
+                    output: "Mise".to_string(),
+                    upgrades: Vec::new(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_should_notify() {
+        let ok = record(true, &[]);
+        let failed = record(false, &[]);
+        let upgraded = record(true, &["git 2.50.0 -> 2.51.0"]);
+        assert!(should_notify(NotifyOn::Always, &ok));
+        assert!(!should_notify(NotifyOn::Failure, &ok));
+        assert!(should_notify(NotifyOn::Failure, &failed));
+        assert!(!should_notify(NotifyOn::Upgrades, &failed));
+        assert!(should_notify(NotifyOn::Upgrades, &upgraded));
+    }
+
+    #[test]
+    fn test_webhook_bodies() {
+        let notification =
+            Notification::new(&record(false, &["git 2.50.0 -> 2.51.0"]), "mbp", "en");
+        assert_eq!(notification.title, "devtool update failed on mbp");
+        assert_eq!(
+            notification.text,
+            "Homebrew: 1 upgraded\n  - git 2.50.0 -> 2.51.0\nMise: failed\nTook 3m"
+        );
+
+        let json: Value =
+            serde_json::from_str(&webhook_body(&notification, WebhookFormat::Json, None)).unwrap();
+        assert_eq!(json["status"], "failure");
+        assert_eq!(json["upgrades"], 1);
+        assert_eq!(json["run"]["tools"][1]["tool"], "mise");
+
+        let slack: Value =
+            serde_json::from_str(&webhook_body(&notification, WebhookFormat::Slack, None)).unwrap();
+        assert!(slack["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("*devtool update failed on mbp*\n"));
+        let feishu: Value =
+            serde_json::from_str(&webhook_body(&notification, WebhookFormat::Feishu, None))
+                .unwrap();
+        assert_eq!(feishu["msg_type"], "text");
+
+        let custom = webhook_body(
+            &notification,
+            WebhookFormat::Json,
+            Some(r#"{"msg": "{{title}}", "body": "{{text}}", "n": {{upgrades}}}"#),
+        );
+        let custom: Value = serde_json::from_str(&custom).unwrap();
+        assert_eq!(custom["body"], notification.text);
+        assert_eq!(custom["n"], 1);
+    }
+
+    #[test]
+    fn test_email_message() {
+        assert_eq!(base64(b"devtool"), "ZGV2dG9vbA==");
+        assert_eq!(base64(b"ab"), "YWI=");
+        let notification = Notification::new(&record(true, &[]), "mbp", "zh");
+        let message = email_message(
+            &notification,
+            "devtool@example.com",
+            &["me@example.com".to_string()],
+        );
+        assert!(message.contains("Subject: =?UTF-8?B?"));
+        assert!(message.contains("To: me@example.com\r\n"));
+        assert!(message.contains("Content-Transfer-Encoding: base64"));
+    }
+
+    #[test]
+    fn test_sink_failures_are_collected() {
+        let tmp = tempdir().unwrap();
+        let runner = RecordingRunner {
+            commands: Mutex::new(Vec::new()),
+            files: Mutex::new(Vec::new()),
+            curl_rc: 22,
+        };
+        let configs = vec![
+            NotifyConfig {
+                on: NotifyOn::Always,
+                sink: SinkConfig::Gotify {
+                    url: "https://gotify.example.com/".to_string(),
+                    token: "secret".to_string(),
+                },
+            },
+            NotifyConfig {
+                on: NotifyOn::Failure,
+                sink: SinkConfig::Webhook {
+                    url: "https://example.com/hook".to_string(),
+                    format: WebhookFormat::Json,
+                    template: None,
+                },
+            },
+            NotifyConfig {
+                on: NotifyOn::Always,
+                sink: SinkConfig::Desktop,
+            },
+        ];
+        let failures = notify_all(&runner, tmp.path(), &configs, &record(true, &[]), "en");
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "gotify");
+
+        let commands = runner.commands.lock().unwrap();
+        assert_eq!(commands.len(), 2);
+        assert!(commands[0].ends_with("'https://gotify.example.com/message'"));
+        assert!(!commands[0].contains("secret"));
+        assert!(commands[1].starts_with("notify-send -a devtool -u normal"));
+        let files = runner.files.lock().unwrap();
+        assert!(files
+            .iter()
+            .any(|file| file.contains("X-Gotify-Key: secret")));
+
+        // 运行目录中只留下命令日志，凭据文件发送后删除
+        let mut left: Vec<String> = std::fs::read_dir(tmp.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(left, vec!["notify_desktop.log", "notify_gotify.log"]);
+    }
+}
@@ -0,0 +1,891 @@
+// 可执行文件归属模块
+// 扫描 PATH 中的可执行文件，识别其所属工具（Homebrew、rustup、cargo、mise），
+// 并找出被多个工具同时提供的命令
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsStr;
+use std::path::{Component, Path, PathBuf};
+use which::which;
+
+use crate::runner::Runner;
+use crate::utils::shell_quote;
+
+/// rustup 在 `~/.cargo/bin` 中安装的代理程序
+const RUSTUP_PROXIES: &[&str] = &[
+    "cargo",
+    "cargo-clippy",
+    "cargo-fmt",
+    "cargo-miri",
+    "clippy-driver",
+    "rls",
+    "rust-analyzer",
+    "rust-gdb",
+    "rust-gdbgui",
+    "rust-lldb",
+    "rustc",
+    "rustdoc",
+    "rustfmt",
+    "rustup",
+];
+
+/// 可执行文件的归属
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "provider", rename_all = "lowercase")]
+pub enum Owner {
+    /// Homebrew formula 或 cask
+    Homebrew {
+        package: String,
+        version: Option<String>,
+        cask: bool,
+    },
+    /// rustup 代理（实际执行的工具链由 rustup 决定）
+    Rustup,
+    /// `cargo install` 安装的程序
+    Cargo {
+        package: Option<String>,
+        version: Option<String>,
+    },
+    /// mise 管理的工具
+    Mise {
+        tool: String,
+        version: Option<String>,
+    },
+    /// 系统自带或其他来源
+    System,
+}
+
+impl Owner {
+    /// 提供者名称
+    pub fn provider(&self) -> &'static str {
+        match self {
+            Owner::Homebrew { .. } => "homebrew",
+            Owner::Rustup => "rustup",
+            Owner::Cargo { .. } => "cargo",
+            Owner::Mise { .. } => "mise",
+            Owner::System => "system",
+        }
+    }
+
+    /// 便于阅读的描述，例如 "homebrew node 21.0.0"、"mise node@20.11.0"
+    pub fn describe(&self) -> String {
+        match self {
+            Owner::Homebrew {
+                package,
+                version,
+                cask,
+            } => {
+                let kind = if *cask { "homebrew cask" } else { "homebrew" };
+                match version {
+                    Some(version) => format!("{} {} {}", kind, package, version),
+                    None => format!("{} {}", kind, package),
+                }
+            }
+            Owner::Mise { tool, version } => match version {
+                Some(version) => format!("mise {}@{}", tool, version),
+                None => format!("mise {}", tool),
+            },
+            Owner::Cargo {
+                package: Some(package),
+                version,
+            } => match version {
+                Some(version) => format!("cargo {} {}", package, version),
+                None => format!("cargo {}", package),
+            },
+            other => other.provider().to_string(),
+        }
+    }
+}
+
+/// 识别归属所需的目录信息
+#[derive(Debug, Clone, Default)]
+pub struct OwnershipContext {
+    /// Homebrew 前缀，例如 /opt/homebrew
+    pub brew_prefix: Option<PathBuf>,
+    /// cargo 主目录，例如 ~/.cargo
+    pub cargo_home: Option<PathBuf>,
+    /// mise 数据目录，例如 ~/.local/share/mise
+    pub mise_data_dir: Option<PathBuf>,
+    /// mise shim 对应的命令 -> (工具, 版本)，用于识别 shim
+    mise_commands: HashMap<String, (String, String)>,
+    /// `cargo install` 安装的命令 -> (crate, 版本)
+    cargo_commands: HashMap<String, (String, String)>,
+}
+
+impl OwnershipContext {
+    /// 根据环境变量和已安装的工具探测目录
+    pub fn detect(runner: &dyn Runner, tmpdir: &Path) -> Self {
+        let home = dirs::home_dir();
+
+        let brew_prefix = std::env::var_os("HOMEBREW_PREFIX")
+            .map(PathBuf::from)
+            .or_else(|| {
+                which("brew").ok()?;
+                match runner.capture("brew --prefix", &tmpdir.join("brew_prefix.log"), false) {
+                    Ok((0, output)) if !output.trim().is_empty() => {
+                        Some(PathBuf::from(output.trim()))
+                    }
+                    _ => None,
+                }
+            });
+
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(".cargo")));
+
+        let mise_data_dir = std::env::var_os("MISE_DATA_DIR")
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("XDG_DATA_HOME").map(|dir| PathBuf::from(dir).join("mise"))
+            })
+            .or_else(|| home.as_ref().map(|home| home.join(".local/share/mise")));
+
+        let mut ctx = Self::new(brew_prefix, cargo_home, mise_data_dir);
+        if which("mise").is_ok() {
+            let logfile = tmpdir.join("mise_ls_current.log");
+            if let Ok((0, output)) = runner.capture("mise ls --current --json", &logfile, false) {
+                ctx.index_mise_current(&parse_mise_current(&output));
+            }
+        }
+        ctx
+    }
+
+    /// 使用指定目录创建上下文，并索引 mise 安装目录中的命令
+    ///
+    /// 只有一个已安装版本提供的命令才能仅凭目录确定归属；多个工具或版本提供的命令
+    /// 由 [`index_mise_current`](Self::index_mise_current) 按当前激活的版本确定
+    pub fn new(
+        brew_prefix: Option<PathBuf>,
+        cargo_home: Option<PathBuf>,
+        mise_data_dir: Option<PathBuf>,
+    ) -> Self {
+        let mut providers: HashMap<String, Option<(String, String)>> = HashMap::new();
+        if let Some(installs) = mise_data_dir.as_ref().map(|dir| dir.join("installs")) {
+            for (tool, version, bin) in list_mise_installs(&installs) {
+                for command in list_executables(&bin) {
+                    let provider = (tool.clone(), version.clone());
+                    providers
+                        .entry(command)
+                        .and_modify(|existing| {
+                            if existing.as_ref() != Some(&provider) {
+                                *existing = None;
+                            }
+                        })
+                        .or_insert(Some(provider));
+                }
+            }
+        }
+        let mise_commands = providers
+            .into_iter()
+            .filter_map(|(command, provider)| Some((command, provider?)))
+            .collect();
+        let cargo_commands = cargo_home
+            .as_ref()
+            .and_then(|home| std::fs::read_to_string(home.join(".crates2.json")).ok())
+            .map(|content| parse_cargo_installs(&content))
+            .unwrap_or_default();
+        Self {
+            brew_prefix,
+            cargo_home,
+            mise_data_dir,
+            mise_commands,
+            cargo_commands,
+        }
+    }
+
+    /// 按 mise 当前激活的版本 (工具, 版本, 安装目录) 索引 shim 对应的命令，覆盖仅凭目录得到的结果
+    pub fn index_mise_current(&mut self, current: &[(String, String, PathBuf)]) {
+        for (tool, version, install_path) in current {
+            for command in list_executables(&install_path.join("bin")) {
+                self.mise_commands
+                    .insert(command, (tool.clone(), version.clone()));
+            }
+        }
+    }
+}
+
+/// `mise ls --current --json` 中的单个版本
+#[derive(Debug, Deserialize)]
+struct MiseCurrentEntry {
+    version: String,
+    install_path: PathBuf,
+    #[serde(default = "default_true")]
+    installed: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 解析 `mise ls --current --json`，返回 (工具, 版本, 安装目录)
+///
+/// 输出格式为以工具名为键的对象，例如
+/// `{"node": [{"version": "20.11.0", "install_path": "~/.local/share/mise/installs/node/20.11.0"}]}`
+fn parse_mise_current(content: &str) -> Vec<(String, String, PathBuf)> {
+    let entries: BTreeMap<String, Vec<MiseCurrentEntry>> =
+        serde_json::from_str(content.trim()).unwrap_or_default();
+    entries
+        .into_iter()
+        .flat_map(|(tool, versions)| {
+            versions
+                .into_iter()
+                .filter(|entry| entry.installed)
+                .map(move |entry| (tool.clone(), entry.version, entry.install_path))
+        })
+        .collect()
+}
+
+/// 解析 `~/.cargo/.crates2.json`，返回命令 -> (crate, 版本)
+///
+/// 键的格式为 "ripgrep 14.1.0 (registry+https://github.com/rust-lang/crates.io-index)"
+fn parse_cargo_installs(content: &str) -> HashMap<String, (String, String)> {
+    let mut commands = HashMap::new();
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return commands;
+    };
+    let Some(installs) = value.get("installs").and_then(|v| v.as_object()) else {
+        return commands;
+    };
+    for (key, install) in installs {
+        let mut parts = key.split_whitespace();
+        let (Some(package), Some(version)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let bins = install.get("bins").and_then(|v| v.as_array());
+        for bin in bins.into_iter().flatten().filter_map(|v| v.as_str()) {
+            commands.insert(bin.to_string(), (package.to_string(), version.to_string()));
+        }
+    }
+    commands
+}
+
+/// 列出 mise 安装目录中的 (工具, 版本, bin 目录)，版本按版本号升序
+fn list_mise_installs(installs: &Path) -> Vec<(String, String, PathBuf)> {
+    let mut result = Vec::new();
+    let Ok(tools) = std::fs::read_dir(installs) else {
+        return result;
+    };
+    for tool in tools.flatten() {
+        let Ok(versions) = std::fs::read_dir(tool.path()) else {
+            continue;
+        };
+        let mut versions: Vec<_> = versions
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .collect();
+        versions.sort_by_key(|entry| {
+            crate::version::Version::parse(&entry.file_name().to_string_lossy())
+        });
+        for version in versions {
+            result.push((
+                tool.file_name().to_string_lossy().to_string(),
+                version.file_name().to_string_lossy().to_string(),
+                version.path().join("bin"),
+            ));
+        }
+    }
+    result
+}
+
+/// 判断路径是否为可执行文件
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    if !metadata.is_file() {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        true
+    }
+}
+
+/// 列出目录中的可执行文件名
+fn list_executables(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| is_executable(&entry.path()))
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+/// 返回路径中紧跟在 `marker` 目录之后的两个组件，例如 Cellar/node/21.0.0
+fn components_after<'a>(path: &'a Path, marker: &str) -> Option<(&'a str, Option<&'a str>)> {
+    let parts: Vec<&str> = path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect();
+    let index = parts.iter().position(|part| *part == marker)?;
+    let first = parts.get(index + 1)?;
+    Some((first, parts.get(index + 2).copied()))
+}
+
+/// 识别可执行文件的归属
+///
+/// `path` 为 PATH 中的位置，`resolved` 为解析符号链接后的真实路径
+pub fn classify(path: &Path, resolved: &Path, ctx: &OwnershipContext) -> Owner {
+    let command = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if let Some((package, version)) = components_after(resolved, "Cellar") {
+        return Owner::Homebrew {
+            package: package.to_string(),
+            version: version.map(|v| v.to_string()),
+            cask: false,
+        };
+    }
+    if let Some((package, version)) = components_after(resolved, "Caskroom") {
+        return Owner::Homebrew {
+            package: package.to_string(),
+            version: version.map(|v| v.to_string()),
+            cask: true,
+        };
+    }
+
+    if let Some(mise_dir) = &ctx.mise_data_dir {
+        if let Ok(rest) = path.strip_prefix(mise_dir.join("installs")) {
+            let mut parts = rest.iter().map(|part| part.to_string_lossy().to_string());
+            if let Some(tool) = parts.next() {
+                return Owner::Mise {
+                    tool,
+                    version: parts.next(),
+                };
+            }
+        }
+        if path.starts_with(mise_dir.join("shims")) {
+            let (tool, version) = ctx
+                .mise_commands
+                .get(&command)
+                .map(|(tool, version)| (tool.clone(), Some(version.clone())))
+                .unwrap_or_else(|| (command.clone(), None));
+            return Owner::Mise { tool, version };
+        }
+    }
+
+    if let Some(cargo_home) = &ctx.cargo_home {
+        if path.parent() == Some(cargo_home.join("bin").as_path()) {
+            let resolved_name = resolved
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if RUSTUP_PROXIES.contains(&command.as_str()) || resolved_name.starts_with("rustup") {
+                return Owner::Rustup;
+            }
+            let (package, version) = match ctx.cargo_commands.get(&command) {
+                Some((package, version)) => (Some(package.clone()), Some(version.clone())),
+                None => (None, None),
+            };
+            return Owner::Cargo { package, version };
+        }
+    }
+
+    // 位于 Homebrew 前缀下但不是 Cellar 链接的程序（例如 brew 本身）
+    if let Some(prefix) = &ctx.brew_prefix {
+        if path.starts_with(prefix) {
+            return Owner::Homebrew {
+                package: command,
+                version: None,
+                cask: false,
+            };
+        }
+    }
+
+    Owner::System
+}
+
+/// PATH 中的一个命令提供者
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Provision {
+    pub path: PathBuf,
+    pub resolved: PathBuf,
+    pub owner: Owner,
+}
+
+/// 被多个工具提供的命令
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Conflict {
+    pub command: String,
+    /// PATH 中最先出现、实际生效的副本
+    pub winner: Provision,
+    /// 被遮蔽的副本
+    pub shadowed: Vec<Provision>,
+}
+
+/// PATH 中所有命令的归属表
+#[derive(Debug, Clone, Default)]
+pub struct OwnershipMap {
+    commands: BTreeMap<String, Vec<Provision>>,
+}
+
+impl OwnershipMap {
+    /// 按 PATH 顺序扫描所有可执行文件
+    pub fn scan(path_var: &OsStr, ctx: &OwnershipContext) -> Self {
+        let mut commands: BTreeMap<String, Vec<Provision>> = BTreeMap::new();
+        let mut seen_dirs: Vec<PathBuf> = Vec::new();
+
+        for dir in std::env::split_paths(path_var) {
+            if seen_dirs.contains(&dir) {
+                continue;
+            }
+            seen_dirs.push(dir.clone());
+
+            for command in list_executables(&dir) {
+                let path = dir.join(&command);
+                let resolved = path.canonicalize().unwrap_or_else(|_| path.clone());
+                let provisions = commands.entry(command).or_default();
+                // 同一文件通过不同 PATH 目录出现多次时只记录一次
+                if provisions.iter().any(|p| p.resolved == resolved) {
+                    continue;
+                }
+                let owner = classify(&path, &resolved, ctx);
+                provisions.push(Provision {
+                    path,
+                    resolved,
+                    owner,
+                });
+            }
+        }
+
+        Self { commands }
+    }
+
+    /// 命令在 PATH 中的所有提供者（按 PATH 顺序）
+    pub fn provisions(&self, command: &str) -> &[Provision] {
+        self.commands
+            .get(command)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// 由两个及以上不同工具提供的命令，至少一个提供者由 devtool 支持的工具或 cargo 管理
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        self.commands
+            .iter()
+            .filter_map(|(command, provisions)| {
+                let mut providers: Vec<&str> =
+                    provisions.iter().map(|p| p.owner.provider()).collect();
+                providers.sort();
+                providers.dedup();
+                let managed = provisions.iter().any(|p| p.owner != Owner::System);
+                if providers.len() < 2 || !managed {
+                    return None;
+                }
+                let (winner, shadowed) = provisions.split_first()?;
+                Some(Conflict {
+                    command: command.clone(),
+                    winner: winner.clone(),
+                    shadowed: shadowed
+                        .iter()
+                        .filter(|p| p.owner.provider() != winner.owner.provider())
+                        .cloned()
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// 被遮蔽且满足条件的副本，返回 (冲突, 被遮蔽的副本)
+    pub fn shadowed_copies<F>(&self, predicate: F) -> Vec<(Conflict, Provision)>
+    where
+        F: Fn(&Owner) -> bool,
+    {
+        self.conflicts()
+            .into_iter()
+            .flat_map(|conflict| {
+                conflict
+                    .shadowed
+                    .iter()
+                    .filter(|p| predicate(&p.owner))
+                    .cloned()
+                    .map(|p| (conflict.clone(), p))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// rustup 代理对应的组件
+pub fn rustup_component(command: &str) -> Option<&'static str> {
+    let component = match command {
+        "rustc" | "rustdoc" | "rust-gdb" | "rust-gdbgui" | "rust-lldb" => "rustc",
+        "cargo" => "cargo",
+        "rustfmt" | "cargo-fmt" => "rustfmt",
+        "cargo-clippy" | "clippy-driver" => "clippy",
+        "rust-analyzer" => "rust-analyzer",
+        "cargo-miri" => "miri",
+        "rls" => "rls",
+        _ => return None,
+    };
+    Some(component)
+}
+
+/// 升级单个项目的命令，未被支持的工具管理时返回 `None`
+///
+/// rustup 代理升级其所在的工具链，未知工具链时升级全部工具链
+pub fn upgrade_command(owner: &Owner, toolchain: Option<&str>) -> Option<String> {
+    match owner {
+        Owner::Homebrew { package, cask, .. } => Some(if *cask {
+            format!("brew upgrade --cask {}", shell_quote(package))
+        } else {
+            format!("brew upgrade {}", shell_quote(package))
+        }),
+        Owner::Rustup => Some(match toolchain {
+            Some(toolchain) => format!("rustup update {}", shell_quote(toolchain)),
+            None => "rustup update".to_string(),
+        }),
+        Owner::Cargo {
+            package: Some(package),
+            ..
+        } => Some(format!("cargo install {}", shell_quote(package))),
+        Owner::Mise { tool, .. } => Some(format!("mise upgrade {}", shell_quote(tool))),
+        Owner::Cargo { package: None, .. } | Owner::System => None,
+    }
+}
+
+/// 从 `--version` 输出中提取版本号，例如 "ripgrep 14.1.0 (rev ...)" -> "14.1.0"
+fn parse_version_output(output: &str) -> Option<String> {
+    output
+        .lines()
+        .next()?
+        .split_whitespace()
+        .map(|word| word.trim_start_matches('v'))
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()) && word.contains('.'))
+        .map(|word| word.trim_end_matches([',', ';', ')']).to_string())
+}
+
+/// 从 `rustup which` 返回的路径中提取工具链名称
+fn toolchain_from_path(path: &str) -> Option<String> {
+    components_after(Path::new(path.trim()), "toolchains").map(|(name, _)| name.to_string())
+}
+
+/// 命令的解析结果
+#[derive(Debug, Clone, Serialize)]
+pub struct Resolution {
+    pub command: String,
+    /// PATH 中生效的位置
+    pub path: PathBuf,
+    /// 解析符号链接和 shim 后的真实路径
+    pub resolved: PathBuf,
+    pub owner: Owner,
+    /// rustup 代理当前使用的工具链
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<String>,
+    /// rustup 代理对应的组件
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    pub version: Option<String>,
+    /// 只升级该项目的命令
+    pub upgrade: Option<String>,
+    /// 被遮蔽的其他副本
+    pub shadowed: Vec<Provision>,
+}
+
+/// 解析命令：定位 PATH 中生效的副本，穿透 rustup 代理和 mise shim，识别归属、版本和升级命令
+pub fn resolve(
+    map: &OwnershipMap,
+    command: &str,
+    runner: &dyn Runner,
+    tmpdir: &Path,
+) -> Option<Resolution> {
+    let (winner, shadowed) = map.provisions(command).split_first()?;
+    let logfile = tmpdir.join("which.log");
+    let mut resolved = winner.resolved.clone();
+    let mut toolchain = None;
+    let mut owner = winner.owner.clone();
+
+    match &winner.owner {
+        Owner::Rustup => {
+            let cmd = format!("rustup which {}", shell_quote(command));
+            if let Ok((0, output)) = runner.capture(&cmd, &logfile, false) {
+                toolchain = toolchain_from_path(&output);
+                resolved = PathBuf::from(output.trim());
+            }
+        }
+        Owner::Mise { .. } if which("mise").is_ok() => {
+            let cmd = format!("mise which {}", shell_quote(command));
+            if let Ok((0, output)) = runner.capture(&cmd, &logfile, false) {
+                resolved = PathBuf::from(output.trim());
+                // 实际执行的安装目录，例如 installs/node/20.11.0/bin/node
+                if let Some((tool, Some(version))) = components_after(&resolved, "installs") {
+                    owner = Owner::Mise {
+                        tool: tool.to_string(),
+                        version: Some(version.to_string()),
+                    };
+                }
+            }
+        }
+        _ => {}
+    }
+
+    let version = match &owner {
+        Owner::Homebrew {
+            version: Some(version),
+            ..
+        }
+        | Owner::Mise {
+            version: Some(version),
+            ..
+        }
+        | Owner::Cargo {
+            version: Some(version),
+            ..
+        } => Some(version.clone()),
+        _ => {
+            let cmd = format!("{} --version", shell_quote(&resolved.to_string_lossy()));
+            match runner.capture(&cmd, &logfile, false) {
+                Ok((0, output)) => parse_version_output(&output),
+                _ => None,
+            }
+        }
+    };
+
+    Some(Resolution {
+        command: command.to_string(),
+        path: winner.path.clone(),
+        resolved,
+        upgrade: upgrade_command(&owner, toolchain.as_deref()),
+        component: match owner {
+            Owner::Rustup => rustup_component(command).map(|c| c.to_string()),
+            _ => None,
+        },
+        owner,
+        toolchain,
+        version,
+        shadowed: shadowed.to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::{symlink, PermissionsExt};
+    use tempfile::{tempdir, TempDir};
+
+    fn touch_executable(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    /// 构造包含 Homebrew、cargo、mise 的目录布局
+    fn layout() -> (TempDir, OwnershipContext, std::ffi::OsString) {
+        let root = tempdir().unwrap();
+        let prefix = root.path().join("homebrew");
+        let cargo = root.path().join("cargo");
+        let mise = root.path().join("mise");
+
+        touch_executable(&prefix.join("Cellar/node/21.0.0/bin/node"));
+        touch_executable(&prefix.join("Cellar/rust/1.79.0/bin/rustc"));
+        fs::create_dir_all(prefix.join("bin")).unwrap();
+        symlink(
+            prefix.join("Cellar/node/21.0.0/bin/node"),
+            prefix.join("bin/node"),
+        )
+        .unwrap();
+        symlink(
+            prefix.join("Cellar/rust/1.79.0/bin/rustc"),
+            prefix.join("bin/rustc"),
+        )
+        .unwrap();
+
+        touch_executable(&cargo.join("bin/rustc"));
+        touch_executable(&cargo.join("bin/rg"));
+
+        touch_executable(&mise.join("installs/node/20.11.0/bin/node"));
+        touch_executable(&mise.join("shims/node"));
+
+        let ctx = OwnershipContext::new(
+            Some(prefix.clone()),
+            Some(cargo.clone()),
+            Some(mise.clone()),
+        );
+        let path_var =
+            std::env::join_paths([mise.join("shims"), prefix.join("bin"), cargo.join("bin")])
+                .unwrap();
+        (root, ctx, path_var)
+    }
+
+    #[test]
+    fn test_classify_owners() {
+        let (_root, ctx, path_var) = layout();
+        let map = OwnershipMap::scan(&path_var, &ctx);
+
+        assert_eq!(
+            map.provisions("node")[0].owner,
+            Owner::Mise {
+                tool: "node".to_string(),
+                version: Some("20.11.0".to_string())
+            }
+        );
+        assert_eq!(
+            map.provisions("node")[1].owner,
+            Owner::Homebrew {
+                package: "node".to_string(),
+                version: Some("21.0.0".to_string()),
+                cask: false
+            }
+        );
+        assert_eq!(map.provisions("rustc")[1].owner, Owner::Rustup);
+        assert_eq!(
+            map.provisions("rg")[0].owner,
+            Owner::Cargo {
+                package: None,
+                version: None
+            }
+        );
+        assert!(map.provisions("missing").is_empty());
+    }
+
+    #[test]
+    fn test_mise_shim_uses_current_version() {
+        let root = tempdir().unwrap();
+        let mise = root.path().join("mise");
+        touch_executable(&mise.join("installs/node/20.11.0/bin/node"));
+        touch_executable(&mise.join("installs/node/22.1.0/bin/node"));
+        touch_executable(&mise.join("installs/python/3.12.1/bin/python3"));
+        touch_executable(&mise.join("shims/node"));
+        touch_executable(&mise.join("shims/python3"));
+        let shim = mise.join("shims/node");
+
+        // 多个版本都提供的命令不能仅凭目录确定版本
+        let mut ctx = OwnershipContext::new(None, None, Some(mise.clone()));
+        assert_eq!(
+            classify(&shim, &shim, &ctx),
+            Owner::Mise {
+                tool: "node".to_string(),
+                version: None
+            }
+        );
+        let python = mise.join("shims/python3");
+        assert_eq!(
+            classify(&python, &python, &ctx),
+            Owner::Mise {
+                tool: "python".to_string(),
+                version: Some("3.12.1".to_string())
+            }
+        );
+
+        let output = format!(
+            r#"{{"node": [{{"version": "20.11.0", "install_path": "{}", "installed": true}}]}}"#,
+            mise.join("installs/node/20.11.0").display()
+        );
+        ctx.index_mise_current(&parse_mise_current(&output));
+        assert_eq!(
+            classify(&shim, &shim, &ctx),
+            Owner::Mise {
+                tool: "node".to_string(),
+                version: Some("20.11.0".to_string())
+            }
+        );
+        assert!(parse_mise_current("not json").is_empty());
+    }
+
+    #[test]
+    fn test_conflicts_and_shadowed_copies() {
+        let (_root, ctx, path_var) = layout();
+        let map = OwnershipMap::scan(&path_var, &ctx);
+
+        let conflicts = map.conflicts();
+        let commands: Vec<&str> = conflicts.iter().map(|c| c.command.as_str()).collect();
+        assert_eq!(commands, vec!["node", "rustc"]);
+        assert_eq!(conflicts[0].winner.owner.provider(), "mise");
+        assert_eq!(conflicts[1].winner.owner.provider(), "homebrew");
+
+        // rustup 的 rustc 被 Homebrew 的 rust 遮蔽
+        let shadowed = map.shadowed_copies(|owner| *owner == Owner::Rustup);
+        assert_eq!(shadowed.len(), 1);
+        assert_eq!(shadowed[0].0.command, "rustc");
+    }
+
+    #[test]
+    fn test_owner_describe() {
+        let owner = Owner::Mise {
+            tool: "node".to_string(),
+            version: Some("20.11.0".to_string()),
+        };
+        assert_eq!(owner.describe(), "mise node@20.11.0");
+        let cargo = Owner::Cargo {
+            package: None,
+            version: None,
+        };
+        assert_eq!(cargo.describe(), "cargo");
+    }
+
+    #[test]
+    fn test_upgrade_command() {
+        let brew = Owner::Homebrew {
+            package: "ripgrep".to_string(),
+            version: None,
+            cask: false,
+        };
+        assert_eq!(
+            upgrade_command(&brew, None).as_deref(),
+            Some("brew upgrade 'ripgrep'")
+        );
+        assert_eq!(
+            upgrade_command(&Owner::Rustup, Some("stable-aarch64-apple-darwin")).as_deref(),
+            Some("rustup update 'stable-aarch64-apple-darwin'")
+        );
+        let cargo = Owner::Cargo {
+            package: Some("ripgrep".to_string()),
+            version: Some("14.1.0".to_string()),
+        };
+        assert_eq!(
+            upgrade_command(&cargo, None).as_deref(),
+            Some("cargo install 'ripgrep'")
+        );
+        assert_eq!(upgrade_command(&Owner::System, None), None);
+    }
+
+    #[test]
+    fn test_parse_version_output() {
+        assert_eq!(
+            parse_version_output("ripgrep 14.1.0 (rev 1234)\n+PCRE2").as_deref(),
+            Some("14.1.0")
+        );
+        assert_eq!(
+            parse_version_output("rustc 1.80.0 (051478957 2024-07-21)").as_deref(),
+            Some("1.80.0")
+        );
+        assert_eq!(
+            parse_version_output("node v20.11.0").as_deref(),
+            Some("20.11.0")
+        );
+        assert_eq!(parse_version_output("no version"), None);
+    }
+
+    #[test]
+    fn test_toolchain_and_cargo_installs() {
+        assert_eq!(
+            toolchain_from_path(
+                "/home/u/.rustup/toolchains/stable-x86_64-unknown-linux-gnu/bin/rustc\n"
+            )
+            .as_deref(),
+            Some("stable-x86_64-unknown-linux-gnu")
+        );
+        let installs = parse_cargo_installs(
+            r#"{"installs":{"ripgrep 14.1.0 (registry+https://github.com/rust-lang/crates.io-index)":{"bins":["rg"]}}}"#,
+        );
+        assert_eq!(
+            installs.get("rg"),
+            Some(&("ripgrep".to_string(), "14.1.0".to_string()))
+        );
+    }
+}
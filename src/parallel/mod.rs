@@ -4,13 +4,16 @@
 //! including dependency management, task scheduling, and progress reporting.
 
 use anyhow::Result;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 
 /// Represents a tool that can be updated
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
 pub enum Tool {
     Homebrew,
     Rustup,
@@ -44,6 +47,18 @@ impl DependencyGraph {
         }
     }
 
+    /// Declare that `tool` must wait for `depends_on` to finish
+    pub fn add_dependency(&mut self, tool: Tool, depends_on: Tool) {
+        self.dependencies
+            .entry(tool.clone())
+            .or_default()
+            .push(depends_on.clone());
+        self.reverse_dependencies
+            .entry(depends_on)
+            .or_default()
+            .push(tool);
+    }
+
     /// Get tools that have no dependencies (can be run first)
     pub fn get_ready_tools(&self, available_tools: &HashSet<Tool>) -> Vec<Tool> {
         available_tools
@@ -97,31 +112,57 @@ pub struct TaskResult {
 pub struct ParallelScheduler {
     completed_tools: Arc<Mutex<HashSet<Tool>>>,
     dependency_graph: Arc<DependencyGraph>,
+    max_concurrent: usize,
 }
 
 impl ParallelScheduler {
-    /// Create a new parallel scheduler
-    pub fn new(_max_concurrent: usize) -> Self {
+    /// Create a new parallel scheduler running at most `max_concurrent` tasks at once
+    pub fn new(max_concurrent: usize) -> Self {
         Self {
             completed_tools: Arc::new(Mutex::new(HashSet::new())),
             dependency_graph: Arc::new(DependencyGraph::default()),
+            max_concurrent: max_concurrent.max(1),
+        }
+    }
+
+    /// Create a scheduler with a custom dependency graph and no concurrency limit
+    ///
+    /// Every dependency must also be part of the executed tool set,
+    /// otherwise the dependent tool is never started.
+    pub fn with_dependency_graph(dependency_graph: DependencyGraph) -> Self {
+        Self {
+            completed_tools: Arc::new(Mutex::new(HashSet::new())),
+            dependency_graph: Arc::new(dependency_graph),
+            max_concurrent: usize::MAX,
         }
     }
 
     /// Execute tools in parallel with dependency management
+    ///
+    /// Ready tools are started in the given order while fewer than
+    /// `max_concurrent` tasks are running.
     pub async fn execute_parallel(
         &self,
         tools: Vec<Tool>,
         update_fn: impl Fn(Tool) -> JoinHandle<Result<TaskResult>> + Send + Sync + 'static,
     ) -> Result<Vec<TaskResult>> {
         let mut results = Vec::new();
-        let mut pending_tools: HashSet<Tool> = tools.into_iter().collect();
-        let mut running_tasks: Vec<JoinHandle<Result<TaskResult>>> = Vec::new();
+        let mut pending_tools: HashSet<Tool> = tools.iter().cloned().collect();
+        let mut running_tasks: Vec<(Tool, JoinHandle<Result<TaskResult>>)> = Vec::new();
 
-        while !pending_tools.is_empty() || !running_tasks.is_empty() {
+        // Tools whose dependencies are satisfied, waiting for a free slot
+        let initially_ready = self.dependency_graph.get_ready_tools(&pending_tools);
+        let mut ready_queue: Vec<Tool> = Vec::new();
+        for tool in tools {
+            if initially_ready.contains(&tool) && pending_tools.remove(&tool) {
+                ready_queue.push(tool);
+            }
+        }
+
+        while !pending_tools.is_empty() || !ready_queue.is_empty() || !running_tasks.is_empty() {
             // Check for completed tasks
             let mut completed_indices = Vec::new();
-            for (i, task) in running_tasks.iter().enumerate() {
+            for (i, (_, task)) in running_tasks.iter().enumerate() {
                 if task.is_finished() {
                     completed_indices.push(i);
                 }
@@ -129,48 +170,52 @@ impl ParallelScheduler {
 
             // Process completed tasks
             for &i in completed_indices.iter().rev() {
-                let task = running_tasks.remove(i);
-                if let Ok(result) = task.await? {
-                    let tool = result.tool.clone();
-                    results.push(result);
-
-                    // Mark tool as completed
-                    {
-                        let mut completed = self.completed_tools.lock().await;
-                        completed.insert(tool.clone());
-                    }
+                let (tool, task) = running_tasks.remove(i);
+                // A task that errors or panics counts as a failed result,
+                // so tools depending on it are not left waiting
+                let result = match task.await {
+                    Ok(Ok(result)) => result,
+                    Ok(Err(e)) => TaskResult {
+                        tool: tool.clone(),
+                        success: false,
+                        output: format!("{:#}", e),
+                    },
+                    Err(e) => TaskResult {
+                        tool: tool.clone(),
+                        success: false,
+                        output: e.to_string(),
+                    },
+                };
+                results.push(result);
 
-                    // Check if any pending tools can now be executed
-                    for dependent_tool in self.dependency_graph.get_dependent_tools(&tool) {
-                        if pending_tools.contains(&dependent_tool) {
-                            let can_execute = {
-                                let completed = self.completed_tools.lock().await;
-                                self.dependency_graph
-                                    .can_execute(&dependent_tool, &completed)
-                            };
-
-                            if can_execute {
-                                pending_tools.remove(&dependent_tool);
-                                let task = update_fn(dependent_tool);
-                                running_tasks.push(task);
-                            }
+                // Mark tool as completed
+                {
+                    let mut completed = self.completed_tools.lock().await;
+                    completed.insert(tool.clone());
+                }
+
+                // Queue pending tools that can now be executed
+                for dependent_tool in self.dependency_graph.get_dependent_tools(&tool) {
+                    if pending_tools.contains(&dependent_tool) {
+                        let can_execute = {
+                            let completed = self.completed_tools.lock().await;
+                            self.dependency_graph
+                                .can_execute(&dependent_tool, &completed)
+                        };
+
+                        if can_execute {
+                            pending_tools.remove(&dependent_tool);
+                            ready_queue.push(dependent_tool);
                         }
                     }
                 }
             }
 
-            // Start new tasks if we have capacity and ready tools
-            let ready_tools = {
-                let _completed = self.completed_tools.lock().await;
-                self.dependency_graph.get_ready_tools(&pending_tools)
-            };
-
-            for tool in ready_tools {
-                if pending_tools.contains(&tool) {
-                    pending_tools.remove(&tool);
-                    let task = update_fn(tool);
-                    running_tasks.push(task);
-                }
+            // Start queued tools while there is capacity
+            while running_tasks.len() < self.max_concurrent && !ready_queue.is_empty() {
+                let tool = ready_queue.remove(0);
+                let task = update_fn(tool.clone());
+                running_tasks.push((tool, task));
             }
 
             // Small delay to prevent busy waiting
@@ -199,6 +244,107 @@ mod tests {
         assert_eq!(ready_tools.len(), 3); // All tools should be ready initially
     }
 
+    #[test]
+    fn test_dependency_graph_with_dependency() {
+        let mut graph = DependencyGraph::new();
+        graph.add_dependency(Tool::Mise, Tool::Homebrew);
+
+        let available_tools: HashSet<Tool> = [Tool::Homebrew, Tool::Mise].into();
+        assert_eq!(
+            graph.get_ready_tools(&available_tools),
+            vec![Tool::Homebrew]
+        );
+        assert_eq!(graph.get_dependent_tools(&Tool::Homebrew), vec![Tool::Mise]);
+        assert!(!graph.can_execute(&Tool::Mise, &HashSet::new()));
+        assert!(graph.can_execute(&Tool::Mise, &[Tool::Homebrew].into()));
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_respects_dependencies() {
+        let mut graph = DependencyGraph::new();
+        graph.add_dependency(Tool::Rustup, Tool::Homebrew);
+        let scheduler = ParallelScheduler::with_dependency_graph(graph);
+
+        let results = scheduler
+            .execute_parallel(vec![Tool::Rustup, Tool::Homebrew], |tool| {
+                tokio::spawn(async move {
+                    Ok(TaskResult {
+                        tool,
+                        success: true,
+                        output: String::new(),
+                    })
+                })
+            })
+            .await
+            .unwrap();
+
+        let order: Vec<Tool> = results.into_iter().map(|r| r.tool).collect();
+        assert_eq!(order, vec![Tool::Homebrew, Tool::Rustup]);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_runs_dependents_after_failed_task() {
+        let mut graph = DependencyGraph::new();
+        graph.add_dependency(Tool::Rustup, Tool::Homebrew);
+        let scheduler = ParallelScheduler::with_dependency_graph(graph);
+
+        let results = scheduler
+            .execute_parallel(vec![Tool::Homebrew, Tool::Rustup], |tool| {
+                tokio::spawn(async move {
+                    if tool == Tool::Homebrew {
+                        anyhow::bail!("brew exploded");
+                    }
+                    Ok(TaskResult {
+                        tool,
+                        success: true,
+                        output: String::new(),
+                    })
+                })
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].tool, Tool::Homebrew);
+        assert!(!results[0].success);
+        assert_eq!(results[0].output, "brew exploded");
+        assert!(results[1].success);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_limits_concurrency() {
+        let running = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let peak = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let scheduler = ParallelScheduler::new(1);
+
+        let (running_in_task, peak_in_task) = (running.clone(), peak.clone());
+        let results = scheduler
+            .execute_parallel(
+                vec![Tool::Homebrew, Tool::Rustup, Tool::Mise],
+                move |tool| {
+                    let running = running_in_task.clone();
+                    let peak = peak_in_task.clone();
+                    tokio::spawn(async move {
+                        use std::sync::atomic::Ordering;
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        Ok(TaskResult {
+                            tool,
+                            success: true,
+                            output: String::new(),
+                        })
+                    })
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(peak.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn test_tool_display_names() {
         assert_eq!(Tool::Homebrew.display_name(), "Homebrew");
@@ -0,0 +1,388 @@
+// 更新策略模块
+// 维护窗口与更新冻结期：决定当前时间是否允许执行更新
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::config::PolicyConfig;
+
+/// 维护窗口，例如 "Mon-Fri 08:00-10:00"
+///
+/// 结束时间早于开始时间表示窗口跨越午夜。
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaintenanceWindow {
+    days: Vec<Weekday>,
+    start: NaiveTime,
+    end: NaiveTime,
+    spec: String,
+}
+
+/// 更新冻结期（包含起止日期）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Freeze {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub reason: Option<String>,
+}
+
+/// 策略判定结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyDecision {
+    /// 允许更新
+    Allowed,
+    /// 处于冻结期
+    Frozen(Freeze),
+    /// 不在任何维护窗口内
+    OutsideWindow,
+}
+
+/// 更新策略
+#[derive(Debug, Clone, Default)]
+pub struct UpdatePolicy {
+    windows: Vec<MaintenanceWindow>,
+    freezes: Vec<Freeze>,
+}
+
+/// 解析星期缩写（英文三字母或全称）
+fn parse_weekday(input: &str) -> Result<Weekday> {
+    input
+        .trim()
+        .parse::<Weekday>()
+        .map_err(|_| anyhow!("invalid weekday: {:?}", input))
+}
+
+/// 解析星期范围，例如 "Mon-Fri"、"Sat,Sun"、"daily"
+pub(crate) fn parse_days(input: &str) -> Result<Vec<Weekday>> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("daily") || input == "*" {
+        return Ok(vec![
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]);
+    }
+    if input.eq_ignore_ascii_case("weekdays") {
+        return parse_days("Mon-Fri");
+    }
+    if input.eq_ignore_ascii_case("weekends") {
+        return parse_days("Sat,Sun");
+    }
+
+    let mut days = Vec::new();
+    for part in input.split(',') {
+        if let Some((first, last)) = part.split_once('-') {
+            let mut day = parse_weekday(first)?;
+            let last = parse_weekday(last)?;
+            days.push(day);
+            while day != last {
+                day = day.succ();
+                days.push(day);
+            }
+        } else {
+            days.push(parse_weekday(part)?);
+        }
+    }
+    Ok(days)
+}
+
+pub(crate) fn parse_time(input: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(input.trim(), "%H:%M")
+        .map_err(|_| anyhow!("invalid time (expected HH:MM): {:?}", input))
+}
+
+fn parse_date(input: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d")
+        .map_err(|_| anyhow!("invalid date (expected YYYY-MM-DD): {:?}", input))
+}
+
+impl MaintenanceWindow {
+    /// 解析维护窗口描述，格式为 "<星期> <开始>-<结束>"，星期部分可省略（表示每天）
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (days_part, time_part) = match spec.rsplit_once(' ') {
+            Some((days, time)) => (days, time),
+            None => ("daily", spec),
+        };
+        let (start, end) = time_part
+            .split_once('-')
+            .ok_or_else(|| anyhow!("invalid window (expected HH:MM-HH:MM): {:?}", spec))?;
+
+        Ok(Self {
+            days: parse_days(days_part)?,
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+            spec: spec.to_string(),
+        })
+    }
+
+    /// 原始描述
+    pub fn spec(&self) -> &str {
+        &self.spec
+    }
+
+    fn wraps_midnight(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// 判断时间点是否落在窗口内
+    pub fn contains(&self, at: NaiveDateTime) -> bool {
+        let time = at.time();
+        let today = at.date().weekday();
+        if self.wraps_midnight() {
+            (self.days.contains(&today) && time >= self.start)
+                || (self.days.contains(&today.pred()) && time < self.end)
+        } else {
+            self.days.contains(&today) && time >= self.start && time < self.end
+        }
+    }
+
+    /// 指定日期的窗口开始时间（若该日有窗口）
+    fn start_on(&self, date: NaiveDate) -> Option<NaiveDateTime> {
+        self.days
+            .contains(&date.weekday())
+            .then(|| date.and_time(self.start))
+    }
+}
+
+impl Freeze {
+    /// 判断时间点是否处于冻结期
+    pub fn contains(&self, at: NaiveDateTime) -> bool {
+        let date = at.date();
+        date >= self.from && date <= self.to
+    }
+
+    /// 冻结期结束后的第一个时间点
+    fn ends_at(&self) -> NaiveDateTime {
+        (self.to + Duration::days(1)).and_time(NaiveTime::MIN)
+    }
+}
+
+impl UpdatePolicy {
+    /// 从配置构建更新策略
+    pub fn from_config(config: &PolicyConfig) -> Result<Self> {
+        let windows = config
+            .windows
+            .iter()
+            .map(|spec| MaintenanceWindow::parse(spec))
+            .collect::<Result<Vec<_>>>()
+            .context("parse [policy] windows")?;
+
+        let freezes = config
+            .freeze
+            .iter()
+            .map(|freeze| {
+                let from = parse_date(&freeze.from)?;
+                let to = parse_date(&freeze.to)?;
+                if from > to {
+                    bail!(
+                        "freeze starts after it ends: {} > {}",
+                        freeze.from,
+                        freeze.to
+                    );
+                }
+                Ok(Freeze {
+                    from,
+                    to,
+                    reason: freeze.reason.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()
+            .context("parse [[policy.freeze]]")?;
+
+        Ok(Self { windows, freezes })
+    }
+
+    /// 是否配置了任何限制
+    pub fn is_restricted(&self) -> bool {
+        !self.windows.is_empty() || !self.freezes.is_empty()
+    }
+
+    /// 已配置的维护窗口
+    pub fn windows(&self) -> &[MaintenanceWindow] {
+        &self.windows
+    }
+
+    /// 已配置的冻结期
+    pub fn freezes(&self) -> &[Freeze] {
+        &self.freezes
+    }
+
+    /// 判断当前本地时间是否允许更新
+    pub fn check_now(&self) -> PolicyDecision {
+        self.check(Local::now().naive_local())
+    }
+
+    /// 判断指定时间是否允许更新
+    pub fn check(&self, at: NaiveDateTime) -> PolicyDecision {
+        if let Some(freeze) = self.freezes.iter().find(|f| f.contains(at)) {
+            return PolicyDecision::Frozen(freeze.clone());
+        }
+        if !self.windows.is_empty() && !self.windows.iter().any(|w| w.contains(at)) {
+            return PolicyDecision::OutsideWindow;
+        }
+        PolicyDecision::Allowed
+    }
+
+    /// 计算从指定时间起下一个允许更新的时间点
+    ///
+    /// 当前时间已允许时返回 `at` 本身；一年内找不到允许的时间时返回 `None`。
+    pub fn next_allowed(&self, at: NaiveDateTime) -> Option<NaiveDateTime> {
+        if self.check(at) == PolicyDecision::Allowed {
+            return Some(at);
+        }
+
+        // 候选时间点：各维护窗口的开始时间以及冻结期结束时间
+        let mut candidates: Vec<NaiveDateTime> = self
+            .freezes
+            .iter()
+            .map(|f| f.ends_at())
+            .filter(|t| *t > at)
+            .collect();
+        for offset in 0..=366 {
+            let date = at.date() + Duration::days(offset);
+            candidates.extend(
+                self.windows
+                    .iter()
+                    .filter_map(|w| w.start_on(date))
+                    .filter(|t| *t > at),
+            );
+        }
+
+        candidates.sort();
+        candidates
+            .into_iter()
+            .find(|t| self.check(*t) == PolicyDecision::Allowed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FreezeConfig;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn policy(windows: &[&str], freezes: &[(&str, &str)]) -> UpdatePolicy {
+        UpdatePolicy::from_config(&PolicyConfig {
+            windows: windows.iter().map(|w| w.to_string()).collect(),
+            freeze: freezes
+                .iter()
+                .map(|(from, to)| FreezeConfig {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    reason: None,
+                })
+                .collect(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_window() {
+        let window = MaintenanceWindow::parse("Mon-Fri 08:00-10:00").unwrap();
+        assert_eq!(window.days.len(), 5);
+        assert!(MaintenanceWindow::parse("Sat,Sun 22:00-02:00").is_ok());
+        assert!(MaintenanceWindow::parse("09:00-17:00").is_ok());
+        assert!(MaintenanceWindow::parse("Funday 08:00-10:00").is_err());
+        assert!(MaintenanceWindow::parse("Mon 8am").is_err());
+    }
+
+    #[test]
+    fn test_window_contains() {
+        let policy = policy(&["Mon-Fri 08:00-10:00"], &[]);
+        // 2026-10-19 是星期一
+        assert_eq!(
+            policy.check(at("2026-10-19 08:30")),
+            PolicyDecision::Allowed
+        );
+        assert_eq!(
+            policy.check(at("2026-10-19 10:00")),
+            PolicyDecision::OutsideWindow
+        );
+        assert_eq!(
+            policy.check(at("2026-10-18 09:00")),
+            PolicyDecision::OutsideWindow
+        );
+    }
+
+    #[test]
+    fn test_window_wraps_midnight() {
+        let policy = policy(&["Fri 22:00-02:00"], &[]);
+        // 2026-10-23 是星期五
+        assert_eq!(
+            policy.check(at("2026-10-23 23:00")),
+            PolicyDecision::Allowed
+        );
+        assert_eq!(
+            policy.check(at("2026-10-24 01:30")),
+            PolicyDecision::Allowed
+        );
+        assert_eq!(
+            policy.check(at("2026-10-24 02:30")),
+            PolicyDecision::OutsideWindow
+        );
+    }
+
+    #[test]
+    fn test_freeze_blocks_updates() {
+        let policy = policy(&[], &[("2026-12-15", "2027-01-05")]);
+        assert!(matches!(
+            policy.check(at("2027-01-05 12:00")),
+            PolicyDecision::Frozen(_)
+        ));
+        assert_eq!(
+            policy.check(at("2027-01-06 00:00")),
+            PolicyDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn test_freeze_rejects_reversed_range() {
+        let config = PolicyConfig {
+            windows: Vec::new(),
+            freeze: vec![FreezeConfig {
+                from: "2027-01-05".to_string(),
+                to: "2026-12-15".to_string(),
+                reason: None,
+            }],
+        };
+        let err = UpdatePolicy::from_config(&config).unwrap_err();
+        assert!(format!("{:#}", err).contains("starts after it ends"));
+    }
+
+    #[test]
+    fn test_next_allowed() {
+        let policy = policy(&["Mon-Fri 08:00-10:00"], &[("2026-12-15", "2027-01-05")]);
+        // 星期六 → 下周一 08:00
+        assert_eq!(
+            policy.next_allowed(at("2026-10-24 12:00")),
+            Some(at("2026-10-26 08:00"))
+        );
+        // 冻结期内 → 冻结结束后的第一个工作日窗口（2027-01-06 是星期三）
+        assert_eq!(
+            policy.next_allowed(at("2026-12-20 09:00")),
+            Some(at("2027-01-06 08:00"))
+        );
+        // 已允许时返回当前时间
+        assert_eq!(
+            policy.next_allowed(at("2026-10-19 09:00")),
+            Some(at("2026-10-19 09:00"))
+        );
+    }
+
+    #[test]
+    fn test_unrestricted_policy() {
+        let policy = UpdatePolicy::default();
+        assert!(!policy.is_restricted());
+        assert_eq!(
+            policy.check(at("2026-10-19 03:00")),
+            PolicyDecision::Allowed
+        );
+    }
+}
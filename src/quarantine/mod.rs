@@ -0,0 +1,298 @@
+// 新版本隔离期模块
+// 记录每个候选版本首次被观察到的时间，未满隔离期的升级将被暂缓
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::utils::get_cache_dir;
+
+/// 待升级的候选项
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    /// 软件包/工具/工具链名称
+    pub name: String,
+    /// 可升级到的版本
+    pub candidate_version: String,
+}
+
+/// 因隔离期而暂缓的升级
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeferredUpgrade {
+    /// 软件包/工具/工具链名称
+    pub name: String,
+    /// 被暂缓的候选版本
+    pub version: String,
+    /// 剩余等待秒数
+    pub remaining_secs: i64,
+}
+
+impl DeferredUpgrade {
+    /// 剩余等待时长
+    pub fn remaining(&self) -> Duration {
+        Duration::seconds(self.remaining_secs)
+    }
+}
+
+/// 候选版本首次出现记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SeenVersion {
+    version: String,
+    first_seen: DateTime<Utc>,
+}
+
+/// 新版本隔离期策略
+///
+/// 每个工具的观察记录保存在 `~/.cache/devtool/quarantine/<tool>.json`，
+/// 按工具分文件存储，避免并行执行时互相覆盖。
+#[derive(Debug, Clone)]
+pub struct Quarantine {
+    min_age: Option<Duration>,
+    store_dir: PathBuf,
+}
+
+impl Quarantine {
+    /// 创建隔离期策略，`min_age` 为 `None` 时只记录不暂缓
+    pub fn new(min_age: Option<Duration>) -> Self {
+        Self::with_store_dir(min_age, get_cache_dir().join("quarantine"))
+    }
+
+    /// 使用指定的记录目录创建隔离期策略
+    pub fn with_store_dir(min_age: Option<Duration>, store_dir: PathBuf) -> Self {
+        Self {
+            min_age: min_age.filter(|age| *age > Duration::zero()),
+            store_dir,
+        }
+    }
+
+    /// 是否启用了隔离期
+    pub fn is_enabled(&self) -> bool {
+        self.min_age.is_some()
+    }
+
+    /// 记录候选版本并划分可立即升级与需暂缓的项目
+    ///
+    /// 记录文件读写失败不会中断升级：无法读取时视为首次出现，
+    /// 因此启用隔离期时会偏向暂缓。
+    pub fn partition(
+        &self,
+        tool: &str,
+        candidates: Vec<Candidate>,
+    ) -> (Vec<Candidate>, Vec<DeferredUpgrade>) {
+        self.partition_at(tool, candidates, Utc::now(), false)
+    }
+
+    /// 与 [`partition`](Self::partition) 相同，但候选项只是全部待升级项目的一部分
+    /// （交互式选择或升级计划），其他项目的记录保持不变
+    pub fn partition_selected(
+        &self,
+        tool: &str,
+        candidates: Vec<Candidate>,
+    ) -> (Vec<Candidate>, Vec<DeferredUpgrade>) {
+        self.partition_at(tool, candidates, Utc::now(), true)
+    }
+
+    fn partition_at(
+        &self,
+        tool: &str,
+        candidates: Vec<Candidate>,
+        now: DateTime<Utc>,
+        keep_others: bool,
+    ) -> (Vec<Candidate>, Vec<DeferredUpgrade>) {
+        let previous = self.load_store(tool);
+        // 完整的候选列表替换记录，不再待升级的项目随之移除
+        let mut store = if keep_others {
+            previous.clone()
+        } else {
+            HashMap::new()
+        };
+
+        let mut allowed = Vec::new();
+        let mut deferred = Vec::new();
+
+        for candidate in candidates {
+            // 版本变化时重新计时
+            let first_seen = previous
+                .get(&candidate.name)
+                .filter(|seen| seen.version == candidate.candidate_version)
+                .map(|seen| seen.first_seen)
+                .unwrap_or(now);
+
+            store.insert(
+                candidate.name.clone(),
+                SeenVersion {
+                    version: candidate.candidate_version.clone(),
+                    first_seen,
+                },
+            );
+
+            match self.min_age {
+                Some(min_age) if now - first_seen < min_age => {
+                    deferred.push(DeferredUpgrade {
+                        name: candidate.name.clone(),
+                        version: candidate.candidate_version.clone(),
+                        remaining_secs: (min_age - (now - first_seen)).num_seconds(),
+                    });
+                }
+                _ => allowed.push(candidate),
+            }
+        }
+
+        self.save_store(tool, &store);
+
+        (allowed, deferred)
+    }
+
+    fn store_path(&self, tool: &str) -> PathBuf {
+        self.store_dir.join(format!("{}.json", tool))
+    }
+
+    fn load_store(&self, tool: &str) -> HashMap<String, SeenVersion> {
+        std::fs::read_to_string(self.store_path(tool))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_store(&self, tool: &str, store: &HashMap<String, SeenVersion>) {
+        if std::fs::create_dir_all(&self.store_dir).is_err() {
+            return;
+        }
+        if let Ok(content) = serde_json::to_string_pretty(store) {
+            let _ = std::fs::write(self.store_path(tool), content);
+        }
+    }
+}
+
+/// 保存暂缓升级列表到临时目录，供汇总输出读取
+pub fn save_deferred(tmpdir: &Path, tool_name: &str, deferred: &[DeferredUpgrade]) {
+    let file_path = tmpdir.join(format!("{}_deferred.json", tool_name));
+    if let Ok(mut file) = File::create(file_path) {
+        if let Ok(content) = serde_json::to_string_pretty(deferred) {
+            let _ = writeln!(file, "{}", content);
+        }
+    }
+}
+
+/// 从临时目录读取暂缓升级列表
+pub fn load_deferred(tmpdir: &Path, tool_name: &str) -> Vec<DeferredUpgrade> {
+    std::fs::read_to_string(tmpdir.join(format!("{}_deferred.json", tool_name)))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 记录无法列出候选版本的工具：启用隔离期时这类工具不会被升级
+pub fn save_unchecked(tmpdir: &Path, tool_name: &str) {
+    let _ = File::create(tmpdir.join(format!("{}_unchecked", tool_name)));
+}
+
+/// 工具是否因无法列出候选版本而跳过了升级
+pub fn is_unchecked(tmpdir: &Path, tool_name: &str) -> bool {
+    tmpdir.join(format!("{}_unchecked", tool_name)).exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn candidate(name: &str, version: &str) -> Candidate {
+        Candidate {
+            name: name.to_string(),
+            candidate_version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_disabled_allows_everything() {
+        let tmp = tempdir().unwrap();
+        let quarantine = Quarantine::with_store_dir(None, tmp.path().to_path_buf());
+        let (allowed, deferred) = quarantine.partition("homebrew", vec![candidate("wget", "1.1")]);
+        assert_eq!(allowed.len(), 1);
+        assert!(deferred.is_empty());
+        // 未启用时仍然记录首次出现时间
+        assert!(tmp.path().join("homebrew.json").exists());
+    }
+
+    #[test]
+    fn test_new_version_is_deferred_until_min_age() {
+        let tmp = tempdir().unwrap();
+        let quarantine =
+            Quarantine::with_store_dir(Some(Duration::days(3)), tmp.path().to_path_buf());
+        let start = Utc::now();
+
+        let (allowed, deferred) =
+            quarantine.partition_at("mise", vec![candidate("node", "20.1.0")], start, false);
+        assert!(allowed.is_empty());
+        assert_eq!(deferred[0].remaining(), Duration::days(3));
+
+        let later = start + Duration::days(2);
+        let (allowed, deferred) =
+            quarantine.partition_at("mise", vec![candidate("node", "20.1.0")], later, false);
+        assert!(allowed.is_empty());
+        assert_eq!(deferred[0].remaining(), Duration::days(1));
+
+        let after = start + Duration::days(3);
+        let (allowed, deferred) =
+            quarantine.partition_at("mise", vec![candidate("node", "20.1.0")], after, false);
+        assert_eq!(allowed.len(), 1);
+        assert!(deferred.is_empty());
+    }
+
+    #[test]
+    fn test_changed_version_restarts_clock() {
+        let tmp = tempdir().unwrap();
+        let quarantine =
+            Quarantine::with_store_dir(Some(Duration::days(1)), tmp.path().to_path_buf());
+        let start = Utc::now();
+
+        quarantine.partition_at("rustup", vec![candidate("stable", "1.80.0")], start, false);
+        let (allowed, deferred) = quarantine.partition_at(
+            "rustup",
+            vec![candidate("stable", "1.80.1")],
+            start + Duration::days(2),
+            false,
+        );
+        assert!(allowed.is_empty());
+        assert_eq!(deferred[0].version, "1.80.1");
+    }
+
+    #[test]
+    fn test_partition_selected_keeps_other_records() {
+        let tmp = tempdir().unwrap();
+        let quarantine =
+            Quarantine::with_store_dir(Some(Duration::days(1)), tmp.path().to_path_buf());
+        let start = Utc::now();
+
+        quarantine.partition_at(
+            "homebrew",
+            vec![candidate("wget", "1.25.0"), candidate("jq", "1.8.0")],
+            start,
+            false,
+        );
+        // 只选中 jq 时 wget 的首次出现时间不受影响
+        let later = start + Duration::days(2);
+        quarantine.partition_at("homebrew", vec![candidate("jq", "1.8.0")], later, true);
+        let (allowed, deferred) =
+            quarantine.partition_at("homebrew", vec![candidate("wget", "1.25.0")], later, true);
+        assert_eq!(allowed.len(), 1);
+        assert!(deferred.is_empty());
+    }
+
+    #[test]
+    fn test_deferred_roundtrip() {
+        let tmp = tempdir().unwrap();
+        let deferred = vec![DeferredUpgrade {
+            name: "wget".to_string(),
+            version: "1.25.0".to_string(),
+            remaining_secs: 3600,
+        }];
+        save_deferred(tmp.path(), "brew", &deferred);
+        assert_eq!(load_deferred(tmp.path(), "brew"), deferred);
+        assert!(load_deferred(tmp.path(), "mise").is_empty());
+    }
+}
@@ -0,0 +1,556 @@
+// 本地 HTTP API（devtool serve）
+// 只监听本机回环地址或 Unix 套接字并使用令牌认证；提供运行状态查询、
+// 触发更新，以及通过 Server-Sent Events 推送更新进度
+
+use anyhow::{bail, Context, Result};
+use axum::extract::{Path as UrlPath, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt;
+
+use crate::history::ToolResult;
+use crate::live::{self, StatusReport, StepState};
+use crate::parallel::Tool;
+use crate::ui::progress::{ProgressDisplay, SimpleProgressState};
+
+/// 保留的运行记录数量
+const MAX_RUNS: usize = 50;
+
+/// 监听位置
+#[derive(Debug, Clone, PartialEq)]
+pub enum Listen {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+impl Listen {
+    /// 解析监听参数：指定 `socket` 时使用 Unix 套接字，否则 TCP 地址必须是本机回环地址
+    pub fn parse(listen: &str, socket: Option<&Path>) -> Result<Self> {
+        if let Some(path) = socket {
+            return Ok(Listen::Unix(path.to_path_buf()));
+        }
+        let addr: SocketAddr = listen
+            .parse()
+            .with_context(|| format!("invalid listen address {:?}", listen))?;
+        if !addr.ip().is_loopback() {
+            bail!(
+                "refusing to listen on {}: only loopback addresses are allowed",
+                addr
+            );
+        }
+        Ok(Listen::Tcp(addr))
+    }
+}
+
+/// 生成随机访问令牌
+pub fn generate_token() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    std::fs::File::open("/dev/urandom")
+        .and_then(|mut f| f.read_exact(&mut bytes))
+        .context("read /dev/urandom")?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// `POST /runs` 的请求体
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunRequest {
+    /// 要更新的工具，默认为所有已安装的工具
+    pub tools: Option<Vec<Tool>>,
+    pub dry_run: bool,
+    /// 并行任务数量，默认 3
+    pub jobs: Option<usize>,
+    /// 新版本隔离期，覆盖配置文件
+    pub min_age: Option<String>,
+    /// 忽略维护窗口和冻结期
+    pub force: bool,
+}
+
+/// 运行状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunState {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// 一次通过 API 触发的更新
+#[derive(Debug, Clone, Serialize)]
+pub struct Run {
+    pub id: u64,
+    pub state: RunState,
+    pub request: RunRequest,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub results: Vec<ToolResult>,
+    pub error: Option<String>,
+    /// 已发生的进度事件，供后来的订阅者补发
+    #[serde(skip)]
+    events: Vec<RunEvent>,
+}
+
+/// 推送给订阅者的进度事件
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum RunEvent {
+    /// 工具状态变化
+    Tool { tool: Tool, state: StepState },
+    /// 运行结束
+    Finished { state: RunState },
+}
+
+impl RunEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            RunEvent::Tool { .. } => "tool",
+            RunEvent::Finished { .. } => "finished",
+        }
+    }
+}
+
+/// 执行一次更新：接收请求和进度显示，返回各工具的结果；运行日志由执行器写入运行目录
+pub type RunFuture = Pin<Box<dyn Future<Output = Result<Vec<ToolResult>>> + Send>>;
+pub type Executor = Arc<dyn Fn(RunRequest, Box<dyn ProgressDisplay>) -> RunFuture + Send + Sync>;
+
+#[derive(Default)]
+struct RunStore {
+    next_id: u64,
+    runs: Vec<Run>,
+    /// 运行中的更新的事件通道，结束后移除
+    channels: HashMap<u64, broadcast::Sender<RunEvent>>,
+}
+
+impl RunStore {
+    fn get_mut(&mut self, id: u64) -> Option<&mut Run> {
+        self.runs.iter_mut().find(|run| run.id == id)
+    }
+
+    /// 记录事件并推送给订阅者
+    fn publish(&mut self, id: u64, event: RunEvent) {
+        if let Some(run) = self.get_mut(id) {
+            run.events.push(event.clone());
+        }
+        if let Some(sender) = self.channels.get(&id) {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn finish(&mut self, id: u64, outcome: Result<Vec<ToolResult>>) {
+        let Some(run) = self.get_mut(id) else {
+            return;
+        };
+        match outcome {
+            Ok(results) => {
+                run.state = if results.iter().all(|r| r.success) {
+                    RunState::Succeeded
+                } else {
+                    RunState::Failed
+                };
+                run.results = results;
+            }
+            Err(e) => {
+                run.state = RunState::Failed;
+                run.error = Some(format!("{:#}", e));
+            }
+        }
+        run.finished_at = Some(Utc::now());
+        let state = run.state;
+        self.publish(id, RunEvent::Finished { state });
+        // 关闭通道，订阅者的事件流随之结束
+        self.channels.remove(&id);
+    }
+}
+
+/// 将进度显示的状态变化转换为运行事件
+struct EventProgress {
+    store: Arc<Mutex<RunStore>>,
+    id: u64,
+}
+
+impl EventProgress {
+    fn publish(&self, tool: &Tool, state: &SimpleProgressState) {
+        if let Ok(mut store) = self.store.lock() {
+            store.publish(
+                self.id,
+                RunEvent::Tool {
+                    tool: tool.clone(),
+                    state: state.into(),
+                },
+            );
+        }
+    }
+}
+
+impl ProgressDisplay for EventProgress {
+    fn create_progress_bars(&mut self, tools: &[Tool]) {
+        for tool in tools {
+            self.publish(tool, &SimpleProgressState::Preparing);
+        }
+    }
+
+    fn update_state(&mut self, tool: &Tool, new_state: SimpleProgressState) {
+        self.publish(tool, &new_state);
+    }
+
+    fn finalize_all(&mut self) {}
+}
+
+#[derive(Clone)]
+pub struct AppState {
+    token: Arc<str>,
+    store: Arc<Mutex<RunStore>>,
+    executor: Executor,
+}
+
+impl AppState {
+    pub fn new(token: &str, executor: Executor) -> Self {
+        Self {
+            token: Arc::from(token),
+            store: Arc::new(Mutex::new(RunStore::default())),
+            executor,
+        }
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+/// 比较令牌，耗时与第一个不同字节的位置无关，避免通过响应时间逐字节猜测令牌
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// 校验 `Authorization: Bearer <token>`
+async fn require_token(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), state.token.as_bytes()));
+    if !authorized {
+        return error_response(StatusCode::UNAUTHORIZED, "missing or invalid token");
+    }
+    next.run(request).await
+}
+
+/// 当前正在运行的更新（包括命令行启动的更新）
+async fn get_status() -> Response {
+    match tokio::task::spawn_blocking(|| live::query(&live::socket_path())).await {
+        Ok(Ok(run)) => Json(StatusReport::new(run)).into_response(),
+        Ok(Err(e)) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &format!("{:#}", e)),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    }
+}
+
+/// 最近的运行记录，最新的在前
+async fn list_runs(State(state): State<AppState>) -> Response {
+    let store = state.store.lock().unwrap_or_else(|e| e.into_inner());
+    let runs: Vec<&Run> = store.runs.iter().rev().collect();
+    Json(runs).into_response()
+}
+
+async fn get_run(State(state): State<AppState>, UrlPath(id): UrlPath<u64>) -> Response {
+    let store = state.store.lock().unwrap_or_else(|e| e.into_inner());
+    match store.runs.iter().find(|run| run.id == id) {
+        Some(run) => Json(run).into_response(),
+        None => error_response(StatusCode::NOT_FOUND, "run not found"),
+    }
+}
+
+/// 触发一次更新，立即返回 202 和运行记录
+async fn create_run(State(state): State<AppState>, Json(request): Json<RunRequest>) -> Response {
+    if request.jobs == Some(0) {
+        return error_response(StatusCode::BAD_REQUEST, "jobs must be at least 1");
+    }
+    let run = {
+        let mut store = state.store.lock().unwrap_or_else(|e| e.into_inner());
+        if store.runs.iter().any(|run| run.state == RunState::Running) {
+            return error_response(StatusCode::CONFLICT, "an update is already running");
+        }
+        store.next_id += 1;
+        let run = Run {
+            id: store.next_id,
+            state: RunState::Running,
+            request: request.clone(),
+            started_at: Utc::now(),
+            finished_at: None,
+            results: Vec::new(),
+            error: None,
+            events: Vec::new(),
+        };
+        store.runs.push(run.clone());
+        if store.runs.len() > MAX_RUNS {
+            store.runs.remove(0);
+        }
+        let (sender, _) = broadcast::channel(64);
+        store.channels.insert(run.id, sender);
+        run
+    };
+
+    let id = run.id;
+    let store = state.store.clone();
+    let executor = state.executor.clone();
+    tokio::spawn(async move {
+        let display = EventProgress {
+            store: store.clone(),
+            id,
+        };
+        let outcome = executor(request, Box::new(display)).await;
+        store
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .finish(id, outcome);
+    });
+
+    (
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, format!("/runs/{}", id))],
+        Json(run),
+    )
+        .into_response()
+}
+
+/// 以 Server-Sent Events 推送运行进度：先补发已发生的事件，运行结束后关闭
+async fn run_events(State(state): State<AppState>, UrlPath(id): UrlPath<u64>) -> Response {
+    let (history, receiver) = {
+        let store = state.store.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(run) = store.runs.iter().find(|run| run.id == id) else {
+            return error_response(StatusCode::NOT_FOUND, "run not found");
+        };
+        let receiver = match store.channels.get(&id) {
+            Some(sender) => sender.subscribe(),
+            // 已结束的运行：使用已关闭的通道，只补发历史事件
+            None => broadcast::channel(1).1,
+        };
+        (run.events.clone(), receiver)
+    };
+
+    let events = tokio_stream::iter(history)
+        .chain(BroadcastStream::new(receiver).filter_map(|event| event.ok()))
+        .map(|event| Event::default().event(event.name()).json_data(&event));
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/status", get(get_status))
+        .route("/runs", get(list_runs).post(create_run))
+        .route("/runs/{id}", get(get_run))
+        .route("/runs/{id}/events", get(run_events))
+        .layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state)
+}
+
+/// 启动服务，直到收到 Ctrl-C
+pub async fn serve(listen: &Listen, state: AppState) -> Result<()> {
+    let app = router(state);
+    let shutdown = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+    match listen {
+        Listen::Tcp(addr) => {
+            let listener = tokio::net::TcpListener::bind(addr)
+                .await
+                .with_context(|| format!("bind {}", addr))?;
+            axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown)
+                .await?;
+        }
+        Listen::Unix(path) => {
+            use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+            // 只删除上次异常退出残留的套接字，不覆盖其他文件或正在使用的套接字
+            if let Ok(metadata) = std::fs::symlink_metadata(path) {
+                if !metadata.file_type().is_socket() {
+                    bail!("refusing to replace {:?}: not a socket", path);
+                }
+                if std::os::unix::net::UnixStream::connect(path).is_ok() {
+                    bail!("{:?} is already in use", path);
+                }
+                std::fs::remove_file(path)?;
+            }
+            let listener =
+                tokio::net::UnixListener::bind(path).with_context(|| format!("bind {:?}", path))?;
+            // 只允许当前用户连接
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+            let result = axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown)
+                .await;
+            let _ = std::fs::remove_file(path);
+            result?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// 立即成功的执行器，模拟两个工具的进度
+    fn fake_executor() -> Executor {
+        Arc::new(
+            |request: RunRequest, mut display: Box<dyn ProgressDisplay>| {
+                Box::pin(async move {
+                    let tools = request.tools.unwrap_or_default();
+                    display.create_progress_bars(&tools);
+                    for tool in &tools {
+                        display.update_state(tool, SimpleProgressState::Executing);
+                        display.update_state(tool, SimpleProgressState::Completed);
+                    }
+                    display.finalize_all();
+                    Ok(tools
+                        .into_iter()
+                        .map(|tool| ToolResult {
+                            tool,
+                            success: true,
+                            output: "ok".to_string(),
+                            upgrades: vec!["jq: 1.7 → 1.8".to_string()],
+                        })
+                        .collect())
+                })
+            },
+        )
+    }
+
+    async fn start() -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = router(AppState::new("secret", fake_executor()));
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        addr
+    }
+
+    /// 发送一个 HTTP/1.1 请求，返回状态码和响应体
+    async fn request(
+        addr: SocketAddr,
+        method: &str,
+        path: &str,
+        token: &str,
+        body: &str,
+    ) -> (u16, String) {
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {token}\r\n\
+             Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        let status = response[9..12].parse().unwrap();
+        let body = response
+            .split_once("\r\n\r\n")
+            .map(|(_, b)| b.to_string())
+            .unwrap_or_default();
+        (status, body)
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+        assert!(!constant_time_eq(b"", b"secret"));
+    }
+
+    #[tokio::test]
+    async fn test_unix_socket_refuses_other_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("devtool.sock");
+        std::fs::write(&path, "data").unwrap();
+        let state = AppState::new("secret", fake_executor());
+        let err = serve(&Listen::Unix(path.clone()), state).await.unwrap_err();
+        assert!(err.to_string().contains("not a socket"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "data");
+    }
+
+    #[test]
+    fn test_listen_parse() {
+        assert_eq!(
+            Listen::parse("127.0.0.1:7878", None).unwrap(),
+            Listen::Tcp("127.0.0.1:7878".parse().unwrap())
+        );
+        assert!(Listen::parse("[::1]:7878", None).is_ok());
+        assert!(Listen::parse("0.0.0.0:7878", None).is_err());
+        assert_eq!(
+            Listen::parse("0.0.0.0:7878", Some(Path::new("/tmp/devtool.sock"))).unwrap(),
+            Listen::Unix(PathBuf::from("/tmp/devtool.sock"))
+        );
+        assert_eq!(generate_token().unwrap().len(), 32);
+    }
+
+    #[tokio::test]
+    async fn test_requires_token() {
+        let addr = start().await;
+        let (status, _) = request(addr, "GET", "/runs", "wrong", "").await;
+        assert_eq!(status, 401);
+        let (status, body) = request(addr, "GET", "/runs", "secret", "").await;
+        assert_eq!(status, 200);
+        assert_eq!(body, "[]");
+        let (status, _) = request(addr, "GET", "/runs/7", "secret", "").await;
+        assert_eq!(status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_create_run_rejects_zero_jobs() {
+        let addr = start().await;
+        let (status, body) = request(addr, "POST", "/runs", "secret", r#"{"jobs": 0}"#).await;
+        assert_eq!(status, 400);
+        assert!(body.contains("jobs"));
+        let (_, body) = request(addr, "GET", "/runs", "secret", "").await;
+        assert_eq!(body, "[]");
+    }
+
+    #[tokio::test]
+    async fn test_create_run_and_stream_events() {
+        let addr = start().await;
+        let (status, body) = request(
+            addr,
+            "POST",
+            "/runs",
+            "secret",
+            r#"{"tools": ["rustup", "mise"], "dry_run": true}"#,
+        )
+        .await;
+        assert_eq!(status, 202);
+        let run: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(run["id"], 1);
+
+        // 事件流在运行结束后关闭，并补发已发生的事件
+        let (status, events) = request(addr, "GET", "/runs/1/events", "secret", "").await;
+        assert_eq!(status, 200);
+        assert!(events.contains(r#"{"event":"tool","tool":"rustup","state":"running"}"#));
+        assert!(events.contains(r#"{"event":"finished","state":"succeeded"}"#));
+
+        let (_, body) = request(addr, "GET", "/runs/1", "secret", "").await;
+        let run: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(run["state"], "succeeded");
+        assert_eq!(run["request"]["dry_run"], true);
+        assert_eq!(run["results"][1]["tool"], "mise");
+        assert_eq!(run["results"][1]["upgrades"][0], "jq: 1.7 → 1.8");
+    }
+}
@@ -0,0 +1,263 @@
+// 快照对比
+// 按工具列出两个快照之间缺失、多余和版本不同的项目
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::Snapshot;
+use crate::parallel::Tool;
+use crate::version::{Version, VersionDistance};
+
+/// 差异类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffStatus {
+    /// 基准快照中有，对比方没有
+    Missing,
+    /// 对比方有，基准快照中没有
+    Extra,
+    /// 两边都有但版本不同
+    Changed,
+}
+
+/// 单项差异
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffEntry {
+    pub tool: Tool,
+    /// 项目类别，例如 formula、cask、tap、toolchain、component、target
+    pub category: &'static str,
+    pub name: String,
+    pub status: DiffStatus,
+    /// 基准快照中的版本
+    pub base: Option<String>,
+    /// 对比方的版本
+    pub other: Option<String>,
+    /// 版本差距（仅 changed）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<VersionDistance>,
+}
+
+/// 快照中的可比较项目：(工具, 类别, 名称) -> 版本（仅表示存在的项目为 `None`）
+type Items = BTreeMap<(u8, &'static str, String), (Tool, Option<String>)>;
+
+/// 工具排序键，使输出顺序与更新顺序一致
+pub fn tool_order(tool: &Tool) -> u8 {
+    match tool {
+        Tool::Homebrew => 0,
+        Tool::Rustup => 1,
+        Tool::Mise => 2,
+    }
+}
+
+fn insert(
+    items: &mut Items,
+    tool: Tool,
+    category: &'static str,
+    name: &str,
+    version: Option<&str>,
+) {
+    items.insert(
+        (tool_order(&tool), category, name.to_string()),
+        (tool, version.map(|v| v.to_string())),
+    );
+}
+
+/// 将快照展开为可比较的项目
+fn flatten(snapshot: &Snapshot) -> Items {
+    let mut items = Items::new();
+
+    if let Some(homebrew) = &snapshot.homebrew {
+        for tap in &homebrew.taps {
+            insert(&mut items, Tool::Homebrew, "tap", tap, None);
+        }
+        for formula in &homebrew.formulae {
+            insert(
+                &mut items,
+                Tool::Homebrew,
+                "formula",
+                &formula.name,
+                Some(&formula.version),
+            );
+        }
+        for cask in &homebrew.casks {
+            insert(
+                &mut items,
+                Tool::Homebrew,
+                "cask",
+                &cask.name,
+                Some(&cask.version),
+            );
+        }
+    }
+
+    if let Some(rustup) = &snapshot.rustup {
+        if let Some(default) = &rustup.default_toolchain {
+            insert(
+                &mut items,
+                Tool::Rustup,
+                "default",
+                "toolchain",
+                Some(default),
+            );
+        }
+        for toolchain in &rustup.toolchains {
+            insert(
+                &mut items,
+                Tool::Rustup,
+                "toolchain",
+                &toolchain.name,
+                toolchain.version.as_deref(),
+            );
+            for component in &toolchain.components {
+                let name = format!("{}/{}", toolchain.name, component);
+                insert(&mut items, Tool::Rustup, "component", &name, None);
+            }
+            for target in &toolchain.targets {
+                let name = format!("{}/{}", toolchain.name, target);
+                insert(&mut items, Tool::Rustup, "target", &name, None);
+            }
+        }
+    }
+
+    if let Some(mise) = &snapshot.mise {
+        for (name, version) in &mise.tools {
+            insert(&mut items, Tool::Mise, "tool", name, Some(version));
+        }
+    }
+
+    items
+}
+
+/// 对比两个快照
+///
+/// `base` 为参照：其中有而 `other` 没有的项目为 missing，反之为 extra
+pub fn diff(base: &Snapshot, other: &Snapshot) -> Vec<DiffEntry> {
+    let base_items = flatten(base);
+    let mut other_items = flatten(other);
+    let mut entries = Vec::new();
+
+    for (key, (tool, base_version)) in base_items {
+        let (_, category, name) = key.clone();
+        match other_items.remove(&key) {
+            None => entries.push(DiffEntry {
+                tool,
+                category,
+                name,
+                status: DiffStatus::Missing,
+                base: base_version,
+                other: None,
+                distance: None,
+            }),
+            Some((_, other_version)) if other_version != base_version => {
+                let distance = match (&base_version, &other_version) {
+                    (Some(a), Some(b)) => Version::parse(a).distance(&Version::parse(b)),
+                    _ => None,
+                };
+                // 版本字符串不同但数值相等（如 "1.2" 与 "1.2.0"）时不视为差异
+                if distance.is_none() && base_version.is_some() && other_version.is_some() {
+                    continue;
+                }
+                entries.push(DiffEntry {
+                    tool,
+                    category,
+                    name,
+                    status: DiffStatus::Changed,
+                    base: base_version,
+                    other: other_version,
+                    distance,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+
+    for ((_, category, name), (tool, other_version)) in other_items {
+        entries.push(DiffEntry {
+            tool,
+            category,
+            name,
+            status: DiffStatus::Extra,
+            base: None,
+            other: other_version,
+            distance: None,
+        });
+    }
+
+    entries.sort_by(|a, b| {
+        (tool_order(&a.tool), a.category, &a.name).cmp(&(tool_order(&b.tool), b.category, &b.name))
+    });
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{HomebrewSnapshot, MiseSnapshot, PackageVersion};
+    use chrono::Utc;
+
+    fn snapshot(formulae: &[(&str, &str)], mise: &[(&str, &str)]) -> Snapshot {
+        Snapshot {
+            devtool_version: "0.8.23".to_string(),
+            created_at: Utc::now(),
+            homebrew: Some(HomebrewSnapshot {
+                taps: vec![],
+                formulae: formulae
+                    .iter()
+                    .map(|(name, version)| PackageVersion {
+                        name: name.to_string(),
+                        version: version.to_string(),
+                    })
+                    .collect(),
+                casks: vec![],
+            }),
+            rustup: None,
+            mise: Some(MiseSnapshot {
+                tools: mise
+                    .iter()
+                    .map(|(name, version)| (name.to_string(), version.to_string()))
+                    .collect(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_identical_snapshots() {
+        let a = snapshot(&[("git", "2.45.0")], &[("node", "20.11.0")]);
+        assert!(diff(&a, &a.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_missing_extra_and_changed() {
+        let a = snapshot(
+            &[("git", "2.45.0"), ("jq", "1.7.1")],
+            &[("node", "20.11.0")],
+        );
+        let b = snapshot(
+            &[("git", "2.46.0"), ("wget", "1.24.5")],
+            &[("node", "22.1.0")],
+        );
+        let entries = diff(&a, &b);
+
+        let find = |name: &str| entries.iter().find(|e| e.name == name).unwrap();
+        assert_eq!(find("jq").status, DiffStatus::Missing);
+        assert_eq!(find("wget").status, DiffStatus::Extra);
+        assert_eq!(find("git").status, DiffStatus::Changed);
+        assert_eq!(find("git").distance, Some(VersionDistance::Minor));
+        assert_eq!(find("node").distance, Some(VersionDistance::Major));
+        assert_eq!(entries.len(), 4);
+        // Homebrew 排在 Mise 之前
+        assert_eq!(entries.last().unwrap().tool, Tool::Mise);
+    }
+
+    #[test]
+    fn test_missing_tool_section() {
+        let a = snapshot(&[("git", "2.45.0")], &[]);
+        let b = Snapshot {
+            homebrew: None,
+            ..a.clone()
+        };
+        let entries = diff(&a, &b);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, DiffStatus::Missing);
+    }
+}
@@ -0,0 +1,264 @@
+// 环境快照模块
+// 采集 Homebrew、Rustup、Mise 的完整安装状态，并导出为 JSON 或各工具的原生格式
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use which::which;
+
+use crate::commands::rustup::RustupInventory;
+use crate::commands::{homebrew, mise, rustup};
+use crate::runner::Runner;
+
+pub mod diff;
+
+/// 软件包及版本
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageVersion {
+    pub name: String,
+    pub version: String,
+}
+
+/// Homebrew 安装状态
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HomebrewSnapshot {
+    pub taps: Vec<String>,
+    pub formulae: Vec<PackageVersion>,
+    pub casks: Vec<PackageVersion>,
+}
+
+/// Mise 安装状态
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MiseSnapshot {
+    /// 工具名称 -> 当前版本
+    pub tools: BTreeMap<String, String>,
+}
+
+/// 环境快照
+///
+/// 未安装的工具对应字段为 `None`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// 生成快照的 devtool 版本
+    pub devtool_version: String,
+    /// 生成时间
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub homebrew: Option<HomebrewSnapshot>,
+    #[serde(default)]
+    pub rustup: Option<RustupInventory>,
+    #[serde(default)]
+    pub mise: Option<MiseSnapshot>,
+}
+
+impl Snapshot {
+    /// 采集当前机器的安装状态
+    pub fn capture(runner: &dyn Runner, tmpdir: &Path) -> Result<Self> {
+        let homebrew = if which("brew").is_ok() {
+            let mut snapshot = HomebrewSnapshot {
+                taps: homebrew::installed_taps(runner, tmpdir)?,
+                ..Default::default()
+            };
+            for package in homebrew::installed_packages(runner, tmpdir)? {
+                let entry = PackageVersion {
+                    name: package.name,
+                    version: package.version,
+                };
+                if package.cask {
+                    snapshot.casks.push(entry);
+                } else {
+                    snapshot.formulae.push(entry);
+                }
+            }
+            Some(snapshot)
+        } else {
+            None
+        };
+
+        let rustup = if which("rustup").is_ok() {
+            Some(rustup::inventory(runner, tmpdir)?)
+        } else {
+            None
+        };
+
+        let mise = if which("mise").is_ok() {
+            Some(MiseSnapshot {
+                tools: mise::installed_tools(runner, tmpdir)?.into_iter().collect(),
+            })
+        } else {
+            None
+        };
+
+        Ok(Self {
+            devtool_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now(),
+            homebrew,
+            rustup,
+            mise,
+        })
+    }
+
+    /// 从 JSON 文件加载快照
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            std::fs::read_to_string(path).with_context(|| format!("read snapshot {:?}", path))?;
+        serde_json::from_str(&content).with_context(|| format!("parse snapshot {:?}", path))
+    }
+
+    /// 导出为 JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 导出为 `brew bundle` 使用的 Brewfile
+    ///
+    /// Brewfile 不支持固定版本，版本号以注释形式保留
+    pub fn to_brewfile(&self) -> Option<String> {
+        let homebrew = self.homebrew.as_ref()?;
+        let mut out = String::new();
+        for tap in &homebrew.taps {
+            let _ = writeln!(out, "tap \"{}\"", tap);
+        }
+        for formula in &homebrew.formulae {
+            let _ = writeln!(out, "brew \"{}\" # {}", formula.name, formula.version);
+        }
+        for cask in &homebrew.casks {
+            let _ = writeln!(out, "cask \"{}\" # {}", cask.name, cask.version);
+        }
+        Some(out)
+    }
+
+    /// 为默认工具链导出 rust-toolchain.toml
+    ///
+    /// 默认工具链为 stable 时固定到当前 rustc 版本，以便复现
+    pub fn to_rust_toolchain(&self) -> Option<String> {
+        let rustup = self.rustup.as_ref()?;
+        let default = rustup.default_toolchain.as_ref()?;
+        let toolchain = rustup.toolchains.iter().find(|t| &t.name == default)?;
+
+        let channel = match (default.as_str(), &toolchain.version) {
+            ("stable", Some(version)) => version.clone(),
+            _ => default.clone(),
+        };
+
+        let mut out = String::from("[toolchain]\n");
+        let _ = writeln!(out, "channel = {}", toml_string(&channel));
+        if !toolchain.components.is_empty() {
+            let _ = writeln!(out, "components = {}", toml_array(&toolchain.components));
+        }
+        if !toolchain.targets.is_empty() {
+            let _ = writeln!(out, "targets = {}", toml_array(&toolchain.targets));
+        }
+        Some(out)
+    }
+
+    /// 导出为 mise 的 config.toml
+    pub fn to_mise_config(&self) -> Option<String> {
+        let mise = self.mise.as_ref()?;
+        let mut out = String::from("[tools]\n");
+        for (name, version) in &mise.tools {
+            let _ = writeln!(out, "{} = {}", toml_key(name), toml_string(version));
+        }
+        Some(out)
+    }
+}
+
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+fn toml_array(values: &[String]) -> String {
+    let items: Vec<String> = values.iter().map(|v| toml_string(v)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// mise 工具名可能包含 ":" 或 "/"（如 "npm:prettier"），需要加引号
+fn toml_key(key: &str) -> String {
+    if key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        key.to_string()
+    } else {
+        toml_string(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::rustup::InstalledToolchain;
+
+    fn sample() -> Snapshot {
+        Snapshot {
+            devtool_version: "0.8.23".to_string(),
+            created_at: Utc::now(),
+            homebrew: Some(HomebrewSnapshot {
+                taps: vec!["homebrew/core".to_string()],
+                formulae: vec![PackageVersion {
+                    name: "git".to_string(),
+                    version: "2.45.0".to_string(),
+                }],
+                casks: vec![PackageVersion {
+                    name: "firefox".to_string(),
+                    version: "125.0".to_string(),
+                }],
+            }),
+            rustup: Some(RustupInventory {
+                host: Some("aarch64-apple-darwin".to_string()),
+                default_toolchain: Some("stable".to_string()),
+                toolchains: vec![InstalledToolchain {
+                    name: "stable".to_string(),
+                    version: Some("1.80.0".to_string()),
+                    components: vec!["clippy".to_string(), "rustfmt".to_string()],
+                    targets: vec!["wasm32-unknown-unknown".to_string()],
+                }],
+            }),
+            mise: Some(MiseSnapshot {
+                tools: BTreeMap::from([
+                    ("node".to_string(), "20.11.0".to_string()),
+                    ("npm:prettier".to_string(), "3.2.5".to_string()),
+                ]),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let snapshot = sample();
+        let parsed: Snapshot = serde_json::from_str(&snapshot.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, snapshot);
+    }
+
+    #[test]
+    fn test_native_exports() {
+        let snapshot = sample();
+        assert_eq!(
+            snapshot.to_brewfile().unwrap(),
+            "tap \"homebrew/core\"\nbrew \"git\" # 2.45.0\ncask \"firefox\" # 125.0\n"
+        );
+        assert_eq!(
+            snapshot.to_rust_toolchain().unwrap(),
+            "[toolchain]\nchannel = \"1.80.0\"\ncomponents = [\"clippy\", \"rustfmt\"]\ntargets = [\"wasm32-unknown-unknown\"]\n"
+        );
+        let mise = snapshot.to_mise_config().unwrap();
+        assert!(toml::from_str::<toml::Value>(&mise).is_ok());
+        assert!(mise.contains("\"npm:prettier\" = \"3.2.5\""));
+    }
+
+    #[test]
+    fn test_exports_skip_missing_tools() {
+        let snapshot = Snapshot {
+            homebrew: None,
+            rustup: None,
+            mise: None,
+            ..sample()
+        };
+        assert!(snapshot.to_brewfile().is_none());
+        assert!(snapshot.to_rust_toolchain().is_none());
+        assert!(snapshot.to_mise_config().is_none());
+    }
+}
@@ -0,0 +1,172 @@
+// 无障碍输出（--accessible）
+// 面向屏幕阅读器：不显示进度条、横幅、图标和颜色，每次状态变化输出一个完整的句子，
+// 结束时按顺序逐句朗读结果，不使用表格
+
+use super::compact::SummaryRow;
+use super::progress::{ProgressDisplay, SimpleProgressState};
+use crate::parallel::Tool;
+use crate::t;
+use std::collections::HashMap;
+
+/// 是否启用无障碍输出：命令行参数、配置文件的 `accessible` 或 `DEVTOOL_ACCESSIBLE` 环境变量
+pub fn enabled(flag: bool, config: bool) -> bool {
+    let env = std::env::var("DEVTOOL_ACCESSIBLE")
+        .is_ok_and(|value| !value.is_empty() && value != "0" && value != "false");
+    flag || config || env
+}
+
+/// 把升级详情中的版本箭头换成文字，例如 "git: 2.50.0 → 2.51.0" 读作 "git: 2.50.0 to 2.51.0"
+pub fn speakable(detail: &str, lang: &str) -> String {
+    let arrow = t!(lang, "accessible-version-arrow");
+    detail.replace(" → ", &arrow).replace(" -> ", &arrow)
+}
+
+/// 逐句播报每个工具的开始和结束
+pub struct AccessibleProgress {
+    lang: String,
+    /// 工具结束时统计升级的软件包数量
+    count_upgrades: Box<dyn Fn(&Tool) -> usize + Send>,
+    states: HashMap<Tool, SimpleProgressState>,
+}
+
+impl AccessibleProgress {
+    pub fn new(lang: &str, count_upgrades: impl Fn(&Tool) -> usize + Send + 'static) -> Self {
+        Self {
+            lang: lang.to_string(),
+            count_upgrades: Box::new(count_upgrades),
+            states: HashMap::new(),
+        }
+    }
+
+    /// 状态变化对应的句子，准备阶段不播报
+    fn announcement(&self, tool: &Tool, state: &SimpleProgressState) -> Option<String> {
+        let name = tool.display_name();
+        match state {
+            SimpleProgressState::Preparing => None,
+            SimpleProgressState::Executing => {
+                Some(t!(&self.lang, "accessible-started", tool = name))
+            }
+            SimpleProgressState::Completed => Some(t!(
+                &self.lang,
+                "accessible-finished",
+                tool = name,
+                count = (self.count_upgrades)(tool)
+            )),
+            SimpleProgressState::Failed => Some(t!(&self.lang, "accessible-failed", tool = name)),
+        }
+    }
+}
+
+impl ProgressDisplay for AccessibleProgress {
+    fn create_progress_bars(&mut self, tools: &[Tool]) {
+        for tool in tools {
+            self.states
+                .insert(tool.clone(), SimpleProgressState::Preparing);
+        }
+    }
+
+    fn update_state(&mut self, tool: &Tool, new_state: SimpleProgressState) {
+        if let Some(sentence) = self.announcement(tool, &new_state) {
+            println!("{}", sentence);
+        }
+        self.states.insert(tool.clone(), new_state);
+    }
+
+    fn finalize_all(&mut self) {
+        for (tool, state) in &self.states {
+            if *state == SimpleProgressState::Executing {
+                println!(
+                    "{}",
+                    t!(
+                        &self.lang,
+                        "accessible-interrupted",
+                        tool = tool.display_name()
+                    )
+                );
+            }
+        }
+    }
+}
+
+/// 结果摘要：每个工具一句，随后每项升级一句
+pub fn summary_lines(rows: &[SummaryRow], lang: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for row in rows {
+        let name = row.tool.display_name();
+        if !row.success {
+            lines.push(t!(lang, "accessible-tool-failed", tool = name));
+            continue;
+        }
+        lines.push(t!(
+            lang,
+            "accessible-tool-result",
+            tool = name,
+            count = row.upgrades.len()
+        ));
+        lines.extend(row.upgrades.iter().map(|detail| {
+            t!(
+                lang,
+                "accessible-upgrade",
+                tool = name,
+                detail = speakable(detail, lang)
+            )
+        }));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_announcement() {
+        let progress = AccessibleProgress::new("en", |_| 4);
+        assert_eq!(
+            progress.announcement(&Tool::Homebrew, &SimpleProgressState::Executing),
+            Some("Homebrew upgrade started.".to_string())
+        );
+        assert_eq!(
+            progress.announcement(&Tool::Homebrew, &SimpleProgressState::Completed),
+            Some("Homebrew upgrade finished, 4 packages upgraded.".to_string())
+        );
+        assert_eq!(
+            progress.announcement(&Tool::Mise, &SimpleProgressState::Failed),
+            Some("Mise upgrade failed.".to_string())
+        );
+        assert_eq!(
+            progress.announcement(&Tool::Mise, &SimpleProgressState::Preparing),
+            None
+        );
+    }
+
+    #[test]
+    fn test_summary_lines() {
+        let rows = vec![
+            SummaryRow {
+                tool: Tool::Homebrew,
+                success: true,
+                upgrades: vec!["git: 2.50.0 → 2.51.0".to_string()],
+            },
+            SummaryRow {
+                tool: Tool::Rustup,
+                success: true,
+                upgrades: Vec::new(),
+            },
+            SummaryRow {
+                tool: Tool::Mise,
+                success: false,
+                upgrades: Vec::new(),
+            },
+        ];
+        assert_eq!(
+            summary_lines(&rows, "en"),
+            vec![
+                "Homebrew: 1 package upgraded.",
+                "Homebrew upgraded git: 2.50.0 to 2.51.0.",
+                "Rustup: already up to date.",
+                "Mise: failed.",
+            ]
+        );
+    }
+}
@@ -1,54 +1,77 @@
+use super::theme::{self, Role};
 use colored::*;
 use std::io::IsTerminal;
 
 /// 检查终端是否支持颜色输出
+///
+/// 与 colored 的规则一致：`CLICOLOR_FORCE` 非 0 时强制启用，其次 `NO_COLOR` 禁用，
+/// 再次 `CLICOLOR=0` 或 `TERM=dumb` 禁用，否则取决于标准输出是否为终端。
+/// 已弃用的 `DEVMODE_FORCE_COLOR`、`DEVMODE_NO_COLOR` 分别等同于 `CLICOLOR_FORCE`、`NO_COLOR`。
 pub fn supports_color() -> bool {
-    std::io::stdout().is_terminal() && std::env::var("NO_COLOR").is_err()
+    color_enabled(
+        |name| std::env::var(name).ok(),
+        std::io::stdout().is_terminal(),
+    )
 }
 
-/// 打印成功消息（绿色加粗）
-pub fn print_success(msg: &str) {
-    if supports_color() {
-        println!("{}", msg.green().bold());
-    } else {
-        println!("{}", msg);
+fn color_enabled(var: impl Fn(&str) -> Option<String>, is_terminal: bool) -> bool {
+    if var("CLICOLOR_FORCE").is_some_and(|value| !value.is_empty() && value != "0")
+        || var("DEVMODE_FORCE_COLOR").is_some()
+    {
+        return true;
+    }
+    if var("NO_COLOR").is_some_and(|value| !value.is_empty()) || var("DEVMODE_NO_COLOR").is_some() {
+        return false;
+    }
+    if var("CLICOLOR").as_deref() == Some("0") || var("TERM").as_deref() == Some("dumb") {
+        return false;
     }
+    is_terminal
 }
 
-/// 打印信息消息（蓝色）
-pub fn print_info(msg: &str) {
-    if supports_color() {
-        println!("{}", msg.blue());
-    } else {
-        println!("{}", msg);
+/// 按当前主题中角色的颜色着色；不支持颜色或主题未指定颜色时原样返回
+pub fn paint(role: Role, text: &str, bold: bool) -> String {
+    if !supports_color() {
+        return text.to_string();
+    }
+    let mut styled = match theme::current().color(role) {
+        Some(color) => text.color(color),
+        None => text.normal(),
+    };
+    if bold {
+        styled = styled.bold();
     }
+    styled.to_string()
 }
 
-/// 打印警告消息（黄色）
+/// 打印成功消息（默认绿色加粗）
+pub fn print_success(msg: &str) {
+    println!("{}", paint(Role::Success, msg, true));
+}
+
+/// 打印信息消息（默认蓝色）
+pub fn print_info(msg: &str) {
+    println!("{}", paint(Role::Info, msg, false));
+}
+
+/// 打印警告消息（默认黄色）
 pub fn print_warning(msg: &str) {
-    if supports_color() {
-        println!("{}", msg.yellow());
-    } else {
-        println!("{}", msg);
-    }
+    println!("{}", paint(Role::Warning, msg, false));
 }
 
-/// 打印错误消息（红色加粗）
+/// 打印错误消息（默认红色加粗）
 pub fn print_error(msg: &str) {
-    if supports_color() {
-        println!("{}", msg.red().bold());
-    } else {
-        println!("{}", msg);
-    }
+    println!("{}", paint(Role::Failure, msg, true));
 }
 
-/// 打印横幅消息（品红色加粗）
+/// 打印横幅消息（默认品红色加粗）
 pub fn print_banner(msg: &str) {
-    if supports_color() {
-        println!("{}", msg.magenta().bold());
-    } else {
-        println!("{}", msg);
-    }
+    println!("{}", paint(Role::Header, msg, true));
+}
+
+/// 打印工具标题（与横幅颜色相同）
+pub fn print_header(msg: &str) {
+    println!("{}", paint(Role::Header, msg, false));
 }
 
 #[cfg(test)]
@@ -61,6 +84,32 @@ mod tests {
         let _ = supports_color();
     }
 
+    #[test]
+    fn test_color_enabled() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert!(color_enabled(env(&[]), true));
+        assert!(!color_enabled(env(&[]), false));
+        assert!(!color_enabled(env(&[("NO_COLOR", "1")]), true));
+        assert!(color_enabled(env(&[("NO_COLOR", "")]), true));
+        assert!(color_enabled(env(&[("CLICOLOR_FORCE", "1")]), false));
+        assert!(color_enabled(
+            env(&[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")]),
+            false
+        ));
+        assert!(!color_enabled(env(&[("CLICOLOR_FORCE", "0")]), false));
+        // 已弃用的别名
+        assert!(!color_enabled(env(&[("DEVMODE_NO_COLOR", "")]), true));
+        assert!(color_enabled(env(&[("DEVMODE_FORCE_COLOR", "1")]), false));
+        assert!(!color_enabled(env(&[("CLICOLOR", "0")]), true));
+        assert!(!color_enabled(env(&[("TERM", "dumb")]), true));
+    }
+
     #[test]
     fn test_print_functions() {
         // 确保打印函数不会 panic
@@ -69,5 +118,6 @@ mod tests {
         print_warning("Warning test");
         print_error("Error test");
         print_banner("Banner test");
+        print_header("Header test");
     }
 }
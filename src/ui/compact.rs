@@ -0,0 +1,194 @@
+// 紧凑输出（--compact）
+// 面向 CI 日志和非交互环境：不显示进度条、横幅和表情符号，每个步骤开始和结束各输出一行带时间戳的记录，
+// 结束时输出简洁的结果表
+
+use super::progress::{ProgressDisplay, SimpleProgressState};
+use crate::parallel::Tool;
+use crate::t;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// 是否自动使用紧凑输出：标准输出不是终端，或设置了 `CI` 环境变量
+pub fn auto_enabled() -> bool {
+    use std::io::IsTerminal;
+    let ci = std::env::var("CI").is_ok_and(|value| !value.is_empty() && value != "false");
+    ci || !std::io::stdout().is_terminal()
+}
+
+/// 当前本地时间，作为每行的前缀
+pub fn timestamp() -> String {
+    chrono::Local::now().format("[%H:%M:%S]").to_string()
+}
+
+/// 输出一行带时间戳的记录
+pub fn log_line(message: &str) {
+    println!("{} {}", timestamp(), message);
+}
+
+/// 格式化耗时，例如 "45s"、"2m05s"
+pub fn format_secs(secs: u64) -> String {
+    if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// 逐行输出步骤开始和结束的进度显示
+pub struct CompactProgress {
+    lang: String,
+    started: HashMap<Tool, Instant>,
+}
+
+impl CompactProgress {
+    pub fn new(lang: &str) -> Self {
+        Self {
+            lang: lang.to_string(),
+            started: HashMap::new(),
+        }
+    }
+
+    fn elapsed(&self, tool: &Tool) -> String {
+        self.started
+            .get(tool)
+            .map(|start| format_secs(start.elapsed().as_secs()))
+            .unwrap_or_default()
+    }
+}
+
+impl ProgressDisplay for CompactProgress {
+    fn create_progress_bars(&mut self, _tools: &[Tool]) {}
+
+    fn update_state(&mut self, tool: &Tool, new_state: SimpleProgressState) {
+        let name = tool.display_name();
+        match new_state {
+            SimpleProgressState::Preparing => {}
+            SimpleProgressState::Executing => {
+                self.started.insert(tool.clone(), Instant::now());
+                log_line(&t!(&self.lang, "compact-started", tool = name));
+            }
+            SimpleProgressState::Completed | SimpleProgressState::Failed => {
+                let id = if new_state == SimpleProgressState::Completed {
+                    "compact-done"
+                } else {
+                    "compact-failed"
+                };
+                log_line(&t!(
+                    &self.lang,
+                    id,
+                    tool = name,
+                    elapsed = self.elapsed(tool)
+                ));
+            }
+        }
+    }
+
+    fn finalize_all(&mut self) {}
+}
+
+/// 结果表中的一行
+pub struct SummaryRow {
+    pub tool: Tool,
+    pub success: bool,
+    pub upgrades: Vec<String>,
+}
+
+/// 终端显示宽度（中文字符按两列计算）
+fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum()
+}
+
+/// 右侧补空格到指定显示宽度
+fn pad(text: &str, width: usize) -> String {
+    format!(
+        "{}{}",
+        text,
+        " ".repeat(width.saturating_sub(display_width(text)))
+    )
+}
+
+/// 结果表：每个工具一行，后面列出升级详情
+pub fn summary_lines(rows: &[SummaryRow], lang: &str) -> Vec<String> {
+    let header = [
+        t!(lang, "summary-header-tool"),
+        t!(lang, "summary-header-status"),
+        t!(lang, "summary-header-upgrades"),
+    ];
+    let width = rows
+        .iter()
+        .map(|row| row.tool.display_name().len())
+        .chain([display_width(&header[0])])
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![format!(
+        "{}  {} {}",
+        pad(&header[0], width),
+        pad(&header[1], 7),
+        header[2]
+    )];
+    for row in rows {
+        let status = if row.success {
+            t!(lang, "summary-status-ok")
+        } else {
+            t!(lang, "summary-status-failed")
+        };
+        let upgrades = if row.success {
+            row.upgrades.len().to_string()
+        } else {
+            "-".to_string()
+        };
+        lines.push(format!(
+            "{}  {} {}",
+            pad(row.tool.display_name(), width),
+            pad(&status, 7),
+            upgrades
+        ));
+    }
+    for row in rows.iter().filter(|row| !row.upgrades.is_empty()) {
+        lines.push(t!(
+            lang,
+            "summary-upgrades-heading",
+            tool = row.tool.display_name()
+        ));
+        lines.extend(row.upgrades.iter().map(|detail| format!("  {}", detail)));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_secs() {
+        assert_eq!(format_secs(7), "7s");
+        assert_eq!(format_secs(125), "2m05s");
+    }
+
+    #[test]
+    fn test_summary_lines() {
+        let rows = vec![
+            SummaryRow {
+                tool: Tool::Homebrew,
+                success: true,
+                upgrades: vec!["git 2.50.0 -> 2.51.0".to_string()],
+            },
+            SummaryRow {
+                tool: Tool::Mise,
+                success: false,
+                upgrades: Vec::new(),
+            },
+        ];
+        assert_eq!(
+            summary_lines(&rows, "en"),
+            vec![
+                "TOOL      STATUS  UPGRADES",
+                "Homebrew  ok      1",
+                "Mise      failed  -",
+                "Homebrew upgrades:",
+                "  git 2.50.0 -> 2.51.0",
+            ]
+        );
+    }
+}
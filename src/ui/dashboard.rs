@@ -0,0 +1,508 @@
+// 全屏更新仪表盘（--tui）
+// 每个工具一个面板，显示实时日志、耗时和状态；队列面板显示等待中和被阻塞的步骤
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Paragraph};
+use ratatui::Frame;
+use regex::Regex;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use super::progress::{ProgressDisplay, SimpleProgressState};
+use crate::parallel::Tool;
+use crate::runner::{cancel_running, running_logfiles};
+use crate::t;
+
+/// 非详细模式下每个面板显示的日志行数
+const COMPACT_LINES: usize = 5;
+/// 读取日志文件末尾的字节数
+const TAIL_BYTES: u64 = 64 * 1024;
+
+/// 工具的更新步骤
+pub(crate) fn steps(tool: &Tool) -> &'static [&'static str] {
+    match tool {
+        Tool::Homebrew => &["update", "upgrade", "cleanup"],
+        Tool::Rustup => &["update"],
+        Tool::Mise => &["up"],
+    }
+}
+
+/// 工具日志文件名前缀
+pub(crate) fn log_prefix(tool: &Tool) -> &'static str {
+    match tool {
+        Tool::Homebrew => "brew_",
+        Tool::Rustup => "rustup_",
+        Tool::Mise => "mise_",
+    }
+}
+
+/// 根据日志文件名判断当前步骤
+pub(crate) fn step_index(tool: &Tool, logfile: &Path) -> usize {
+    let name = logfile
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match tool {
+        Tool::Homebrew if name.starts_with("brew_update") => 0,
+        Tool::Homebrew if name.starts_with("brew_cleanup") => 2,
+        Tool::Homebrew => 1,
+        _ => 0,
+    }
+}
+
+/// 单个工具面板的状态
+#[derive(Debug, Clone)]
+struct Pane {
+    tool: Tool,
+    state: SimpleProgressState,
+    started: Option<Instant>,
+    elapsed: Option<Duration>,
+    /// 当前或最近一个步骤的日志文件
+    logfile: Option<PathBuf>,
+}
+
+impl Pane {
+    fn elapsed(&self) -> Option<Duration> {
+        self.elapsed.or_else(|| self.started.map(|s| s.elapsed()))
+    }
+
+    fn current_step(&self) -> Option<usize> {
+        match self.state {
+            SimpleProgressState::Executing => Some(
+                self.logfile
+                    .as_ref()
+                    .map(|log| step_index(&self.tool, log))
+                    .unwrap_or(0),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// 队列中的步骤
+#[derive(Debug, Clone, PartialEq)]
+enum QueueEntry {
+    /// 工具尚未开始
+    Pending { tool: Tool, step: &'static str },
+    /// 等待同一工具的前一个步骤完成
+    Blocked {
+        tool: Tool,
+        step: &'static str,
+        by: &'static str,
+    },
+}
+
+/// 列出尚未执行的步骤
+fn queue(panes: &[Pane]) -> Vec<QueueEntry> {
+    let mut entries = Vec::new();
+    for pane in panes {
+        let tool_steps = steps(&pane.tool);
+        match pane.state {
+            SimpleProgressState::Preparing => {
+                for step in tool_steps {
+                    entries.push(QueueEntry::Pending {
+                        tool: pane.tool.clone(),
+                        step,
+                    });
+                }
+            }
+            SimpleProgressState::Executing => {
+                let current = pane.current_step().unwrap_or(0);
+                for step in tool_steps.iter().skip(current + 1) {
+                    entries.push(QueueEntry::Blocked {
+                        tool: pane.tool.clone(),
+                        step,
+                        by: tool_steps[current],
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// 格式化耗时为 mm:ss
+fn format_elapsed(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+/// 读取日志文件末尾
+pub(crate) fn read_tail(path: &Path) -> String {
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return String::new();
+    };
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if len > TAIL_BYTES {
+        let _ = file.seek(SeekFrom::Start(len - TAIL_BYTES));
+    }
+    let mut buf = Vec::new();
+    let _ = file.read_to_end(&mut buf);
+    String::from_utf8_lossy(&buf).to_string()
+}
+
+/// 取日志最后 `n` 个非空行：处理回车覆盖的进度输出并去除 ANSI 转义序列
+pub(crate) fn tail_lines(content: &str, n: usize) -> Vec<String> {
+    static ANSI: OnceLock<Regex> = OnceLock::new();
+    let ansi = ANSI.get_or_init(|| Regex::new(r"\x1b\[[0-9;?]*[ -/]*[@-~]").expect("valid regex"));
+
+    let lines: Vec<String> = content
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .map(|line| line.rsplit('\r').next().unwrap_or(line))
+        .map(|line| ansi.replace_all(line, "").trim_end().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(n)..].to_vec()
+}
+
+/// 界面状态（仅渲染线程使用）
+#[derive(Debug, Default)]
+struct View {
+    selected: usize,
+    expanded: bool,
+    verbose: bool,
+}
+
+fn state_label(state: &SimpleProgressState, lang: &str) -> (String, Color) {
+    match state {
+        SimpleProgressState::Preparing => (t!(lang, "dashboard-pending"), Color::Gray),
+        SimpleProgressState::Executing => (t!(lang, "dashboard-running"), Color::Cyan),
+        SimpleProgressState::Completed => (t!(lang, "dashboard-done"), Color::Green),
+        SimpleProgressState::Failed => (t!(lang, "dashboard-failed"), Color::Red),
+    }
+}
+
+fn render_pane(
+    frame: &mut Frame,
+    area: Rect,
+    pane: &Pane,
+    selected: bool,
+    view: &View,
+    lang: &str,
+) {
+    let (status, color) = state_label(&pane.state, lang);
+    let step = pane
+        .current_step()
+        .map(|i| format!(" › {}", steps(&pane.tool)[i]))
+        .unwrap_or_default();
+    let elapsed = pane
+        .elapsed()
+        .map(format_elapsed)
+        .unwrap_or_else(|| "--:--".to_string());
+
+    let mut block = Block::bordered()
+        .border_style(Style::default().fg(color))
+        .title(format!(" {}{} ", pane.tool.display_name(), step))
+        .title_bottom(Line::from(format!(" {} · {} ", status, elapsed)).right_aligned());
+    if selected {
+        block = block
+            .border_type(BorderType::Thick)
+            .title_style(Style::default().add_modifier(Modifier::BOLD));
+    }
+
+    let height = area.height.saturating_sub(2) as usize;
+    let lines = if view.verbose {
+        height
+    } else {
+        COMPACT_LINES.min(height)
+    };
+    let content = pane
+        .logfile
+        .as_deref()
+        .map(|log| tail_lines(&read_tail(log), lines))
+        .unwrap_or_default();
+    let text: Vec<Line> = content.into_iter().map(Line::from).collect();
+    frame.render_widget(Paragraph::new(text).block(block), area);
+}
+
+fn render(frame: &mut Frame, panes: &[Pane], view: &View, lang: &str) {
+    let entries = queue(panes);
+    let queue_height = (entries.len().max(1) as u16 + 2).min(7);
+    let [panes_area, queue_area, help_area] = Layout::vertical([
+        Constraint::Min(4),
+        Constraint::Length(queue_height),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    if view.expanded {
+        if let Some(pane) = panes.get(view.selected) {
+            render_pane(frame, panes_area, pane, true, view, lang);
+        }
+    } else if !panes.is_empty() {
+        let constraints = vec![Constraint::Ratio(1, panes.len() as u32); panes.len()];
+        let areas = Layout::horizontal(constraints).split(panes_area);
+        for (index, (pane, area)) in panes.iter().zip(areas.iter()).enumerate() {
+            render_pane(frame, *area, pane, index == view.selected, view, lang);
+        }
+    }
+
+    let lines: Vec<Line> = if entries.is_empty() {
+        vec![Line::from(t!(lang, "dashboard-queue-empty"))
+            .style(Style::default().fg(Color::DarkGray))]
+    } else {
+        entries
+            .iter()
+            .map(|entry| match entry {
+                QueueEntry::Pending { tool, step } => Line::from(format!(
+                    "○ {} › {}  {}",
+                    tool.display_name(),
+                    step,
+                    t!(lang, "dashboard-pending")
+                )),
+                QueueEntry::Blocked { tool, step, by } => Line::from(format!(
+                    "◌ {} › {}  {}",
+                    tool.display_name(),
+                    step,
+                    t!(lang, "dashboard-blocked-by", step = *by)
+                ))
+                .style(Style::default().fg(Color::DarkGray)),
+            })
+            .collect()
+    };
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::bordered().title(format!(" {} ", t!(lang, "dashboard-queue")))),
+        queue_area,
+    );
+
+    let help = t!(lang, "dashboard-help");
+    frame.render_widget(
+        Line::from(help).style(Style::default().fg(Color::DarkGray)),
+        help_area,
+    );
+}
+
+/// 正在执行的属于该工具的命令日志
+pub(crate) fn running_logfile(tool: &Tool, tmpdir: &Path) -> Option<PathBuf> {
+    running_logfiles().into_iter().find(|log| {
+        log.parent() == Some(tmpdir)
+            && log
+                .file_name()
+                .map(|n| n.to_string_lossy().starts_with(log_prefix(tool)))
+                .unwrap_or(false)
+    })
+}
+
+fn handle_key(view: &mut View, panes: &[Pane], tmpdir: &Path, code: KeyCode, ctrl: bool) {
+    let count = panes.len().max(1);
+    match code {
+        KeyCode::Char('c') if ctrl => {
+            for pane in panes {
+                if let Some(log) = running_logfile(&pane.tool, tmpdir) {
+                    cancel_running(&log);
+                }
+            }
+        }
+        KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => {
+            view.selected = (view.selected + 1) % count;
+        }
+        KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+            view.selected = (view.selected + count - 1) % count;
+        }
+        KeyCode::Enter | KeyCode::Char('e') => view.expanded = !view.expanded,
+        KeyCode::Char('v') => view.verbose = !view.verbose,
+        KeyCode::Char('c') => {
+            if let Some(pane) = panes.get(view.selected) {
+                if let Some(log) = running_logfile(&pane.tool, tmpdir) {
+                    cancel_running(&log);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 渲染线程：定期刷新面板并处理按键，直到收到停止信号
+fn render_loop(panes: Arc<Mutex<Vec<Pane>>>, tmpdir: PathBuf, stop: Arc<AtomicBool>, lang: String) {
+    let mut terminal = ratatui::init();
+    let mut view = View::default();
+
+    loop {
+        let snapshot = match panes.lock() {
+            Ok(mut panes) => {
+                for pane in panes.iter_mut() {
+                    if let Some(log) = running_logfile(&pane.tool, &tmpdir) {
+                        pane.logfile = Some(log);
+                    }
+                }
+                panes.clone()
+            }
+            Err(_) => break,
+        };
+        let _ = terminal.draw(|frame| render(frame, &snapshot, &view, &lang));
+
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+        if let Ok(true) = event::poll(Duration::from_millis(200)) {
+            if let Ok(Event::Key(key)) = event::read() {
+                if key.kind == KeyEventKind::Press {
+                    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                    handle_key(&mut view, &snapshot, &tmpdir, key.code, ctrl);
+                }
+            }
+        }
+    }
+
+    ratatui::restore();
+}
+
+/// 全屏更新仪表盘
+pub struct Dashboard {
+    panes: Arc<Mutex<Vec<Pane>>>,
+    tmpdir: PathBuf,
+    lang: String,
+    stop: Arc<AtomicBool>,
+    render_thread: Option<JoinHandle<()>>,
+}
+
+impl Dashboard {
+    /// 创建仪表盘，`tmpdir` 为本次更新的日志目录
+    pub fn new(tmpdir: &Path, lang: &str) -> Self {
+        Self {
+            panes: Arc::new(Mutex::new(Vec::new())),
+            tmpdir: tmpdir.to_path_buf(),
+            lang: lang.to_string(),
+            stop: Arc::new(AtomicBool::new(false)),
+            render_thread: None,
+        }
+    }
+}
+
+impl ProgressDisplay for Dashboard {
+    fn create_progress_bars(&mut self, tools: &[Tool]) {
+        if let Ok(mut panes) = self.panes.lock() {
+            *panes = tools
+                .iter()
+                .map(|tool| Pane {
+                    tool: tool.clone(),
+                    state: SimpleProgressState::Preparing,
+                    started: None,
+                    elapsed: None,
+                    logfile: None,
+                })
+                .collect();
+        }
+        if self.render_thread.is_none() {
+            let panes = self.panes.clone();
+            let tmpdir = self.tmpdir.clone();
+            let stop = self.stop.clone();
+            let lang = self.lang.clone();
+            self.render_thread = Some(std::thread::spawn(move || {
+                render_loop(panes, tmpdir, stop, lang)
+            }));
+        }
+    }
+
+    fn update_state(&mut self, tool: &Tool, new_state: SimpleProgressState) {
+        let Ok(mut panes) = self.panes.lock() else {
+            return;
+        };
+        let Some(pane) = panes.iter_mut().find(|p| &p.tool == tool) else {
+            return;
+        };
+        match new_state {
+            SimpleProgressState::Executing if pane.started.is_none() => {
+                pane.started = Some(Instant::now());
+            }
+            SimpleProgressState::Completed | SimpleProgressState::Failed => {
+                pane.elapsed = pane.started.map(|s| s.elapsed());
+            }
+            _ => {}
+        }
+        pane.state = new_state;
+    }
+
+    fn finalize_all(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.render_thread.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for Dashboard {
+    /// 确保提前退出时也能恢复终端
+    fn drop(&mut self) {
+        self.finalize_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane(tool: Tool, state: SimpleProgressState, logfile: Option<&str>) -> Pane {
+        Pane {
+            tool,
+            state,
+            started: None,
+            elapsed: None,
+            logfile: logfile.map(PathBuf::from),
+        }
+    }
+
+    #[test]
+    fn test_tail_lines() {
+        let content = "==> Upgrading git\n\x1b[32mok\x1b[0m\n\n 10%\r 50%\r100%\nlast\n";
+        // 去除颜色、保留回车覆盖后的最后内容、跳过空行
+        assert_eq!(tail_lines(content, 3), vec!["ok", "100%", "last"]);
+        assert_eq!(tail_lines("a\nb\n", 10), vec!["a", "b"]);
+        assert!(tail_lines("", 5).is_empty());
+    }
+
+    #[test]
+    fn test_queue_lists_pending_and_blocked_steps() {
+        let panes = vec![
+            pane(
+                Tool::Homebrew,
+                SimpleProgressState::Executing,
+                Some("/tmp/x/brew_update.log"),
+            ),
+            pane(Tool::Rustup, SimpleProgressState::Completed, None),
+            pane(Tool::Mise, SimpleProgressState::Preparing, None),
+        ];
+        assert_eq!(
+            queue(&panes),
+            vec![
+                QueueEntry::Blocked {
+                    tool: Tool::Homebrew,
+                    step: "upgrade",
+                    by: "update"
+                },
+                QueueEntry::Blocked {
+                    tool: Tool::Homebrew,
+                    step: "cleanup",
+                    by: "update"
+                },
+                QueueEntry::Pending {
+                    tool: Tool::Mise,
+                    step: "up"
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_step_index_and_elapsed() {
+        assert_eq!(
+            step_index(&Tool::Homebrew, Path::new("brew_outdated.log")),
+            1
+        );
+        assert_eq!(
+            step_index(&Tool::Homebrew, Path::new("brew_cleanup.log")),
+            2
+        );
+        assert_eq!(format_elapsed(Duration::from_secs(83)), "01:23");
+    }
+}
@@ -1,240 +1,121 @@
 // 统一图标风格系统
-// 提供本地化的图标资源管理，支持颜色和样式效果
+// 图标字符和颜色来自当前主题（见 theme.rs），颜色开关与 colors.rs 一致
 
+use super::colors::supports_color;
+use super::theme::{self, Role, Theme};
 use colored::*;
-use std::env;
-
-/// 图标类型枚举
-#[derive(Debug, Clone, PartialEq)]
-pub enum IconType {
-    /// 本地化图标（支持颜色和样式）
-    Local,
-    /// ASCII 字符降级
-    Ascii,
-}
-
-/// 图标样式枚举
-#[derive(Debug, Clone, PartialEq)]
-pub enum IconStyle {
-    /// 默认样式
-    Default,
-    /// 成功样式（绿色加粗）
-    Success,
-    /// 失败样式（红色）
-    Failure,
-    /// 警告样式（黄色）
-    Warning,
-    /// 信息样式（蓝色）
-    Info,
-}
 
 /// 图标管理器
 pub struct IconManager {
-    icon_type: IconType,
+    theme: Theme,
     supports_color: bool,
 }
 
 impl IconManager {
-    /// 创建新的图标管理器
+    /// 使用当前主题创建图标管理器
     pub fn new() -> Self {
-        let icon_type = Self::detect_icon_support();
-        let supports_color = Self::detect_color_support();
-        Self {
-            icon_type,
-            supports_color,
-        }
-    }
-
-    /// 检测终端对图标的支持情况
-    fn detect_icon_support() -> IconType {
-        // 检查是否明确禁用了图标
-        if env::var("DEVMODE_NO_ICONS").is_ok() {
-            return IconType::Ascii;
-        }
-
-        // 检查是否明确启用了本地化图标
-        if env::var("DEVMODE_FORCE_LOCAL_ICONS").is_ok() {
-            return IconType::Local;
-        }
-
-        // 默认使用本地化图标
-        IconType::Local
+        Self::with_theme(theme::current().clone(), supports_color())
     }
 
-    /// 检测终端对颜色的支持情况
-    fn detect_color_support() -> bool {
-        // 检查是否明确禁用了颜色
-        if env::var("NO_COLOR").is_ok() || env::var("DEVMODE_NO_COLOR").is_ok() {
-            return false;
-        }
-
-        // 检查是否明确启用了颜色
-        if env::var("FORCE_COLOR").is_ok() || env::var("DEVMODE_FORCE_COLOR").is_ok() {
-            return true;
+    /// 使用指定主题创建图标管理器
+    pub fn with_theme(theme: Theme, supports_color: bool) -> Self {
+        Self {
+            theme,
+            supports_color,
         }
-
-        // 检查终端类型
-        let term = env::var("TERM").unwrap_or_default();
-        let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
-
-        // 大多数现代终端都支持颜色
-        term.contains("xterm")
-            || term.contains("screen")
-            || term.contains("tmux")
-            || term_program.contains("iTerm")
-            || term_program.contains("Terminal")
-            || term_program.contains("vscode")
-            || term_program.contains("Alacritty")
-            || term_program.contains("Kitty")
-            || term_program.contains("WezTerm")
-            || term_program.contains("Hyper")
-            || term_program.contains("Terminus")
-            || term_program.contains("Terminator")
-            || term_program.contains("Gnome")
-            || term_program.contains("Konsole")
-            || term_program.contains("Xfce")
     }
 
-    /// 获取当前图标类型
-    #[cfg(test)]
-    pub fn icon_type(&self) -> &IconType {
-        &self.icon_type
-    }
-
-    /// 应用图标样式
-    fn apply_style(&self, icon: &str, style: IconStyle) -> String {
-        if !self.supports_color {
+    /// 按角色着色，成功图标加粗
+    fn apply_style(&self, icon: &str, role: Role) -> String {
+        let Some(color) = self.theme.color(role).filter(|_| self.supports_color) else {
             return icon.to_string();
-        }
-
-        match style {
-            IconStyle::Success => icon.green().bold().to_string(),
-            IconStyle::Failure => icon.red().to_string(),
-            IconStyle::Warning => icon.yellow().to_string(),
-            IconStyle::Info => icon.blue().to_string(),
-            IconStyle::Default => icon.to_string(),
+        };
+        let styled = icon.color(color);
+        if role == Role::Success {
+            styled.bold().to_string()
+        } else {
+            styled.to_string()
         }
     }
 
     /// 获取火箭图标 (🚀)
     pub fn rocket(&self) -> String {
-        match self.icon_type {
-            IconType::Local => {
-                let icon = "🚀";
-                self.apply_style(icon, IconStyle::Default)
-            }
-            IconType::Ascii => ">".to_string(),
-        }
+        self.theme.glyphs.rocket.clone()
     }
 
     /// 获取剪贴板图标 (📋)
     pub fn clipboard(&self) -> String {
-        match self.icon_type {
-            IconType::Local => {
-                let icon = "📋";
-                self.apply_style(icon, IconStyle::Default)
-            }
-            IconType::Ascii => "[*]".to_string(),
-        }
+        self.theme.glyphs.clipboard.clone()
     }
 
-    /// 获取成功图标 (✅) - 绿色加粗
+    /// 获取成功图标 (✓)
     pub fn success(&self) -> String {
-        match self.icon_type {
-            IconType::Local => {
-                let icon = "✓";
-                self.apply_style(icon, IconStyle::Success)
-            }
-            IconType::Ascii => "✓".to_string(),
-        }
+        self.apply_style(&self.theme.glyphs.success, Role::Success)
     }
 
-    /// 获取失败图标 (❌) - 红色
+    /// 获取失败图标 (✗)
     pub fn failure(&self) -> String {
-        match self.icon_type {
-            IconType::Local => {
-                let icon = "✗";
-                self.apply_style(icon, IconStyle::Failure)
-            }
-            IconType::Ascii => "✗".to_string(),
-        }
+        self.apply_style(&self.theme.glyphs.failure, Role::Failure)
     }
 
-    /// 获取警告图标 (⚠️) - 黄色
+    /// 获取警告图标 (⚠)
     pub fn warning(&self) -> String {
-        match self.icon_type {
-            IconType::Local => {
-                let icon = "⚠";
-                self.apply_style(icon, IconStyle::Warning)
-            }
-            IconType::Ascii => "⚠".to_string(),
-        }
+        self.apply_style(&self.theme.glyphs.warning, Role::Warning)
     }
 
-    /// 获取信息图标 (ℹ️) - 蓝色
+    /// 获取信息图标 (ℹ)
     pub fn info(&self) -> String {
-        match self.icon_type {
-            IconType::Local => {
-                let icon = "ℹ";
-                self.apply_style(icon, IconStyle::Info)
-            }
-            IconType::Ascii => "ℹ".to_string(),
-        }
+        self.apply_style(&self.theme.glyphs.info, Role::Info)
+    }
+
+    /// 获取版本箭头 (→)
+    pub fn arrow(&self) -> String {
+        self.apply_style(&self.theme.glyphs.arrow, Role::Arrow)
+    }
+
+    /// 未着色的版本箭头，用于整行着色的消息中
+    pub fn plain_arrow(&self) -> &str {
+        &self.theme.glyphs.arrow
     }
 
     /// 获取包裹图标 (📦) - Homebrew
     pub fn package(&self) -> String {
-        match self.icon_type {
-            IconType::Local => {
-                let icon = "📦";
-                self.apply_style(icon, IconStyle::Default)
-            }
-            IconType::Ascii => "📦".to_string(),
-        }
+        self.theme.glyphs.package.clone()
     }
 
     /// 获取 Rust 图标 (🦀)
     pub fn rust(&self) -> String {
-        match self.icon_type {
-            IconType::Local => {
-                let icon = "🦀";
-                self.apply_style(icon, IconStyle::Default)
-            }
-            IconType::Ascii => "🦀".to_string(),
-        }
+        self.theme.glyphs.rust.clone()
     }
 
     /// 获取扳手图标 (🔧) - Mise
     pub fn wrench(&self) -> String {
-        match self.icon_type {
-            IconType::Local => {
-                let icon = "🔧";
-                self.apply_style(icon, IconStyle::Default)
-            }
-            IconType::Ascii => "🔧".to_string(),
-        }
+        self.theme.glyphs.wrench.clone()
     }
 
-    /// 获取暂停图标 (⏸️)
+    /// 获取暂停图标 (⏸)
     pub fn pause(&self) -> String {
-        match self.icon_type {
-            IconType::Local => {
-                let icon = "⏸";
-                self.apply_style(icon, IconStyle::Default)
-            }
-            IconType::Ascii => "⏸".to_string(),
-        }
+        self.theme.glyphs.pause.clone()
     }
 
-    /// 获取工具图标 (🛠️)
+    /// 获取工具图标 (🛠)
     pub fn tools(&self) -> String {
-        match self.icon_type {
-            IconType::Local => {
-                let icon = "🛠";
-                self.apply_style(icon, IconStyle::Default)
-            }
-            IconType::Ascii => "🛠".to_string(),
-        }
+        self.theme.glyphs.tools.clone()
+    }
+
+    /// 获取刷新图标 (🔄)
+    pub fn refresh(&self) -> String {
+        self.theme.glyphs.refresh.clone()
+    }
+
+    /// 获取备忘图标 (📝)
+    pub fn memo(&self) -> String {
+        self.theme.glyphs.memo.clone()
+    }
+
+    /// 获取提示图标 (💡)
+    pub fn bulb(&self) -> String {
+        self.theme.glyphs.bulb.clone()
     }
 }
 
@@ -248,15 +129,6 @@ impl Default for IconManager {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_icon_manager_creation() {
-        let manager = IconManager::new();
-        assert!(matches!(
-            manager.icon_type(),
-            IconType::Local | IconType::Ascii
-        ));
-    }
-
     #[test]
     fn test_icon_consistency() {
         let manager = IconManager::new();
@@ -268,19 +140,31 @@ mod tests {
         let _ = manager.failure();
         let _ = manager.warning();
         let _ = manager.info();
+        let _ = manager.arrow();
         let _ = manager.package();
         let _ = manager.rust();
+        let _ = manager.refresh();
+        let _ = manager.memo();
+        let _ = manager.bulb();
         let _ = manager.wrench();
         let _ = manager.pause();
         let _ = manager.tools();
     }
 
     #[test]
-    fn test_ascii_fallback() {
-        // 设置环境变量强制使用 ASCII 模式
-        env::set_var("DEVMODE_NO_ICONS", "1");
-        let manager = IconManager::new();
-        assert_eq!(manager.icon_type(), &IconType::Ascii);
-        env::remove_var("DEVMODE_NO_ICONS");
+    fn test_ascii_theme() {
+        let manager = IconManager::with_theme(Theme::builtin("ascii").unwrap(), false);
+        assert_eq!(manager.success(), "+");
+        assert_eq!(manager.failure(), "x");
+        assert_eq!(manager.arrow(), "->");
+        assert_eq!(manager.rocket(), ">");
+    }
+
+    #[test]
+    fn test_monochrome_theme() {
+        // 单色主题即使在支持颜色的终端中也不着色
+        let manager = IconManager::with_theme(Theme::builtin("monochrome").unwrap(), true);
+        assert_eq!(manager.success(), "✓");
+        assert_eq!(manager.warning(), "⚠");
     }
 }
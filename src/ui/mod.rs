@@ -1,6 +1,10 @@
 // UI 模块 - 用户界面相关功能
 // 包含颜色输出、进度条和图标
 
+pub mod accessible;
 pub mod colors;
+pub mod compact;
+pub mod dashboard;
 pub mod icons;
 pub mod progress;
+pub mod theme;
@@ -1,5 +1,6 @@
 use super::icons::IconManager;
 use crate::parallel::Tool;
+use crate::t;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use std::collections::HashMap;
 
@@ -24,17 +25,43 @@ impl SimpleProgressState {
     }
 
     /// 获取状态显示消息
-    pub fn display_message(&self, tool_name: &str) -> String {
+    pub fn display_message(&self, lang: &str, tool_name: &str) -> String {
         let icons = IconManager::new();
         match self {
-            SimpleProgressState::Preparing => format!("{} 准备中...", tool_name),
-            SimpleProgressState::Executing => format!("{} 执行中...", tool_name),
-            SimpleProgressState::Completed => format!("{} {} 完成", icons.success(), tool_name),
-            SimpleProgressState::Failed => format!("{} {} 失败", icons.failure(), tool_name),
+            SimpleProgressState::Preparing => t!(lang, "progress-preparing", tool = tool_name),
+            SimpleProgressState::Executing => t!(lang, "progress-executing", tool = tool_name),
+            SimpleProgressState::Completed => {
+                t!(
+                    lang,
+                    "progress-done",
+                    icon = icons.success(),
+                    tool = tool_name
+                )
+            }
+            SimpleProgressState::Failed => {
+                t!(
+                    lang,
+                    "progress-failed",
+                    icon = icons.failure(),
+                    tool = tool_name
+                )
+            }
         }
     }
 }
 
+/// 更新过程的进度显示
+///
+/// 由 [`SimpleProgressManager`] 和全屏仪表盘（`--tui`）实现，编排层只通过此接口更新状态
+pub trait ProgressDisplay: Send {
+    /// 为工具创建进度显示
+    fn create_progress_bars(&mut self, tools: &[Tool]);
+    /// 更新工具状态
+    fn update_state(&mut self, tool: &Tool, new_state: SimpleProgressState);
+    /// 所有工具执行完成后结束显示
+    fn finalize_all(&mut self);
+}
+
 /// 简化的进度条管理器
 ///
 /// 此管理器负责在应用程序编排层统一管理所有工具的进度条显示。
@@ -71,6 +98,7 @@ pub struct SimpleProgressManager {
     multi_progress: MultiProgress,
     progress_bars: HashMap<Tool, ProgressBar>,
     states: HashMap<Tool, SimpleProgressState>,
+    lang: String,
 }
 
 impl SimpleProgressManager {
@@ -82,6 +110,7 @@ impl SimpleProgressManager {
             multi_progress: MultiProgress::new(),
             progress_bars: HashMap::new(),
             states: HashMap::new(),
+            lang: crate::i18n::detect_system_language(),
         }
     }
 
@@ -125,7 +154,9 @@ impl SimpleProgressManager {
                 pb.set_style(style.progress_chars("▰▱ "));
             }
 
-            pb.set_message(format!("{} 准备中...", tool.display_name()));
+            pb.set_message(
+                SimpleProgressState::Preparing.display_message(&self.lang, tool.display_name()),
+            );
             pb.set_position(0);
 
             self.progress_bars.insert(tool.clone(), pb);
@@ -144,7 +175,7 @@ impl SimpleProgressManager {
     pub fn update_state(&mut self, tool: &Tool, new_state: SimpleProgressState) {
         if let Some(pb) = self.progress_bars.get(tool) {
             let progress = new_state.progress_percentage();
-            let message = new_state.display_message(tool.display_name());
+            let message = new_state.display_message(&self.lang, tool.display_name());
 
             pb.set_position(progress);
             pb.set_message(message);
@@ -158,44 +189,22 @@ impl SimpleProgressManager {
     /// 此方法应在所有工具执行完成后调用。
     pub fn finalize_all(&mut self) {
         for (tool, pb) in &self.progress_bars {
-            match self.states.get(tool) {
-                Some(SimpleProgressState::Completed) => {
-                    pb.set_message(format!(
-                        "{} {} 完成",
-                        IconManager::new().success(),
-                        tool.display_name()
-                    ));
-                }
-                Some(SimpleProgressState::Failed) => {
-                    pb.set_message(format!(
-                        "{} {} 失败",
-                        IconManager::new().failure(),
-                        tool.display_name()
-                    ));
+            let message = match self.states.get(tool) {
+                Some(state @ (SimpleProgressState::Completed | SimpleProgressState::Failed)) => {
+                    state.display_message(&self.lang, tool.display_name())
                 }
-                _ => {
-                    pb.set_message(format!(
-                        "{} {} 中断",
-                        IconManager::new().pause(),
-                        tool.display_name()
-                    ));
-                }
-            }
+                _ => t!(
+                    &self.lang,
+                    "progress-interrupted",
+                    icon = IconManager::new().pause(),
+                    tool = tool.display_name()
+                ),
+            };
+            pb.set_message(message);
             pb.finish();
         }
     }
 
-    /// 检查工具是否已有进度条
-    ///
-    /// # 参数
-    /// * `tool` - 要检查的工具
-    ///
-    /// # 返回值
-    /// 如果该工具已有进度条则返回 true，否则返回 false
-    pub fn has_progress_bar(&self, tool: &Tool) -> bool {
-        self.progress_bars.contains_key(tool)
-    }
-
     /// 获取当前活跃的进度条数量
     ///
     /// 这个方法用于调试和监控进度条状态，返回当前管理的进度条数量。
@@ -215,6 +224,20 @@ impl SimpleProgressManager {
     }
 }
 
+impl ProgressDisplay for SimpleProgressManager {
+    fn create_progress_bars(&mut self, tools: &[Tool]) {
+        SimpleProgressManager::create_progress_bars(self, tools);
+    }
+
+    fn update_state(&mut self, tool: &Tool, new_state: SimpleProgressState) {
+        SimpleProgressManager::update_state(self, tool, new_state);
+    }
+
+    fn finalize_all(&mut self) {
+        SimpleProgressManager::finalize_all(self);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
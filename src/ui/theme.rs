@@ -0,0 +1,337 @@
+// 主题
+// 把语义角色（成功、失败、警告、信息、工具标题、版本箭头）对应到颜色和图标；
+// 内置 default、ascii、nerd-font、monochrome 四个主题，配置文件的 [theme] 可选择主题并逐项覆盖
+
+use crate::config::ThemeConfig;
+use anyhow::{bail, Result};
+use colored::Color;
+use std::sync::OnceLock;
+
+/// 内置主题名称
+pub const BUILTIN_THEMES: [&str; 4] = ["default", "ascii", "nerd-font", "monochrome"];
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// 带颜色的语义角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Success,
+    Failure,
+    Warning,
+    Info,
+    /// 横幅和工具标题
+    Header,
+    /// 版本箭头
+    Arrow,
+}
+
+/// 图标字符
+#[derive(Debug, Clone, PartialEq)]
+pub struct Glyphs {
+    pub success: String,
+    pub failure: String,
+    pub warning: String,
+    pub info: String,
+    pub arrow: String,
+    pub rocket: String,
+    pub clipboard: String,
+    pub package: String,
+    pub rust: String,
+    pub wrench: String,
+    pub pause: String,
+    pub tools: String,
+    pub refresh: String,
+    pub memo: String,
+    pub bulb: String,
+}
+
+/// 各角色的颜色，`None` 表示不着色
+#[derive(Debug, Clone, PartialEq)]
+pub struct Colors {
+    pub success: Option<Color>,
+    pub failure: Option<Color>,
+    pub warning: Option<Color>,
+    pub info: Option<Color>,
+    pub header: Option<Color>,
+    pub arrow: Option<Color>,
+}
+
+/// 图标和颜色主题
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub glyphs: Glyphs,
+    pub colors: Colors,
+}
+
+impl Glyphs {
+    fn new(glyphs: [&str; 15]) -> Self {
+        let [success, failure, warning, info, arrow, rocket, clipboard, package, rust, wrench, pause, tools, refresh, memo, bulb] =
+            glyphs.map(str::to_string);
+        Self {
+            success,
+            failure,
+            warning,
+            info,
+            arrow,
+            rocket,
+            clipboard,
+            package,
+            rust,
+            wrench,
+            pause,
+            tools,
+            refresh,
+            memo,
+            bulb,
+        }
+    }
+}
+
+impl Colors {
+    fn standard() -> Self {
+        Self {
+            success: Some(Color::Green),
+            failure: Some(Color::Red),
+            warning: Some(Color::Yellow),
+            info: Some(Color::Blue),
+            header: Some(Color::Magenta),
+            arrow: None,
+        }
+    }
+
+    fn none() -> Self {
+        Self {
+            success: None,
+            failure: None,
+            warning: None,
+            info: None,
+            header: None,
+            arrow: None,
+        }
+    }
+}
+
+impl Theme {
+    /// 内置主题，名称未知时返回 `None`
+    pub fn builtin(name: &str) -> Option<Self> {
+        let (glyphs, colors) = match name {
+            "default" => (
+                Glyphs::new([
+                    "✓", "✗", "⚠", "ℹ", "→", "🚀", "📋", "📦", "🦀", "🔧", "⏸", "🛠", "🔄", "📝",
+                    "💡",
+                ]),
+                Colors::standard(),
+            ),
+            "ascii" => (
+                Glyphs::new([
+                    "+", "x", "!", "i", "->", ">", "[*]", "*", "*", "*", "-", "*", "~", "*", "*",
+                ]),
+                Colors::standard(),
+            ),
+            // Nerd Fonts 私有区字符：check、times、warning、info_circle、arrow_right、rocket、
+            // paste、package、rust、wrench、pause、cogs、refresh、pencil、lightbulb
+            "nerd-font" => (
+                Glyphs::new([
+                    "\u{f00c}", "\u{f00d}", "\u{f071}", "\u{f05a}", "\u{f061}", "\u{f135}",
+                    "\u{f0ea}", "\u{f487}", "\u{e7a8}", "\u{f0ad}", "\u{f04c}", "\u{f085}",
+                    "\u{f021}", "\u{f040}", "\u{f0eb}",
+                ]),
+                Colors {
+                    arrow: Some(Color::Cyan),
+                    ..Colors::standard()
+                },
+            ),
+            "monochrome" => (
+                Glyphs::new([
+                    "✓", "✗", "⚠", "ℹ", "→", "🚀", "📋", "📦", "🦀", "🔧", "⏸", "🛠", "🔄", "📝",
+                    "💡",
+                ]),
+                Colors::none(),
+            ),
+            _ => return None,
+        };
+        Some(Self {
+            name: name.to_string(),
+            glyphs,
+            colors,
+        })
+    }
+
+    /// 按配置选择内置主题并应用覆盖项；`DEVTOOL_THEME` 环境变量优先于配置中的主题名，
+    /// 已弃用的 `DEVMODE_NO_ICONS`、`DEVMODE_FORCE_LOCAL_ICONS` 等同于 `DEVTOOL_THEME=ascii`
+    pub fn from_config(config: &ThemeConfig) -> Result<Self> {
+        let legacy_ascii = ["DEVMODE_NO_ICONS", "DEVMODE_FORCE_LOCAL_ICONS"]
+            .iter()
+            .any(|name| std::env::var_os(name).is_some());
+        let name = std::env::var("DEVTOOL_THEME")
+            .ok()
+            .filter(|name| !name.is_empty())
+            .or_else(|| legacy_ascii.then(|| "ascii".to_string()))
+            .or_else(|| config.name.clone())
+            .unwrap_or_else(|| "default".to_string());
+        let Some(mut theme) = Self::builtin(&name) else {
+            bail!(
+                "unknown theme {:?} (available: {})",
+                name,
+                BUILTIN_THEMES.join(", ")
+            );
+        };
+
+        let colors = &config.colors;
+        for (slot, value) in [
+            (&mut theme.colors.success, &colors.success),
+            (&mut theme.colors.failure, &colors.failure),
+            (&mut theme.colors.warning, &colors.warning),
+            (&mut theme.colors.info, &colors.info),
+            (&mut theme.colors.header, &colors.header),
+            (&mut theme.colors.arrow, &colors.arrow),
+        ] {
+            if let Some(value) = value {
+                *slot = parse_color(value)?;
+            }
+        }
+
+        let glyphs = &config.glyphs;
+        for (slot, value) in [
+            (&mut theme.glyphs.success, &glyphs.success),
+            (&mut theme.glyphs.failure, &glyphs.failure),
+            (&mut theme.glyphs.warning, &glyphs.warning),
+            (&mut theme.glyphs.info, &glyphs.info),
+            (&mut theme.glyphs.arrow, &glyphs.arrow),
+            (&mut theme.glyphs.rocket, &glyphs.rocket),
+            (&mut theme.glyphs.clipboard, &glyphs.clipboard),
+            (&mut theme.glyphs.package, &glyphs.package),
+            (&mut theme.glyphs.rust, &glyphs.rust),
+            (&mut theme.glyphs.wrench, &glyphs.wrench),
+            (&mut theme.glyphs.pause, &glyphs.pause),
+            (&mut theme.glyphs.tools, &glyphs.tools),
+            (&mut theme.glyphs.refresh, &glyphs.refresh),
+            (&mut theme.glyphs.memo, &glyphs.memo),
+            (&mut theme.glyphs.bulb, &glyphs.bulb),
+        ] {
+            if let Some(value) = value {
+                *slot = value.clone();
+            }
+        }
+        Ok(theme)
+    }
+
+    /// 角色的颜色
+    pub fn color(&self, role: Role) -> Option<Color> {
+        match role {
+            Role::Success => self.colors.success,
+            Role::Failure => self.colors.failure,
+            Role::Warning => self.colors.warning,
+            Role::Info => self.colors.info,
+            Role::Header => self.colors.header,
+            Role::Arrow => self.colors.arrow,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::builtin("default").expect("default theme")
+    }
+}
+
+/// 解析颜色名（"red"、"bright blue"）或 "#rrggbb"；"none" 表示不着色
+fn parse_color(value: &str) -> Result<Option<Color>> {
+    if value.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    match value.parse() {
+        Ok(color) => Ok(Some(color)),
+        Err(()) => bail!("unknown color {:?}", value),
+    }
+}
+
+/// 已弃用的环境变量及其替代写法
+pub const DEPRECATED_ENV_VARS: [(&str, &str); 4] = [
+    ("DEVMODE_NO_ICONS", "DEVTOOL_THEME=ascii"),
+    ("DEVMODE_FORCE_LOCAL_ICONS", "DEVTOOL_THEME=ascii"),
+    ("DEVMODE_NO_COLOR", "NO_COLOR=1"),
+    ("DEVMODE_FORCE_COLOR", "CLICOLOR_FORCE=1"),
+];
+
+/// 当前环境中设置了的已弃用变量：(变量, 替代写法)
+pub fn deprecated_env_vars() -> Vec<(&'static str, &'static str)> {
+    DEPRECATED_ENV_VARS
+        .into_iter()
+        .filter(|(name, _)| std::env::var_os(name).is_some())
+        .collect()
+}
+
+/// 设置当前主题（来自配置文件），只有第一次调用生效
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// 当前主题；未设置时使用 `DEVTOOL_THEME` 指定的内置主题或默认主题
+pub fn current() -> &'static Theme {
+    THEME.get_or_init(|| Theme::from_config(&ThemeConfig::default()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_builtin_themes() {
+        for name in BUILTIN_THEMES {
+            let theme = Theme::builtin(name).unwrap();
+            assert_eq!(theme.name, name);
+            assert!(!theme.glyphs.success.is_empty());
+        }
+        assert!(Theme::builtin("solarized").is_none());
+        assert_eq!(Theme::builtin("ascii").unwrap().glyphs.arrow, "->");
+        assert_eq!(
+            Theme::builtin("monochrome").unwrap().color(Role::Failure),
+            None
+        );
+        assert_eq!(Theme::default().color(Role::Header), Some(Color::Magenta));
+    }
+
+    #[test]
+    fn test_theme_from_config() {
+        let config = Config::parse(
+            r##"
+[theme]
+name = "monochrome"
+
+[theme.colors]
+success = "bright green"
+arrow = "#808080"
+
+[theme.glyphs]
+success = "OK"
+"##,
+        )
+        .unwrap();
+        let theme = Theme::from_config(&config.theme).unwrap();
+        assert_eq!(theme.color(Role::Success), Some(Color::BrightGreen));
+        assert_eq!(
+            theme.color(Role::Arrow),
+            Some(Color::TrueColor {
+                r: 128,
+                g: 128,
+                b: 128
+            })
+        );
+        assert_eq!(theme.color(Role::Failure), None);
+        assert_eq!(theme.glyphs.success, "OK");
+        assert_eq!(theme.glyphs.failure, "✗");
+
+        let config = Config::parse("[theme]\nname = \"solarized\"\n").unwrap();
+        assert!(Theme::from_config(&config.theme).is_err());
+        let config = Config::parse("[theme.colors]\ninfo = \"blurple\"\n").unwrap();
+        assert!(Theme::from_config(&config.theme).is_err());
+        let config = Config::parse("[theme.colors]\ninfo = \"none\"\n").unwrap();
+        assert_eq!(
+            Theme::from_config(&config.theme).unwrap().color(Role::Info),
+            None
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use anyhow::{anyhow, Result};
 use std::fs;
 use std::path::PathBuf;
 
@@ -16,7 +17,7 @@ pub fn ensure_cache_dir() -> Result<PathBuf, std::io::Error> {
     fs::create_dir_all(&cache_dir)?;
 
     // 创建子目录结构
-    let subdirs = ["homebrew", "rustup", "mise", "feedback"];
+    let subdirs = ["runs", "feedback", "quarantine"];
     for subdir in &subdirs {
         let subdir_path = cache_dir.join(subdir);
         fs::create_dir_all(&subdir_path)?;
@@ -25,6 +26,85 @@ pub fn ensure_cache_dir() -> Result<PathBuf, std::io::Error> {
     Ok(cache_dir)
 }
 
+/// 单引号转义，用于拼接 shell 命令
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// 是否为合法的软件包、工具链、工具或版本标识
+///
+/// 只允许字母、数字和 `-._+@/:`，且不能以 `-` 开头，例如 `node@20`、`homebrew/cask`、
+/// `stable-x86_64-unknown-linux-gnu`、`cargo:ripgrep`、`npm:@scope/pkg`
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-._+@/:".contains(c))
+}
+
+/// 解析时长字符串
+///
+/// 支持 `s`/`m`/`h`/`d`/`w` 后缀，例如 "30m"、"36h"、"7d"；不带后缀的数字按天计算
+pub fn parse_duration(input: &str) -> Result<chrono::Duration> {
+    let input = input.trim();
+    let (number, unit) = match input.find(|c: char| !c.is_ascii_digit()) {
+        Some(idx) => input.split_at(idx),
+        None => (input, "d"),
+    };
+    let value: i64 = number
+        .parse()
+        .map_err(|_| anyhow!("invalid duration: {:?}", input))?;
+    let duration = match unit.trim() {
+        "s" => chrono::Duration::try_seconds(value),
+        "m" => chrono::Duration::try_minutes(value),
+        "h" => chrono::Duration::try_hours(value),
+        "d" => chrono::Duration::try_days(value),
+        "w" => chrono::Duration::try_weeks(value),
+        _ => return Err(anyhow!("invalid duration unit: {:?}", input)),
+    };
+    duration.ok_or_else(|| anyhow!("duration out of range: {:?}", input))
+}
+
+/// 解析大小字符串
+///
+/// 支持 `B`/`KB`/`MB`/`GB` 后缀（按 1024 进位，`K`/`M`/`G` 亦可，不区分大小写），例如 "500MB"；
+/// 不带后缀的数字按字节计算
+pub fn parse_size(input: &str) -> Result<u64> {
+    let input = input.trim();
+    let (number, unit) = match input.find(|c: char| !c.is_ascii_digit()) {
+        Some(idx) => input.split_at(idx),
+        None => (input, "b"),
+    };
+    let value: u64 = number
+        .parse()
+        .map_err(|_| anyhow!("invalid size: {:?}", input))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "b" => 1,
+        "k" | "kb" => 1024,
+        "m" | "mb" => 1024 * 1024,
+        "g" | "gb" => 1024 * 1024 * 1024,
+        _ => return Err(anyhow!("invalid size unit: {:?}", input)),
+    };
+    value
+        .checked_mul(multiplier)
+        .ok_or_else(|| anyhow!("size too large: {:?}", input))
+}
+
+/// 将时长格式化为紧凑字符串，例如 "2d 5h"、"45m"
+pub fn format_duration_short(duration: chrono::Duration) -> String {
+    let days = duration.num_days();
+    let hours = duration.num_hours() % 24;
+    let minutes = duration.num_minutes() % 60;
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes.max(1))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -34,4 +114,48 @@ mod tests {
         let cache_dir = get_cache_dir();
         assert!(cache_dir.to_string_lossy().contains("devtool"));
     }
+
+    #[test]
+    fn test_is_valid_name() {
+        for name in [
+            "node@20",
+            "homebrew/cask",
+            "stable-x86_64-unknown-linux-gnu",
+            "cargo:ripgrep",
+            "npm:@scope/pkg",
+            "1.80.0",
+        ] {
+            assert!(is_valid_name(name), "{}", name);
+        }
+        for name in ["", "--force", "jq; rm -rf ~", "a b", "$(id)", "a`b`"] {
+            assert!(!is_valid_name(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("7d").unwrap(), chrono::Duration::days(7));
+        assert_eq!(parse_duration("36h").unwrap(), chrono::Duration::hours(36));
+        assert_eq!(parse_duration("2w").unwrap(), chrono::Duration::weeks(2));
+        assert_eq!(parse_duration("3").unwrap(), chrono::Duration::days(3));
+        assert!(parse_duration("abc").is_err());
+        assert!(parse_duration("5y").is_err());
+        assert!(parse_duration("9223372036854775807w").is_err());
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("512").unwrap(), 512);
+        assert_eq!(parse_size("500MB").unwrap(), 500 * 1024 * 1024);
+        assert_eq!(parse_size("2 gb").unwrap(), 2 * 1024 * 1024 * 1024);
+        assert_eq!(parse_size("64k").unwrap(), 64 * 1024);
+        assert!(parse_size("MB").is_err());
+        assert!(parse_size("5TB").is_err());
+    }
+
+    #[test]
+    fn test_format_duration_short() {
+        assert_eq!(format_duration_short(chrono::Duration::hours(53)), "2d 5h");
+        assert_eq!(format_duration_short(chrono::Duration::minutes(45)), "45m");
+    }
 }
@@ -0,0 +1,485 @@
+// 版本号模块
+// 宽松的版本号解析、比较与版本约束匹配
+//
+// Homebrew、Mise、Rustup 的版本号并不都遵循语义化版本（例如 "1.24.5_1"、"2024a"、
+// "1.73.0-nightly"），因此这里按分隔符拆分为片段，数字片段按数值比较，其余按字符串比较。
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// 版本号片段
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Number(u64),
+    Text(String),
+}
+
+impl PartialOrd for Segment {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Segment {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Segment::Number(a), Segment::Number(b)) => a.cmp(b),
+            (Segment::Text(a), Segment::Text(b)) => a.cmp(b),
+            // 数字片段排在文本片段之后（1.0.0 > 1.0.0-beta 的近似）
+            (Segment::Number(_), Segment::Text(_)) => Ordering::Greater,
+            (Segment::Text(_), Segment::Number(_)) => Ordering::Less,
+        }
+    }
+}
+
+/// 宽松解析的版本号
+///
+/// 相等性与排序一致，"1.2" 与 "1.2.0" 视为相等
+#[derive(Debug, Clone)]
+pub struct Version {
+    raw: String,
+    segments: Vec<Segment>,
+}
+
+impl Version {
+    /// 解析版本号，忽略前缀 "v"
+    pub fn parse(input: &str) -> Self {
+        let raw = input.trim().to_string();
+        let body = raw.strip_prefix('v').unwrap_or(&raw);
+        let segments = body
+            .split(['.', '-', '_', '+'])
+            .filter(|s| !s.is_empty())
+            .map(|s| match s.parse::<u64>() {
+                Ok(n) => Segment::Number(n),
+                Err(_) => Segment::Text(s.to_string()),
+            })
+            .collect();
+        Self { raw, segments }
+    }
+
+    /// 原始版本字符串
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// 第 `index` 个数字片段（主版本号为 0）
+    fn number(&self, index: usize) -> Option<u64> {
+        match self.segments.get(index) {
+            Some(Segment::Number(n)) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// 第 `index` 个片段的数值，缺失的片段视为 0，非数字片段返回 `None`
+    fn padded_number(&self, index: usize) -> Option<u64> {
+        match self.segments.get(index) {
+            None => Some(0),
+            Some(Segment::Number(n)) => Some(*n),
+            Some(Segment::Text(_)) => None,
+        }
+    }
+
+    /// 判断 `prefix` 的所有片段是否为本版本的前缀，例如 "20" 匹配 "20.11.0"
+    pub fn starts_with(&self, prefix: &Version) -> bool {
+        prefix.segments.len() <= self.segments.len()
+            && prefix
+                .segments
+                .iter()
+                .zip(&self.segments)
+                .all(|(a, b)| a == b)
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.segments.len().max(other.segments.len());
+        for i in 0..len {
+            // 缺失的片段视为 0，使 "1.2" 等于 "1.2.0"
+            let zero = Segment::Number(0);
+            let a = self.segments.get(i).unwrap_or(&zero);
+            let b = other.segments.get(i).unwrap_or(&zero);
+            match a.cmp(b) {
+                Ordering::Equal => continue,
+                ord => return ord,
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// 两个版本之间的差距，按语义化版本分级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionDistance {
+    /// 主版本号不同
+    Major,
+    /// 次版本号不同
+    Minor,
+    /// 修订号不同
+    Patch,
+    /// 仅预发布/构建后缀不同，或无法按数字比较
+    Other,
+}
+
+impl fmt::Display for VersionDistance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            VersionDistance::Major => "major",
+            VersionDistance::Minor => "minor",
+            VersionDistance::Patch => "patch",
+            VersionDistance::Other => "other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Version {
+    /// 计算与另一版本的差距，版本相等时返回 `None`
+    pub fn distance(&self, other: &Version) -> Option<VersionDistance> {
+        if self == other {
+            return None;
+        }
+        let levels = [
+            VersionDistance::Major,
+            VersionDistance::Minor,
+            VersionDistance::Patch,
+        ];
+        for (index, level) in levels.into_iter().enumerate() {
+            match (self.padded_number(index), other.padded_number(index)) {
+                (Some(a), Some(b)) if a == b => continue,
+                (Some(_), Some(_)) => return Some(level),
+                _ => return Some(VersionDistance::Other),
+            }
+        }
+        Some(VersionDistance::Other)
+    }
+}
+
+/// 单个版本比较条件
+#[derive(Debug, Clone, PartialEq)]
+enum Comparator {
+    /// 任意版本（"*" 或 "latest"）
+    Any,
+    /// 精确或前缀匹配，例如 "1.80.0"、"20"
+    Exact(Version),
+    Greater(Version),
+    GreaterEq(Version),
+    Less(Version),
+    LessEq(Version),
+    /// 兼容版本 "^1.2"：不低于指定版本，且第一个非零片段相同（"^0.2" 不含 0.3）
+    Caret(Version),
+    /// 近似版本 "~1.2"：主次版本号相同且不低于指定版本（"~1" 为主版本号相同）
+    Tilde(Version),
+}
+
+/// 把前 `len` 个数字片段中的最后一个加一作为上界，例如 ("1.2.3", 2) 得到 "1.3"
+fn bump(version: &Version, len: usize) -> Option<Version> {
+    let mut numbers = (0..len)
+        .map(|index| version.number(index))
+        .collect::<Option<Vec<u64>>>()?;
+    *numbers.last_mut()? += 1;
+    let numbers: Vec<String> = numbers.iter().map(u64::to_string).collect();
+    Some(Version::parse(&numbers.join(".")))
+}
+
+/// 指定的数字片段数量（最多主、次、修订三段）
+fn numeric_len(version: &Version) -> usize {
+    (0..3)
+        .take_while(|&index| version.number(index).is_some())
+        .count()
+}
+
+/// "^" 固定的片段数量：到第一个非零片段为止，全为零时为全部片段，例如 "^0.2.3" 为 2
+fn caret_len(version: &Version) -> usize {
+    let len = numeric_len(version);
+    (0..len)
+        .find(|&index| version.number(index) != Some(0))
+        .map_or(len, |index| index + 1)
+}
+
+/// "^" 的上界（不含）：固定片段中的最后一个加一
+fn caret_upper(version: &Version) -> Option<Version> {
+    bump(version, caret_len(version))
+}
+
+/// "~" 的上界（不含）：指定了次版本号时次版本号加一，否则主版本号加一
+fn tilde_upper(version: &Version) -> Option<Version> {
+    bump(version, numeric_len(version).clamp(1, 2))
+}
+
+impl Comparator {
+    fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(anyhow!("empty version requirement"));
+        }
+        if input == "*" || input.eq_ignore_ascii_case("latest") {
+            return Ok(Comparator::Any);
+        }
+        let (op, rest) = if let Some(rest) = input.strip_prefix(">=") {
+            (">=", rest)
+        } else if let Some(rest) = input.strip_prefix("<=") {
+            ("<=", rest)
+        } else if let Some(rest) = input.strip_prefix('>') {
+            (">", rest)
+        } else if let Some(rest) = input.strip_prefix('<') {
+            ("<", rest)
+        } else if let Some(rest) = input.strip_prefix('^') {
+            ("^", rest)
+        } else if let Some(rest) = input.strip_prefix('~') {
+            ("~", rest)
+        } else if let Some(rest) = input.strip_prefix('=') {
+            ("=", rest)
+        } else {
+            ("=", input)
+        };
+        let rest = rest.trim();
+        if rest.is_empty() {
+            return Err(anyhow!("missing version after {:?}", op));
+        }
+        let version = Version::parse(rest);
+        Ok(match op {
+            ">=" => Comparator::GreaterEq(version),
+            "<=" => Comparator::LessEq(version),
+            ">" => Comparator::Greater(version),
+            "<" => Comparator::Less(version),
+            "^" => Comparator::Caret(version),
+            "~" => Comparator::Tilde(version),
+            _ => Comparator::Exact(version),
+        })
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        match self {
+            Comparator::Any => true,
+            Comparator::Exact(v) => version.starts_with(v),
+            Comparator::Greater(v) => version > v,
+            Comparator::GreaterEq(v) => version >= v,
+            Comparator::Less(v) => version < v,
+            Comparator::LessEq(v) => version <= v,
+            Comparator::Caret(v) => {
+                version >= v && caret_upper(v).is_none_or(|upper| *version < upper)
+            }
+            Comparator::Tilde(v) => {
+                version >= v && tilde_upper(v).is_none_or(|upper| *version < upper)
+            }
+        }
+    }
+
+    /// 版本是否高于该条件的上界（没有上界的条件总是 `false`）
+    fn exceeded_by(&self, version: &Version) -> bool {
+        match self {
+            Comparator::Any | Comparator::Greater(_) | Comparator::GreaterEq(_) => false,
+            Comparator::Exact(v) => version > v && !version.starts_with(v),
+            Comparator::Less(v) => version >= v,
+            Comparator::LessEq(v) => version > v,
+            Comparator::Caret(v) => caret_upper(v).is_some_and(|upper| *version >= upper),
+            Comparator::Tilde(v) => tilde_upper(v).is_some_and(|upper| *version >= upper),
+        }
+    }
+}
+
+/// 版本约束，多个条件以逗号分隔，需全部满足，例如 ">=1.78, <2"
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionReq {
+    raw: String,
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    /// 解析版本约束
+    pub fn parse(input: &str) -> Result<Self> {
+        let comparators = input
+            .split(',')
+            .map(Comparator::parse)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            raw: input.trim().to_string(),
+            comparators,
+        })
+    }
+
+    /// 判断版本是否满足约束
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|c| c.matches(version))
+    }
+
+    /// 版本是否高于约束的某个上界，此时升级无法满足约束，只能降级
+    pub fn exceeded_by(&self, version: &Version) -> bool {
+        self.comparators.iter().any(|c| c.exceeded_by(version))
+    }
+
+    /// 若约束是单个精确/前缀版本，返回该版本（可直接用于安装）
+    pub fn exact(&self) -> Option<&Version> {
+        match self.comparators.as_slice() {
+            [Comparator::Exact(v)] => Some(v),
+            _ => None,
+        }
+    }
+
+    /// 可直接交给包管理器安装的版本提示
+    ///
+    /// 精确版本原样返回；"^1.2" 返回 "1"，"^0.2.3" 返回 "0.2"，"~1.2" 返回 "1.2"；
+    /// 其他范围无法确定时返回 `None`
+    pub fn install_hint(&self) -> Option<String> {
+        match self.comparators.as_slice() {
+            [Comparator::Exact(v)] => Some(v.as_str().trim_start_matches('v').to_string()),
+            [Comparator::Caret(v)] => {
+                let prefix: Vec<String> = (0..caret_len(v))
+                    .filter_map(|index| v.number(index))
+                    .map(|n| n.to_string())
+                    .collect();
+                (!prefix.is_empty()).then(|| prefix.join("."))
+            }
+            [Comparator::Tilde(v)] => match (v.number(0), v.number(1)) {
+                (Some(major), Some(minor)) => Some(format!("{}.{}", major, minor)),
+                (Some(major), None) => Some(major.to_string()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(s: &str) -> Version {
+        Version::parse(s)
+    }
+
+    fn req(s: &str) -> VersionReq {
+        VersionReq::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        assert!(v("1.10.0") > v("1.9.0"));
+        assert!(v("1.2") == v("1.2.0"));
+        assert!(v("v2.0.0") > v("1.99.99"));
+        assert!(v("1.24.5_1") > v("1.24.5"));
+        assert!(v("1.73.0") > v("1.73.0-nightly"));
+    }
+
+    #[test]
+    fn test_version_distance() {
+        assert_eq!(v("1.2.3").distance(&v("1.2.3")), None);
+        assert_eq!(
+            v("1.2.3").distance(&v("2.0.0")),
+            Some(VersionDistance::Major)
+        );
+        assert_eq!(
+            v("1.2.3").distance(&v("1.4.0")),
+            Some(VersionDistance::Minor)
+        );
+        assert_eq!(
+            v("1.2.3").distance(&v("1.2.4")),
+            Some(VersionDistance::Patch)
+        );
+        assert_eq!(v("1.2").distance(&v("1.2.1")), Some(VersionDistance::Patch));
+        assert_eq!(
+            v("1.24.5").distance(&v("1.24.5_1")),
+            Some(VersionDistance::Other)
+        );
+        assert_eq!(
+            v("stable").distance(&v("1.80.0")),
+            Some(VersionDistance::Other)
+        );
+    }
+
+    #[test]
+    fn test_exact_and_prefix_requirements() {
+        assert!(req("1.80.0").matches(&v("1.80.0")));
+        assert!(!req("1.80.0").matches(&v("1.80.1")));
+        assert!(req("20").matches(&v("20.11.0")));
+        assert!(!req("20").matches(&v("21.0.0")));
+        assert_eq!(req("=1.80.0").exact(), Some(&v("1.80.0")));
+        assert!(req(">=1.80").exact().is_none());
+    }
+
+    #[test]
+    fn test_range_requirements() {
+        assert!(req(">=14").matches(&v("14.1.0")));
+        assert!(!req(">=14").matches(&v("13.0.0")));
+        assert!(req(">=1.78, <2").matches(&v("1.80.0")));
+        assert!(!req(">=1.78, <2").matches(&v("2.0.0")));
+        assert!(req("^3.11").matches(&v("3.12.1")));
+        assert!(!req("^3.11").matches(&v("4.0.0")));
+        assert!(req("~3.11").matches(&v("3.11.7")));
+        assert!(!req("~3.11").matches(&v("3.12.0")));
+        assert!(req("*").matches(&v("0.1.0")));
+        assert_eq!(req("^3.11").install_hint().as_deref(), Some("3"));
+        assert_eq!(req("~3.11.2").install_hint().as_deref(), Some("3.11"));
+        assert_eq!(req(">=14").install_hint(), None);
+        assert!(req("latest").matches(&v("0.0.1")));
+        assert!(req("~1").matches(&v("1.9.0")));
+        assert!(!req("~1").matches(&v("2.0.0")));
+    }
+
+    #[test]
+    fn test_caret_zero_major() {
+        assert!(req("^0.2.3").matches(&v("0.2.9")));
+        assert!(!req("^0.2.3").matches(&v("0.3.0")));
+        assert!(!req("^0.2.3").matches(&v("0.2.2")));
+        assert!(req("^0.0.3").matches(&v("0.0.3")));
+        assert!(!req("^0.0.3").matches(&v("0.0.4")));
+        assert!(req("^0").matches(&v("0.9.0")));
+        assert!(!req("^0").matches(&v("1.0.0")));
+        assert_eq!(req("^0.2.3").install_hint().as_deref(), Some("0.2"));
+        assert_eq!(req("^0.0.3").install_hint().as_deref(), Some("0.0.3"));
+        assert_eq!(req("^1.80").install_hint().as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn test_upper_bounds() {
+        assert!(req("<2.40").exceeded_by(&v("2.45.0")));
+        assert!(req("<2.40").exceeded_by(&v("2.40")));
+        assert!(!req("<2.40").exceeded_by(&v("2.39.1")));
+        assert!(req("<=2.40").exceeded_by(&v("2.40.1")));
+        assert!(req(">=1.78, <2").exceeded_by(&v("2.1.0")));
+        assert!(!req(">=1.78, <2").exceeded_by(&v("1.70.0")));
+        assert!(req("1.7.1").exceeded_by(&v("1.8.0")));
+        assert!(!req("20").exceeded_by(&v("20.11.0")));
+        assert!(req("20").exceeded_by(&v("21.0.0")));
+        assert!(req("^1.80").exceeded_by(&v("2.0.0")));
+        assert!(!req("^1.80").exceeded_by(&v("1.79.0")));
+        assert!(req("^0.2").exceeded_by(&v("0.3.1")));
+        assert!(req("~3.11").exceeded_by(&v("3.12.0")));
+        assert!(!req(">=14").exceeded_by(&v("99.0.0")));
+    }
+
+    #[test]
+    fn test_invalid_requirements() {
+        assert!(VersionReq::parse("").is_err());
+        assert!(VersionReq::parse(">=").is_err());
+    }
+}